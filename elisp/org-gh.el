@@ -197,9 +197,10 @@ REPO should be in the format \"owner/repo\"."
              (synced (alist-get 'synced-count result))
              (pending (length (alist-get 'pending-creates result)))
              (local (length (alist-get 'local-changes result)))
-             (remote (length (alist-get 'remote-changes result))))
-         (message "%s: %d synced, %d pending, %d local changes, %d remote changes"
-                  repo synced pending local remote)))
+             (remote (length (alist-get 'remote-changes result)))
+             (prs (length (alist-get 'linked-pull-requests result))))
+         (message "%s: %d synced, %d pending, %d local changes, %d remote changes, %d linked PRs"
+                  repo synced pending local remote prs)))
      (lambda (err)
        (message "org-gh status failed: %s" err)))))
 
@@ -0,0 +1,106 @@
+use crate::config::Config;
+use crate::error::{Error, Result};
+use crate::github::GitHubClient;
+use crate::output::{format, CloneOutput, Format};
+use crate::sync::import::{clone_file, import_query, GroupBy};
+use crate::sync::SyncState;
+use clap::Args as ClapArgs;
+use std::path::PathBuf;
+
+#[derive(ClapArgs)]
+pub struct Args {
+    /// GitHub repository (owner/repo)
+    pub repo: String,
+
+    /// Path of the org file to create
+    pub file: PathBuf,
+
+    /// GitHub search filter for the issues to include (e.g. "label:roadmap")
+    #[arg(long)]
+    pub filter: Option<String>,
+
+    /// Include closed issues
+    #[arg(long)]
+    pub closed: bool,
+
+    /// Group issues under a heading per milestone or label
+    #[arg(long, value_enum, default_value_t = GroupBy::Milestone)]
+    pub group_by: GroupBy,
+
+    /// Overwrite the file if it exists
+    #[arg(long, short)]
+    pub force: bool,
+}
+
+pub async fn run(args: Args, output_format: Format) -> Result<()> {
+    // Validate repo format
+    if !args.repo.contains('/') || args.repo.split('/').count() != 2 {
+        return Err(Error::Config(format!(
+            "Invalid repository format: {}. Expected owner/repo",
+            args.repo
+        )));
+    }
+
+    if args.file.exists() && !args.force {
+        return Err(Error::Io(std::io::Error::new(
+            std::io::ErrorKind::AlreadyExists,
+            format!(
+                "File already exists: {} (use --force to overwrite)",
+                args.file.display()
+            ),
+        )));
+    }
+
+    let is_human = output_format == Format::Human;
+
+    let config = Config::load()?;
+    let token = config.github_token()?;
+    let client = GitHubClient::new(&token, &args.repo).await?;
+
+    if is_human {
+        println!("Fetching issues from {}...", args.repo);
+    }
+
+    // Issues changed after this are picked up by the first sync
+    let started = chrono::Utc::now();
+    let issues = match &args.filter {
+        Some(filter) if args.closed => client.search_issues(filter).await?,
+        Some(filter) => client.search_issues(&import_query(filter)).await?,
+        None => client
+            .fetch_issues()
+            .await?
+            .into_iter()
+            .filter(|issue| args.closed || issue.state.is_open())
+            .collect(),
+    };
+
+    let numbers: Vec<u64> = issues.iter().map(|issue| issue.number).collect();
+    let comments = client.fetch_comments_for(&numbers, None).await?;
+
+    let (content, mut state) = clone_file(&args.repo, &issues, &comments, args.group_by, &config);
+    state.last_sync = Some(started);
+
+    std::fs::write(&args.file, content)?;
+    state.save(&args.file)?;
+
+    if is_human {
+        println!("Wrote {} issues to {}", issues.len(), args.file.display());
+        println!(
+            "Created sync state: {}",
+            SyncState::state_path(&args.file).display()
+        );
+        println!(
+            "\nClone complete. Run 'org-gh sync {}' to sync.",
+            args.file.display()
+        );
+    } else {
+        let output = CloneOutput {
+            file: args.file.display().to_string(),
+            repo: args.repo,
+            issues: issues.len(),
+        };
+        print!("{}", format(&output, output_format));
+    }
+
+    Ok(())
+}
@@ -52,8 +52,9 @@ pub async fn run(args: Args, output_format: Format) -> Result<()> {
     }
     let client = GitHubClient::new(&token, &args.repo).await?;
 
-    // Verify repo exists by fetching issues (will error if no access)
-    match client.fetch_issues().await {
+    // Verify repo exists by listing issues (will error if no access). Only
+    // ask for issues updated from now on so large repos stay a single request.
+    match client.fetch_issues_since(Some(chrono::Utc::now())).await {
         Ok(_) => {
             if is_human {
                 println!("OK");
@@ -1,6 +1,8 @@
+pub mod clone;
 pub mod init;
 pub mod pull;
 pub mod push;
+mod resolve;
 pub mod status;
 pub mod sync;
 pub mod unlink;
@@ -55,6 +57,8 @@ impl Cli {
 pub enum Command {
     /// Initialize sync for an org file
     Init(init::Args),
+    /// Create an org file from a repository's issues
+    Clone(clone::Args),
     /// Push org changes to GitHub
     Push(push::Args),
     /// Pull GitHub changes to org
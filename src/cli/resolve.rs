@@ -0,0 +1,258 @@
+//! Interactive conflict resolution
+//!
+//! Conflicts left unresolved by `--force` and the configured per-field
+//! strategies are offered to the user one field at a time, deleted
+//! headings under `deleted_heading = "prompt"` one heading at a time, and
+//! headings to archive under `archive_closed = "prompt"` likewise.
+//! Without a terminal they stay conflicts and warnings, and nothing is
+//! archived.
+
+use crate::config::SyncConfig;
+use crate::error::Result;
+use crate::org::markdown::{title_to_markdown, to_markdown};
+use crate::org::model::TodoKeywords;
+use crate::sync::diff::Field;
+use crate::sync::engine::{field_values, Direction, SyncAction};
+use console::{pad_str, Alignment, Term};
+use dialoguer::{Confirm, Editor, Input, Select};
+use std::io::IsTerminal;
+
+/// Whether conflicts can be resolved by prompting the user
+pub(crate) fn can_prompt() -> bool {
+    std::io::stdin().is_terminal() && std::io::stdout().is_terminal()
+}
+
+/// Prompt for every conflicting field, turning resolved conflicts into updates
+pub(crate) fn resolve_conflicts(
+    actions: Vec<SyncAction>,
+    config: &SyncConfig,
+    keywords: &TodoKeywords,
+) -> Result<Vec<SyncAction>> {
+    let mut resolved = Vec::with_capacity(actions.len());
+
+    for action in actions {
+        let SyncAction::Conflict {
+            issue_number,
+            fields,
+            org_item,
+            gh_issue,
+            ..
+        } = &action
+        else {
+            resolved.push(action);
+            continue;
+        };
+
+        let mut choices = Vec::new();
+        for &field in fields {
+            let (org, gh) = field_values(field, org_item, gh_issue, config);
+            println!();
+            println!(
+                "Conflict #{}: {} ({})",
+                issue_number,
+                org_item.title,
+                field.name()
+            );
+            print!(
+                "{}",
+                side_by_side(&org, &gh, Term::stdout().size().1 as usize)
+            );
+
+            if let Some(direction) = prompt_field(field, &org, keywords)? {
+                choices.push((field, direction));
+            }
+        }
+        resolved.push(action.resolve(choices));
+    }
+
+    Ok(resolved)
+}
+
+/// Ask what to do about each deleted heading's issue
+pub(crate) fn resolve_deleted(actions: Vec<SyncAction>) -> Result<Vec<SyncAction>> {
+    let mut resolved = Vec::with_capacity(actions.len());
+
+    for action in actions {
+        let SyncAction::DeletedHeading {
+            issue_number,
+            title,
+        } = action
+        else {
+            resolved.push(action);
+            continue;
+        };
+
+        println!();
+        println!("Heading deleted for #{}: {}", issue_number, title);
+        let choice = Select::new()
+            .with_prompt("Issue")
+            .items(&["Keep tracking", "Unlink", "Close as not planned"])
+            .default(0)
+            .interact()?;
+
+        resolved.push(match choice {
+            0 => SyncAction::DeletedHeading {
+                issue_number,
+                title,
+            },
+            choice => SyncAction::Untrack {
+                issue_number,
+                title,
+                close: choice == 2,
+                moved_to: None,
+            },
+        });
+    }
+
+    Ok(resolved)
+}
+
+/// Ask whether to archive each heading whose issue was closed
+pub(crate) fn resolve_archive(actions: Vec<SyncAction>) -> Result<Vec<SyncAction>> {
+    let mut resolved = Vec::with_capacity(actions.len());
+
+    for action in actions {
+        let SyncAction::ArchiveHeading {
+            issue_number,
+            org_item,
+            confirmed: false,
+        } = action
+        else {
+            resolved.push(action);
+            continue;
+        };
+
+        println!();
+        let confirmed = Confirm::new()
+            .with_prompt(format!(
+                "#{} was closed on GitHub. Archive \"{}\"?",
+                issue_number, org_item.title
+            ))
+            .default(false)
+            .interact()?;
+
+        resolved.push(SyncAction::ArchiveHeading {
+            issue_number,
+            org_item,
+            confirmed,
+        });
+    }
+
+    Ok(resolved)
+}
+
+/// Ask how to resolve one field (`None` leaves it conflicted)
+fn prompt_field(field: Field, org: &str, keywords: &TodoKeywords) -> Result<Option<Direction>> {
+    let choice = Select::new()
+        .with_prompt("Resolve")
+        .items(&["Keep org", "Keep GitHub", "Edit", "Skip"])
+        .default(0)
+        .interact()?;
+
+    Ok(match choice {
+        0 => Some(Direction::Push),
+        1 => Some(Direction::Pull),
+        2 => edit_field(field, org, keywords)?.map(Direction::Edit),
+        _ => None,
+    })
+}
+
+/// Let the user write the value both sides should get
+///
+/// The title and body are edited as org text and handed on as Markdown.
+fn edit_field(field: Field, org: &str, keywords: &TodoKeywords) -> Result<Option<String>> {
+    match field {
+        Field::Body => Ok(Editor::new()
+            .edit(org)?
+            .map(|body| edited_value(field, &body))),
+        Field::State => {
+            let all: Vec<&String> = keywords.todo.iter().chain(&keywords.done).collect();
+            let current = all.iter().position(|k| *k == org).unwrap_or(0);
+            let choice = Select::new()
+                .with_prompt("State")
+                .items(&all)
+                .default(current)
+                .interact()?;
+            Ok(Some(all[choice].clone()))
+        }
+        Field::Title | Field::Assignees | Field::Labels => {
+            let value = Input::<String>::new()
+                .with_prompt(field.name())
+                .with_initial_text(org)
+                .allow_empty(field != Field::Title)
+                .interact_text()?;
+            Ok(Some(edited_value(field, &value)))
+        }
+    }
+}
+
+/// An edited org value as the engine takes it: Markdown for the title and body
+fn edited_value(field: Field, value: &str) -> String {
+    match field {
+        Field::Title => title_to_markdown(value),
+        Field::Body => to_markdown(value),
+        Field::State | Field::Assignees | Field::Labels => value.to_string(),
+    }
+}
+
+/// Render org and GitHub values in two columns of a `width`-wide terminal
+fn side_by_side(org: &str, gh: &str, width: usize) -> String {
+    let column = (width.max(40) - 3) / 2;
+    let org_lines: Vec<&str> = org.lines().collect();
+    let gh_lines: Vec<&str> = gh.lines().collect();
+    let rows = org_lines.len().max(gh_lines.len());
+
+    let mut out = format!(
+        "{} | {}\n{}-+-{}\n",
+        pad_str("org", column, Alignment::Left, None),
+        "GitHub",
+        "-".repeat(column),
+        "-".repeat(column)
+    );
+    for row in 0..rows {
+        let left = org_lines.get(row).copied().unwrap_or("");
+        let right = gh_lines.get(row).copied().unwrap_or("");
+        let line = format!(
+            "{} | {}",
+            pad_str(left, column, Alignment::Left, Some("…")),
+            pad_str(right, column, Alignment::Left, Some("…"))
+        );
+        out.push_str(line.trim_end());
+        out.push('\n');
+    }
+    out
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_side_by_side() {
+        let out = side_by_side("Org title\nsecond line", "GitHub title", 43);
+        let lines: Vec<&str> = out.lines().collect();
+        assert_eq!(lines[0], "org                  | GitHub");
+        assert_eq!(lines[2], "Org title            | GitHub title");
+        assert_eq!(lines[3], "second line          |");
+    }
+
+    #[test]
+    fn test_edited_value_converts_org_markup() {
+        assert_eq!(
+            edited_value(Field::Title, "Fix =parse()= in *core*"),
+            "Fix `parse()` in **core**"
+        );
+        assert_eq!(
+            edited_value(Field::Body, "See [[https://example.com][docs]]"),
+            "See [docs](https://example.com)"
+        );
+        assert_eq!(edited_value(Field::Labels, "bug, *ui*"), "bug, *ui*");
+    }
+
+    #[test]
+    fn test_side_by_side_truncates() {
+        let out = side_by_side(&"x".repeat(50), "short", 43);
+        let lines: Vec<&str> = out.lines().collect();
+        assert_eq!(lines[2], format!("{}… | short", "x".repeat(19)));
+    }
+}
@@ -1,9 +1,10 @@
 use crate::config::Config;
 use crate::error::{Error, Result};
-use crate::github::GitHubClient;
-use crate::org::parse_file;
+use crate::org::parse_file_with;
 use crate::output::{format, Format, StatusOutput};
-use crate::sync::SyncState;
+use crate::sync::diff::Field;
+use crate::sync::engine::{Direction, SyncAction};
+use crate::sync::SyncEngine;
 use clap::Args as ClapArgs;
 use std::path::PathBuf;
 
@@ -14,81 +15,157 @@ pub struct Args {
 }
 
 pub async fn run(args: Args, output_format: Format) -> Result<()> {
-    // Parse org file
-    let org_file = parse_file(&args.file)?;
-
-    let repo = org_file.repo.as_ref().ok_or(Error::NoRepo)?;
-
-    // Load sync state
-    let state = SyncState::load(&args.file)?;
-
-    // Load config and fetch GitHub data
+    // Load config (TODO keywords affect parsing)
     let config = Config::load()?;
-    let token = config.github_token()?;
-    let client = GitHubClient::new(&token, repo).await?;
 
-    // Count items by status
-    let synced_count = state.items.len();
-    let pending_creates: Vec<String> = org_file
-        .items
-        .iter()
-        .filter(|item| item.gh_issue.is_none())
-        .map(|item| item.title.clone())
-        .collect();
+    // Parse org file
+    let org_file = parse_file_with(&args.file, &config.org)?;
 
-    // Fetch remote state and compare
-    let gh_issues = client.fetch_issues().await?;
+    let repo = org_file.repo.clone().ok_or(Error::NoRepo)?;
 
-    // Find local changes (items that differ from base state)
+    // Plan a sync without executing it
+    let mut engine = SyncEngine::new(config, &org_file).await?;
+    let actions = engine.plan(&org_file, false).await?;
+
+    let mut pending_creates = Vec::new();
     let mut local_changes = Vec::new();
     let mut remote_changes = Vec::new();
 
-    for item in &org_file.items {
-        if let Some(issue_num) = item.gh_issue {
-            if let Some(base) = state.items.get(&issue_num) {
-                // Check if org changed from base
-                if item.title != base.title {
-                    local_changes.push(format!("#{}: title changed", issue_num));
+    for action in &actions {
+        match action {
+            SyncAction::CreateIssue { org_item } => pending_creates.push(org_item.title.clone()),
+            SyncAction::Update {
+                issue_number,
+                org_item,
+                fields,
+                ..
+            } => {
+                for (field, direction) in fields {
+                    let change = match (field, direction) {
+                        (Field::State, Direction::Push) => {
+                            format!("marked {}", org_item.state.keyword())
+                        }
+                        _ => format!("{} changed", field.name()),
+                    };
+                    if *direction != Direction::Pull {
+                        local_changes.push(format!("#{}: {}", issue_number, change));
+                    }
+                    if *direction != Direction::Push {
+                        remote_changes.push(format!("#{}: {}", issue_number, change));
+                    }
                 }
-                let org_state = if item.state.is_open() {
-                    "open"
-                } else {
-                    "closed"
+            }
+            SyncAction::PullComments {
+                issue_number,
+                entries,
+                ..
+            } if !entries.is_empty() => {
+                remote_changes.push(format!("#{}: {} new comments", issue_number, entries.len()));
+            }
+            SyncAction::PostNotes {
+                issue_number,
+                notes,
+            } => {
+                local_changes.push(format!("#{}: {} new notes", issue_number, notes.len()));
+            }
+            SyncAction::SetParent {
+                issue_number,
+                parent,
+                ..
+            } => {
+                let change = match parent {
+                    Some(parent) => format!("#{}: moved under #{}", issue_number, parent),
+                    None => format!("#{}: moved to the top level", issue_number),
                 };
-                if org_state != base.state {
-                    local_changes.push(format!(
-                        "#{}: marked {}",
-                        issue_num,
-                        org_state.to_uppercase()
-                    ));
+                local_changes.push(change);
+            }
+            SyncAction::Conflict {
+                issue_number,
+                fields,
+                ..
+            } => {
+                for field in fields {
+                    let change =
+                        format!("#{}: {} changed on both sides", issue_number, field.name());
+                    local_changes.push(change.clone());
+                    remote_changes.push(change);
                 }
             }
-
-            // Check if GitHub changed from base
-            if let Some(gh) = gh_issues.iter().find(|i| i.number == issue_num) {
-                if let Some(base) = state.items.get(&issue_num) {
-                    if gh.title != base.title {
-                        remote_changes.push(format!("#{}: title changed", issue_num));
-                    }
-                    let gh_state = if gh.state.is_open() { "open" } else { "closed" };
-                    if gh_state != base.state {
-                        remote_changes.push(format!("#{}: now {}", issue_num, gh_state));
+            SyncAction::ImportIssue { gh_issue } => {
+                remote_changes.push(format!("#{}: new issue to import", gh_issue.number));
+            }
+            SyncAction::SetDates {
+                issue_number,
+                changes,
+                ..
+            } => {
+                for change in changes {
+                    let line = format!(
+                        "#{}: {} changed",
+                        issue_number,
+                        change.date.keyword().to_lowercase()
+                    );
+                    if change.direction == Direction::Push {
+                        local_changes.push(line);
+                    } else {
+                        remote_changes.push(line);
                     }
                 }
             }
+            SyncAction::Untrack {
+                issue_number,
+                close,
+                moved_to,
+                ..
+            } => {
+                let change = match (moved_to, close) {
+                    (Some(path), _) => format!("#{}: moved to {}", issue_number, path.display()),
+                    (None, true) => format!("#{}: heading deleted, to close", issue_number),
+                    (None, false) => format!("#{}: heading deleted, to unlink", issue_number),
+                };
+                local_changes.push(change);
+            }
+            SyncAction::Link {
+                issue_number,
+                relink: true,
+                ..
+            } => {
+                local_changes.push(format!("#{}: GH_ISSUE to restore", issue_number));
+            }
+            SyncAction::CloseArchived { issue_number, .. } => {
+                local_changes.push(format!("#{}: archived, to close", issue_number));
+            }
+            SyncAction::ArchiveHeading { issue_number, .. } => {
+                remote_changes.push(format!("#{}: closed, to archive", issue_number));
+            }
+            SyncAction::Warn { .. } | SyncAction::DeletedHeading { .. } => {
+                eprintln!("{}", action.describe())
+            }
+            _ => {}
         }
     }
 
+    let linked_pull_requests = engine
+        .linked_pull_requests(&org_file)
+        .into_iter()
+        .map(|(issue, pr)| {
+            let state = if pr.state.is_open() { "open" } else { "closed" };
+            format!("#{}: PR #{} ({}) {}", issue, pr.number, state, pr.title)
+        })
+        .collect();
+
+    let state = engine.state();
     let output = StatusOutput {
         file: args.file.display().to_string(),
-        repo: repo.clone(),
+        repo,
         last_sync: state
             .last_sync
             .map(|t| t.format("%Y-%m-%d %H:%M:%S UTC").to_string()),
-        synced_count,
+        synced_count: state.items.len(),
         pending_creates,
         local_changes,
         remote_changes,
+        linked_pull_requests,
     };
 
     print!("{}", format(&output, output_format));
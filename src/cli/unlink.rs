@@ -1,5 +1,6 @@
+use crate::config::Config;
 use crate::error::{Error, Result};
-use crate::org::{parse_file, write_file};
+use crate::org::{parse_file_with, write_file};
 use crate::output::{format, Format, UnlinkOutput};
 use crate::sync::SyncState;
 use clap::Args as ClapArgs;
@@ -19,8 +20,11 @@ pub struct Args {
 }
 
 pub async fn run(args: Args, output_format: Format) -> Result<()> {
+    // Load config (TODO keywords affect parsing)
+    let config = Config::load()?;
+
     // Parse org file
-    let mut org_file = parse_file(&args.file)?;
+    let mut org_file = parse_file_with(&args.file, &config.org)?;
 
     // Load sync state
     let mut state = SyncState::load(&args.file)?;
@@ -68,7 +72,6 @@ pub async fn run(args: Args, output_format: Format) -> Result<()> {
     if args.close {
         let repo = org_file.repo.clone().ok_or(Error::NoRepo)?;
 
-        let config = crate::config::Config::load()?;
         let token = config.github_token()?;
         let client = crate::github::GitHubClient::new(&token, &repo).await?;
 
@@ -77,6 +80,7 @@ pub async fn run(args: Args, output_format: Format) -> Result<()> {
             title: None,
             body: None,
             state: Some(GhIssueState::Closed),
+            state_reason: None,
             assignees: None,
             labels: None,
         };
@@ -1,5 +1,6 @@
 use crate::error::{Error, Result};
 use serde::{Deserialize, Serialize};
+use std::collections::BTreeMap;
 use std::path::PathBuf;
 
 #[derive(Debug, Clone, Serialize, Deserialize, Default)]
@@ -22,12 +23,43 @@ pub struct GitHubConfig {
 pub struct SyncConfig {
     pub doing_label: String,
     pub blocked_label: String,
+    #[serde(default = "default_waiting_label")]
+    pub waiting_label: String,
+    /// Labels added to every new issue; org leaves them to GitHub
     #[serde(default)]
     pub default_labels: Vec<String>,
+    /// Prefix added to labels pushed from org; GitHub labels without it are
+    /// left alone (`#+GH_LABEL_PREFIX:` sets it per file)
+    #[serde(default)]
+    pub label_prefix: Option<String>,
+    /// Label for each priority cookie (`A = "priority-high"`); an empty
+    /// table turns priority labels off
+    #[serde(default = "default_priority_labels")]
+    pub priority_labels: BTreeMap<String, String>,
     pub title_conflict: ConflictResolution,
     pub body_conflict: ConflictResolution,
     pub state_conflict: ConflictResolution,
     pub assignee_conflict: ConflictResolution,
+    /// Write unresolved conflicts into the org file as a `:GH_CONFLICT:` drawer
+    #[serde(default)]
+    pub conflict_markers: bool,
+    /// Projects board whose date fields mirror SCHEDULED and DEADLINE
+    #[serde(default)]
+    pub project: Option<ProjectConfig>,
+    /// Issue search filter (e.g. `label:bug assignee:me`) selecting issues to
+    /// import as new headings; unset leaves import off (`#+GH_IMPORT:` sets
+    /// it per file)
+    #[serde(default)]
+    pub import: Option<String>,
+    /// Heading that imported issues are filed under
+    #[serde(default = "default_import_heading")]
+    pub import_heading: String,
+    /// What to do when a linked heading is deleted from the file
+    #[serde(default)]
+    pub deleted_heading: DeletedHeading,
+    /// Whether to archive headings whose issue was closed on GitHub
+    #[serde(default)]
+    pub archive_closed: ArchiveClosed,
 }
 
 impl Default for SyncConfig {
@@ -35,19 +67,92 @@ impl Default for SyncConfig {
         Self {
             doing_label: "in-progress".to_string(),
             blocked_label: "blocked".to_string(),
+            waiting_label: default_waiting_label(),
             default_labels: vec![],
+            label_prefix: None,
+            priority_labels: default_priority_labels(),
             title_conflict: ConflictResolution::OrgWins,
             body_conflict: ConflictResolution::OrgWins,
             state_conflict: ConflictResolution::Prompt,
             assignee_conflict: ConflictResolution::GitHubWins,
+            conflict_markers: false,
+            project: None,
+            import: None,
+            import_heading: default_import_heading(),
+            deleted_heading: DeletedHeading::Warn,
+            archive_closed: ArchiveClosed::Never,
         }
     }
 }
 
+impl SyncConfig {
+    /// Open keywords that are mirrored on GitHub as a label
+    pub fn status_labels(&self) -> [(&'static str, &str); 3] {
+        [
+            ("DOING", &self.doing_label),
+            ("BLOCKED", &self.blocked_label),
+            ("WAITING", &self.waiting_label),
+        ]
+    }
+}
+
+/// A GitHub Projects board and the date fields synced with it
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct ProjectConfig {
+    /// User or organization owning the board
+    pub owner: String,
+    /// Board number, as in `github.com/orgs/<owner>/projects/<number>`
+    pub number: u64,
+    /// Date field mirroring SCHEDULED, e.g. "Start date"
+    #[serde(default)]
+    pub scheduled_field: Option<String>,
+    /// Date field mirroring DEADLINE, e.g. "Target date"
+    #[serde(default)]
+    pub deadline_field: Option<String>,
+}
+
+fn default_waiting_label() -> String {
+    "waiting".to_string()
+}
+
+fn default_import_heading() -> String {
+    "Inbox".to_string()
+}
+
+fn default_priority_labels() -> BTreeMap<String, String> {
+    [
+        ("A", "priority-high"),
+        ("B", "priority-medium"),
+        ("C", "priority-low"),
+    ]
+    .into_iter()
+    .map(|(priority, label)| (priority.to_string(), label.to_string()))
+    .collect()
+}
+
 #[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct OrgConfig {
     pub todo_keywords: Vec<String>,
     pub done_keywords: Vec<String>,
+    /// Done keywords that close an issue as "not planned"
+    #[serde(default = "default_cancelled_keywords")]
+    pub cancelled_keywords: Vec<String>,
+    /// Treat heading tags, including `#+FILETAGS` and tags inherited from
+    /// enclosing headings, as labels
+    #[serde(default)]
+    pub tags_as_labels: bool,
+    /// Where archived subtrees go when a file has no `#+ARCHIVE:` line, as
+    /// in `org-archive-location`
+    #[serde(default = "default_archive_location")]
+    pub archive_location: String,
+    /// Look for headings missing from a file in its archive, so archived
+    /// headings are not taken for deleted ones
+    #[serde(default = "default_scan_archive")]
+    pub scan_archive: bool,
+    /// Give every synced heading an org-id `:ID:`, so renaming it keeps its
+    /// identity
+    #[serde(default)]
+    pub assign_ids: bool,
 }
 
 impl Default for OrgConfig {
@@ -64,18 +169,70 @@ impl Default for OrgConfig {
                 "CANCELLED".to_string(),
                 "WONTFIX".to_string(),
             ],
+            cancelled_keywords: default_cancelled_keywords(),
+            tags_as_labels: false,
+            archive_location: default_archive_location(),
+            scan_archive: default_scan_archive(),
+            assign_ids: false,
         }
     }
 }
 
+fn default_archive_location() -> String {
+    "%s_archive::".to_string()
+}
+
+fn default_scan_archive() -> bool {
+    true
+}
+
+fn default_cancelled_keywords() -> Vec<String> {
+    vec![
+        "CANCELLED".to_string(),
+        "CANCELED".to_string(),
+        "WONTFIX".to_string(),
+    ]
+}
+
 #[derive(Debug, Clone, Copy, Serialize, Deserialize, PartialEq, Eq)]
 #[serde(rename_all = "kebab-case")]
 pub enum ConflictResolution {
     Prompt,
     OrgWins,
+    #[serde(rename = "github-wins", alias = "git-hub-wins")]
     GitHubWins,
 }
 
+/// Handling of issues whose heading was deleted from the file
+///
+/// Headings archived or moved to another file are not deleted.
+#[derive(Debug, Clone, Copy, Default, Serialize, Deserialize, PartialEq, Eq)]
+#[serde(rename_all = "kebab-case")]
+pub enum DeletedHeading {
+    /// Report it on every sync and keep the issue tracked
+    #[default]
+    Warn,
+    /// Stop tracking the issue and leave it open
+    Unlink,
+    /// Close the issue as not planned and stop tracking it
+    Close,
+    /// Ask which of the above to do
+    Prompt,
+}
+
+/// Archiving of headings whose issue was closed on GitHub
+#[derive(Debug, Clone, Copy, Default, Serialize, Deserialize, PartialEq, Eq)]
+#[serde(rename_all = "kebab-case")]
+pub enum ArchiveClosed {
+    /// Leave them where they are
+    #[default]
+    Never,
+    /// Ask for each heading
+    Prompt,
+    /// Archive them as the close is pulled
+    Always,
+}
+
 impl Config {
     pub fn load() -> Result<Self> {
         let path = Self::config_path()?;
@@ -124,3 +281,38 @@ impl Config {
         ))
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_conflict_resolution_values() {
+        let config: Config = toml::from_str(
+            r#"
+[sync]
+doing_label = "in-progress"
+blocked_label = "blocked"
+title_conflict = "org-wins"
+body_conflict = "prompt"
+state_conflict = "github-wins"
+assignee_conflict = "git-hub-wins"
+"#,
+        )
+        .unwrap();
+        assert_eq!(config.sync.title_conflict, ConflictResolution::OrgWins);
+        assert_eq!(config.sync.body_conflict, ConflictResolution::Prompt);
+        assert_eq!(config.sync.state_conflict, ConflictResolution::GitHubWins);
+        assert_eq!(
+            config.sync.assignee_conflict,
+            ConflictResolution::GitHubWins
+        );
+        assert_eq!(
+            toml::to_string(&config.sync)
+                .unwrap()
+                .lines()
+                .find(|l| l.starts_with("state_conflict")),
+            Some("state_conflict = \"github-wins\"")
+        );
+    }
+}
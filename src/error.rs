@@ -11,6 +11,9 @@ pub enum Error {
     #[error("GitHub API error: {0}")]
     GitHub(#[from] octocrab::Error),
 
+    #[error("GitHub GraphQL error: {0}")]
+    GraphQl(String),
+
     #[error("IO error: {0}")]
     Io(#[from] std::io::Error),
 
@@ -20,6 +23,9 @@ pub enum Error {
     #[error("TOML parse error: {0}")]
     TomlParse(#[from] toml::de::Error),
 
+    #[error("Prompt error: {0}")]
+    Prompt(#[from] dialoguer::Error),
+
     #[error("Configuration error: {0}")]
     Config(String),
 
@@ -1,7 +1,15 @@
-use crate::error::Result;
+use crate::error::{Error, Result};
 use crate::github::model::{
-    CreateIssueRequest, GhComment, GhIssue, GhIssueState, UpdateIssueRequest,
+    closing_references, CreateIssueRequest, GhComment, GhIssue, GhIssueState, GhPullRequest,
+    GhStateReason, IssueListing, ProjectBoard, ProjectItem, UpdateIssueRequest,
 };
+use chrono::{DateTime, NaiveDate, Utc};
+use futures::{StreamExt, TryStreamExt};
+use octocrab::models::issues::IssueStateReason;
+use std::collections::HashMap;
+
+/// Issues whose comments are fetched at the same time
+const CONCURRENT_COMMENT_FETCHES: usize = 8;
 
 pub struct GitHubClient {
     client: octocrab::Octocrab,
@@ -33,39 +41,56 @@ impl GitHubClient {
         })
     }
 
-    /// Fetch all open and recently closed issues
+    /// Fetch all open and closed issues (pull requests excluded)
     pub async fn fetch_issues(&self) -> Result<Vec<GhIssue>> {
-        let mut all_issues = Vec::new();
-
-        // Fetch open issues
-        let open_issues = self
-            .client
-            .issues(&self.owner, &self.repo)
-            .list()
-            .state(octocrab::params::State::Open)
-            .per_page(100)
-            .send()
-            .await?;
+        self.fetch_issues_since(None).await
+    }
 
-        for issue in open_issues.items {
-            all_issues.push(convert_issue(issue));
-        }
+    /// Fetch issues updated at or after `since` (all issues when `None`)
+    ///
+    /// Pull requests are excluded; use [`Self::fetch_listing`] to get them.
+    pub async fn fetch_issues_since(&self, since: Option<DateTime<Utc>>) -> Result<Vec<GhIssue>> {
+        Ok(self.fetch_listing(since).await?.issues)
+    }
 
-        // Fetch closed issues (recent)
-        let closed_issues = self
-            .client
-            .issues(&self.owner, &self.repo)
-            .list()
-            .state(octocrab::params::State::Closed)
-            .per_page(100)
-            .send()
+    /// Fetch issues and pull requests updated at or after `since`
+    ///
+    /// Follows pagination until every matching item has been retrieved, then
+    /// splits pull requests out of the issue list.
+    pub async fn fetch_listing(&self, since: Option<DateTime<Utc>>) -> Result<IssueListing> {
+        let mut all = self
+            .list_issues(octocrab::params::State::Open, since)
             .await?;
+        all.extend(
+            self.list_issues(octocrab::params::State::Closed, since)
+                .await?,
+        );
+
+        let mut listing = IssueListing::default();
+        for issue in all {
+            if issue.pull_request.is_some() {
+                listing.pull_requests.push(convert_pull_request(issue));
+            } else {
+                listing.issues.push(convert_issue(issue));
+            }
+        }
+        Ok(listing)
+    }
 
-        for issue in closed_issues.items {
-            all_issues.push(convert_issue(issue));
+    /// List every issue (and pull request) in the given state, following all pages
+    async fn list_issues(
+        &self,
+        state: octocrab::params::State,
+        since: Option<DateTime<Utc>>,
+    ) -> Result<Vec<octocrab::models::issues::Issue>> {
+        let issues = self.client.issues(&self.owner, &self.repo);
+        let mut builder = issues.list().state(state).per_page(100);
+        if let Some(since) = since {
+            builder = builder.since(since);
         }
 
-        Ok(all_issues)
+        let first_page = builder.send().await?;
+        Ok(self.client.all_pages(first_page).await?)
     }
 
     /// Fetch a single issue by number
@@ -79,6 +104,19 @@ impl GitHubClient {
         Ok(convert_issue(issue))
     }
 
+    /// Fetch a single issue by number, returning `None` if it does not exist
+    pub async fn find_issue(&self, number: u64) -> Result<Option<GhIssue>> {
+        match self.get_issue(number).await {
+            Ok(issue) => Ok(Some(issue)),
+            Err(Error::GitHub(octocrab::Error::GitHub { source, .. }))
+                if matches!(source.status_code.as_u16(), 404 | 410) =>
+            {
+                Ok(None)
+            }
+            Err(e) => Err(e),
+        }
+    }
+
     /// Create a new issue
     pub async fn create_issue(&self, req: CreateIssueRequest) -> Result<GhIssue> {
         let issues = self.client.issues(&self.owner, &self.repo);
@@ -120,6 +158,14 @@ impl GitHubClient {
             });
         }
 
+        if let Some(reason) = req.state_reason {
+            builder = builder.state_reason(match reason {
+                GhStateReason::Completed => IssueStateReason::Completed,
+                GhStateReason::NotPlanned => IssueStateReason::NotPlanned,
+                GhStateReason::Reopened => IssueStateReason::Reopened,
+            });
+        }
+
         if let Some(ref assignees) = req.assignees {
             builder = builder.assignees(assignees);
         }
@@ -158,28 +204,319 @@ impl GitHubClient {
 
     /// Fetch comments for an issue
     pub async fn fetch_comments(&self, issue_number: u64) -> Result<Vec<GhComment>> {
-        let comments = self
+        self.fetch_comments_since(issue_number, None).await
+    }
+
+    /// Fetch the comments of several issues, by issue number, optionally
+    /// only those updated since a time
+    ///
+    /// Runs a few requests at a time rather than one issue after another.
+    pub async fn fetch_comments_for(
+        &self,
+        issue_numbers: &[u64],
+        since: Option<DateTime<Utc>>,
+    ) -> Result<HashMap<u64, Vec<GhComment>>> {
+        futures::stream::iter(issue_numbers)
+            .map(|&number| async move {
+                Ok((number, self.fetch_comments_since(number, since).await?))
+            })
+            .buffer_unordered(CONCURRENT_COMMENT_FETCHES)
+            .try_collect()
+            .await
+    }
+
+    /// Fetch comments for an issue, optionally only those updated since a time
+    pub async fn fetch_comments_since(
+        &self,
+        issue_number: u64,
+        since: Option<DateTime<Utc>>,
+    ) -> Result<Vec<GhComment>> {
+        let issues = self.client.issues(&self.owner, &self.repo);
+        let mut builder = issues.list_comments(issue_number).per_page(100);
+        if let Some(since) = since {
+            builder = builder.since(since);
+        }
+
+        let first_page = builder.send().await?;
+        let comments = self.client.all_pages(first_page).await?;
+
+        Ok(comments.into_iter().map(convert_comment).collect())
+    }
+
+    /// Post a new comment on an issue
+    pub async fn create_comment(&self, issue_number: u64, body: &str) -> Result<GhComment> {
+        let comment = self
+            .client
+            .issues(&self.owner, &self.repo)
+            .create_comment(issue_number, body)
+            .await?;
+        Ok(convert_comment(comment))
+    }
+
+    /// Replace the body of an existing comment
+    pub async fn update_comment(&self, comment_id: u64, body: &str) -> Result<GhComment> {
+        let route = format!(
+            "/repos/{}/{}/issues/comments/{}",
+            self.owner, self.repo, comment_id
+        );
+        let comment: octocrab::models::issues::Comment = self
+            .client
+            .patch(route, Some(&serde_json::json!({ "body": body })))
+            .await?;
+        Ok(convert_comment(comment))
+    }
+
+    /// Numbers of an issue's sub-issues
+    pub async fn fetch_sub_issues(&self, number: u64) -> Result<Vec<u64>> {
+        let route = format!(
+            "/repos/{}/{}/issues/{}/sub_issues",
+            self.owner, self.repo, number
+        );
+        let first_page: octocrab::Page<octocrab::models::issues::Issue> =
+            self.client.get(route, Some(&[("per_page", 100)])).await?;
+        let sub_issues = self.client.all_pages(first_page).await?;
+        Ok(sub_issues.into_iter().map(|issue| issue.number).collect())
+    }
+
+    /// Make `child` a sub-issue of `parent`, taking it from any parent it had
+    pub async fn add_sub_issue(&self, parent: u64, child: u64) -> Result<()> {
+        let route = format!(
+            "/repos/{}/{}/issues/{}/sub_issues",
+            self.owner, self.repo, parent
+        );
+        let body = serde_json::json!({
+            "sub_issue_id": self.issue_id(child).await?,
+            "replace_parent": true,
+        });
+        let _: octocrab::models::issues::Issue = self.client.post(route, Some(&body)).await?;
+        Ok(())
+    }
+
+    /// Detach `child` from `parent` (a child that is not there is ignored)
+    pub async fn remove_sub_issue(&self, parent: u64, child: u64) -> Result<()> {
+        let route = format!(
+            "/repos/{}/{}/issues/{}/sub_issue",
+            self.owner, self.repo, parent
+        );
+        let body = serde_json::json!({ "sub_issue_id": self.issue_id(child).await? });
+        let result: octocrab::Result<octocrab::models::issues::Issue> =
+            self.client.delete(route, Some(&body)).await;
+        match result {
+            Ok(_) => Ok(()),
+            Err(octocrab::Error::GitHub { source, .. }) if source.status_code.as_u16() == 404 => {
+                Ok(())
+            }
+            Err(e) => Err(e.into()),
+        }
+    }
+
+    /// GitHub's internal id for an issue, which the sub-issue API takes
+    async fn issue_id(&self, number: u64) -> Result<u64> {
+        let issue = self
             .client
             .issues(&self.owner, &self.repo)
-            .list_comments(issue_number)
+            .get(number)
+            .await?;
+        Ok(issue.id.0)
+    }
+
+    /// Fetch a Projects board owned by a user or organization, with the
+    /// date fields set on this repository's issues
+    pub async fn fetch_project(&self, owner: &str, number: u64) -> Result<ProjectBoard> {
+        const QUERY: &str = r#"
+query($owner: String!, $number: Int!, $cursor: String) {
+  repositoryOwner(login: $owner) {
+    ... on ProjectV2Owner {
+      projectV2(number: $number) {
+        id
+        fields(first: 100) {
+          nodes { ... on ProjectV2Field { id name dataType } }
+        }
+        items(first: 100, after: $cursor) {
+          pageInfo { hasNextPage endCursor }
+          nodes {
+            id
+            content { ... on Issue { number repository { nameWithOwner } } }
+            fieldValues(first: 50) {
+              nodes {
+                ... on ProjectV2ItemFieldDateValue {
+                  date
+                  field { ... on ProjectV2Field { name } }
+                }
+              }
+            }
+          }
+        }
+      }
+    }
+  }
+}"#;
+
+        let repo = format!("{}/{}", self.owner, self.repo);
+        let mut board = ProjectBoard::default();
+        let mut cursor: Option<String> = None;
+        loop {
+            let variables = serde_json::json!({
+                "owner": owner,
+                "number": number,
+                "cursor": cursor,
+            });
+            let data = self.graphql(QUERY, variables).await?;
+            let project = &data["repositoryOwner"]["projectV2"];
+            if project.is_null() {
+                return Err(Error::Config(format!(
+                    "Project {} of {} not found",
+                    number, owner
+                )));
+            }
+
+            board.id = json_str(&project["id"]).to_string();
+            for field in project["fields"]["nodes"].as_array().into_iter().flatten() {
+                if field["dataType"] == "DATE" {
+                    board.date_fields.insert(
+                        json_str(&field["name"]).to_string(),
+                        json_str(&field["id"]).to_string(),
+                    );
+                }
+            }
+
+            let items = &project["items"];
+            for node in items["nodes"].as_array().into_iter().flatten() {
+                let content = &node["content"];
+                let in_repo =
+                    json_str(&content["repository"]["nameWithOwner"]).eq_ignore_ascii_case(&repo);
+                let Some(issue_number) = content["number"].as_u64().filter(|_| in_repo) else {
+                    continue;
+                };
+                let mut item = ProjectItem {
+                    id: json_str(&node["id"]).to_string(),
+                    dates: Default::default(),
+                };
+                for value in node["fieldValues"]["nodes"]
+                    .as_array()
+                    .into_iter()
+                    .flatten()
+                {
+                    let date = NaiveDate::parse_from_str(json_str(&value["date"]), "%Y-%m-%d");
+                    if let Ok(date) = date {
+                        item.dates
+                            .insert(json_str(&value["field"]["name"]).to_string(), date);
+                    }
+                }
+                board.items.insert(issue_number, item);
+            }
+
+            match items["pageInfo"]["endCursor"].as_str() {
+                Some(next) if items["pageInfo"]["hasNextPage"] == true => {
+                    cursor = Some(next.to_string())
+                }
+                _ => break,
+            }
+        }
+        Ok(board)
+    }
+
+    /// Add an issue to a Projects board, returning the new item's id
+    pub async fn add_to_project(&self, project_id: &str, number: u64) -> Result<String> {
+        const MUTATION: &str = r#"
+mutation($project: ID!, $content: ID!) {
+  addProjectV2ItemById(input: {projectId: $project, contentId: $content}) {
+    item { id }
+  }
+}"#;
+
+        let issue = self
+            .client
+            .issues(&self.owner, &self.repo)
+            .get(number)
+            .await?;
+        let variables = serde_json::json!({ "project": project_id, "content": issue.node_id });
+        let data = self.graphql(MUTATION, variables).await?;
+        Ok(json_str(&data["addProjectV2ItemById"]["item"]["id"]).to_string())
+    }
+
+    /// Set a date field on a board item, or clear it with `None`
+    pub async fn set_project_date(
+        &self,
+        project_id: &str,
+        item_id: &str,
+        field_id: &str,
+        date: Option<NaiveDate>,
+    ) -> Result<()> {
+        const SET: &str = r#"
+mutation($project: ID!, $item: ID!, $field: ID!, $date: Date!) {
+  updateProjectV2ItemFieldValue(
+    input: {projectId: $project, itemId: $item, fieldId: $field, value: {date: $date}}
+  ) {
+    projectV2Item { id }
+  }
+}"#;
+        const CLEAR: &str = r#"
+mutation($project: ID!, $item: ID!, $field: ID!) {
+  clearProjectV2ItemFieldValue(
+    input: {projectId: $project, itemId: $item, fieldId: $field}
+  ) {
+    projectV2Item { id }
+  }
+}"#;
+
+        let mut variables = serde_json::json!({
+            "project": project_id,
+            "item": item_id,
+            "field": field_id,
+        });
+        let query = match date {
+            Some(date) => {
+                variables["date"] = serde_json::json!(date.format("%Y-%m-%d").to_string());
+                SET
+            }
+            None => CLEAR,
+        };
+        self.graphql(query, variables).await?;
+        Ok(())
+    }
+
+    /// Run a GraphQL query, returning its `data` or the errors GitHub reported
+    async fn graphql(
+        &self,
+        query: &str,
+        variables: serde_json::Value,
+    ) -> Result<serde_json::Value> {
+        let payload = serde_json::json!({ "query": query, "variables": variables });
+        let mut response: serde_json::Value = self.client.graphql(&payload).await?;
+        if let Some(errors) = response["errors"].as_array().filter(|e| !e.is_empty()) {
+            let messages: Vec<&str> = errors
+                .iter()
+                .map(|error| error["message"].as_str().unwrap_or("unknown error"))
+                .collect();
+            return Err(Error::GraphQl(messages.join("; ")));
+        }
+        Ok(response["data"].take())
+    }
+
+    /// Search this repository's issues (pull requests excluded)
+    ///
+    /// `filter` uses GitHub's search syntax, e.g. `label:bug is:open`.
+    pub async fn search_issues(&self, filter: &str) -> Result<Vec<GhIssue>> {
+        let query = format!("repo:{}/{} is:issue {}", self.owner, self.repo, filter);
+        let first_page = self
+            .client
+            .search()
+            .issues_and_pull_requests(query.trim())
             .per_page(100)
             .send()
             .await?;
-
-        Ok(comments
-            .items
+        let issues = self.client.all_pages(first_page).await?;
+        Ok(issues
             .into_iter()
-            .map(|c| GhComment {
-                id: c.id.0,
-                author: c.user.login,
-                body: c.body.unwrap_or_default(),
-                created_at: c.created_at,
-                updated_at: c.updated_at.unwrap_or(c.created_at),
-            })
+            .filter(|issue| issue.pull_request.is_none())
+            .map(convert_issue)
             .collect())
     }
 
     /// Try to find an existing issue by title (for initial matching)
+    ///
+    /// Pull requests never match, even with the same title.
     pub async fn find_by_title(&self, title: &str) -> Result<Option<GhIssue>> {
         let issues = self.fetch_issues().await?;
         Ok(issues.into_iter().find(|i| i.title == title))
@@ -189,7 +526,7 @@ impl GitHubClient {
 fn parse_repo(repo: &str) -> Result<(String, String)> {
     let parts: Vec<&str> = repo.split('/').collect();
     if parts.len() != 2 {
-        return Err(crate::error::Error::Config(format!(
+        return Err(Error::Config(format!(
             "Invalid repository format: {}. Expected owner/repo",
             repo
         )));
@@ -197,8 +534,37 @@ fn parse_repo(repo: &str) -> Result<(String, String)> {
     Ok((parts[0].to_string(), parts[1].to_string()))
 }
 
+/// A JSON string value, or `""` for anything else
+fn json_str(value: &serde_json::Value) -> &str {
+    value.as_str().unwrap_or("")
+}
+
+fn convert_pull_request(issue: octocrab::models::issues::Issue) -> GhPullRequest {
+    let pr = convert_issue(issue);
+    GhPullRequest {
+        number: pr.number,
+        linked_issues: closing_references(pr.body.as_deref().unwrap_or("")),
+        title: pr.title,
+        state: pr.state,
+        html_url: pr.html_url,
+        updated_at: pr.updated_at,
+        closed_at: pr.closed_at,
+    }
+}
+
+fn convert_comment(comment: octocrab::models::issues::Comment) -> GhComment {
+    GhComment {
+        id: comment.id.0,
+        author: comment.user.login,
+        body: comment.body.unwrap_or_default(),
+        created_at: comment.created_at,
+        updated_at: comment.updated_at.unwrap_or(comment.created_at),
+    }
+}
+
 fn convert_issue(issue: octocrab::models::issues::Issue) -> GhIssue {
     GhIssue {
+        is_pull_request: issue.pull_request.is_some(),
         number: issue.number,
         title: issue.title,
         body: issue.body,
@@ -207,11 +573,19 @@ fn convert_issue(issue: octocrab::models::issues::Issue) -> GhIssue {
             octocrab::models::IssueState::Closed => GhIssueState::Closed,
             _ => GhIssueState::Open,
         },
+        state_reason: issue.state_reason.map(|reason| match reason {
+            IssueStateReason::Reopened => GhStateReason::Reopened,
+            IssueStateReason::Completed => GhStateReason::Completed,
+            // Duplicates and anything newer count as closed without completing
+            _ => GhStateReason::NotPlanned,
+        }),
         assignees: issue.assignees.into_iter().map(|a| a.login).collect(),
         labels: issue.labels.into_iter().map(|l| l.name).collect(),
+        milestone: issue.milestone.map(|m| m.title),
         created_at: issue.created_at,
         updated_at: issue.updated_at,
         closed_at: issue.closed_at,
         html_url: issue.html_url.to_string(),
+        comments: issue.comments,
     }
 }
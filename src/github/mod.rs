@@ -2,4 +2,7 @@ pub mod client;
 pub mod model;
 
 pub use client::GitHubClient;
-pub use model::{GhComment, GhIssue, GhIssueState};
+pub use model::{
+    GhComment, GhIssue, GhIssueState, GhPullRequest, GhStateReason, IssueListing, ProjectBoard,
+    ProjectItem,
+};
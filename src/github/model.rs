@@ -1,5 +1,6 @@
-use chrono::{DateTime, Utc};
+use chrono::{DateTime, NaiveDate, Utc};
 use serde::{Deserialize, Serialize};
+use std::collections::HashMap;
 
 #[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct GhIssue {
@@ -7,12 +8,24 @@ pub struct GhIssue {
     pub title: String,
     pub body: Option<String>,
     pub state: GhIssueState,
+    /// Why the issue was last closed or reopened
+    #[serde(default)]
+    pub state_reason: Option<GhStateReason>,
     pub assignees: Vec<String>,
     pub labels: Vec<String>,
+    /// Title of the issue's milestone
+    #[serde(default)]
+    pub milestone: Option<String>,
     pub created_at: DateTime<Utc>,
     pub updated_at: DateTime<Utc>,
     pub closed_at: Option<DateTime<Utc>>,
     pub html_url: String,
+    /// Number of comments on the issue
+    #[serde(default)]
+    pub comments: u32,
+    /// The issues API also returns pull requests; this marks them
+    #[serde(default)]
+    pub is_pull_request: bool,
 }
 
 #[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
@@ -28,6 +41,61 @@ impl GhIssueState {
     }
 }
 
+/// GitHub's `state_reason` for closing or reopening an issue
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum GhStateReason {
+    Completed,
+    NotPlanned,
+    Reopened,
+}
+
+/// A pull request from the issue listing, with the issues it closes
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct GhPullRequest {
+    pub number: u64,
+    pub title: String,
+    pub state: GhIssueState,
+    pub html_url: String,
+    pub updated_at: DateTime<Utc>,
+    pub closed_at: Option<DateTime<Utc>>,
+    /// Issues referenced with a closing keyword ("Fixes #12") in the PR body
+    pub linked_issues: Vec<u64>,
+}
+
+impl GhPullRequest {
+    pub fn links_issue(&self, issue_number: u64) -> bool {
+        self.linked_issues.contains(&issue_number)
+    }
+}
+
+/// Issue listing with pull requests split out from real issues
+#[derive(Debug, Clone, Default)]
+pub struct IssueListing {
+    pub issues: Vec<GhIssue>,
+    pub pull_requests: Vec<GhPullRequest>,
+}
+
+/// A GitHub Projects board: its date fields and this repository's issues on it
+#[derive(Debug, Clone, Default)]
+pub struct ProjectBoard {
+    /// Node id of the project
+    pub id: String,
+    /// Date field ids by field name
+    pub date_fields: HashMap<String, String>,
+    /// Board items by issue number
+    pub items: HashMap<u64, ProjectItem>,
+}
+
+/// An issue's item on a Projects board
+#[derive(Debug, Clone, Default)]
+pub struct ProjectItem {
+    /// Node id of the board item
+    pub id: String,
+    /// Values of the date fields that are set, by field name
+    pub dates: HashMap<String, NaiveDate>,
+}
+
 #[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct GhComment {
     pub id: u64,
@@ -52,6 +120,58 @@ pub struct UpdateIssueRequest {
     pub title: Option<String>,
     pub body: Option<String>,
     pub state: Option<GhIssueState>,
+    pub state_reason: Option<GhStateReason>,
     pub assignees: Option<Vec<String>>,
     pub labels: Option<Vec<String>>,
 }
+
+/// Extract issue numbers referenced with GitHub closing keywords
+///
+/// Recognizes `close`, `fix` and `resolve` in all their forms, followed by an
+/// optional colon and a `#N` reference, e.g. "Fixes #12" or "resolved: #3".
+pub fn closing_references(text: &str) -> Vec<u64> {
+    const KEYWORDS: &[&str] = &[
+        "close", "closes", "closed", "fix", "fixes", "fixed", "resolve", "resolves", "resolved",
+    ];
+
+    let lower = text.to_lowercase();
+    let words: Vec<&str> = lower.split_whitespace().collect();
+    let mut refs = Vec::new();
+
+    for pair in words.windows(2) {
+        let keyword = pair[0].trim_end_matches(':');
+        if !KEYWORDS.contains(&keyword) {
+            continue;
+        }
+        let Some(reference) = pair[1].strip_prefix('#') else {
+            continue;
+        };
+        let digits: String = reference
+            .chars()
+            .take_while(|c| c.is_ascii_digit())
+            .collect();
+        if let Ok(number) = digits.parse() {
+            if !refs.contains(&number) {
+                refs.push(number);
+            }
+        }
+    }
+
+    refs
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_closing_references() {
+        assert_eq!(closing_references("Fixes #12"), vec![12]);
+        assert_eq!(
+            closing_references("This resolves: #3, and closes #4.\nAlso fixes #3"),
+            vec![3, 4]
+        );
+        assert!(closing_references("Related to #5").is_empty());
+        assert!(closing_references("fixes the bug").is_empty());
+    }
+}
@@ -9,6 +9,7 @@ async fn main() -> Result<()> {
 
     match cli.command {
         Command::Init(args) => org_gh::cli::init::run(args, format).await,
+        Command::Clone(args) => org_gh::cli::clone::run(args, format).await,
         Command::Push(args) => org_gh::cli::push::run(args, format).await,
         Command::Pull(args) => org_gh::cli::pull::run(args, format).await,
         Command::Sync(args) => org_gh::cli::sync::run(args, format).await,
@@ -0,0 +1,1649 @@
+//! Conversion between org markup and GitHub Flavored Markdown
+//!
+//! Issue bodies live as org text in the heading and as Markdown on GitHub.
+//! Sync compares bodies in their Markdown form, so both sides go through
+//! [`normalize_markdown`] before hashing or merging. Markdown written the
+//! way [`to_markdown`] writes it converts back to the same org text.
+
+use orgize::ast::{Link, ListItem, OrgTableRow};
+use orgize::export::{Container, Event, TraversalContext, Traverser};
+use orgize::rowan::ast::AstNode;
+use orgize::{Org, SyntaxKind, SyntaxNode};
+
+/// Path of images uploaded to GitHub issues
+const GITHUB_ASSETS: &str = "/user-attachments/assets/";
+
+/// Zero-width space put in imported text so org reads it as plain text
+const GUARD: char = '\u{200b}';
+
+/// Characters a [`GUARD`] may stand next to: org emphasis markers, and
+/// the openings of links and entities
+const GUARDED: &str = "*/_=~+[\\";
+
+/// Inline HTML elements kept as they are, in `@@html:...@@` snippets
+const INLINE_HTML: &[&str] = &[
+    "a", "abbr", "b", "br", "code", "del", "em", "i", "img", "ins", "kbd", "mark", "q", "s",
+    "samp", "small", "span", "strong", "sub", "sup", "var",
+];
+
+/// Convert an org heading body to GitHub Flavored Markdown
+pub fn to_markdown(org: &str) -> String {
+    let mut export = GfmExport::default();
+    Org::parse(org).traverse(&mut export);
+    normalize_markdown(&export.output)
+}
+
+/// Convert a heading title to Markdown
+///
+/// Only inline markup is converted; a title that org would read as
+/// anything but a plain paragraph is left as it is.
+pub fn title_to_markdown(title: &str) -> String {
+    if !is_paragraph(title) {
+        return title.to_string();
+    }
+    to_markdown(title)
+}
+
+/// Whether org reads `text` as a single plain paragraph
+fn is_paragraph(text: &str) -> bool {
+    let org = Org::parse(text);
+    let section = org
+        .document()
+        .section()
+        .map(|s| s.syntax().children().map(|n| n.kind()).collect::<Vec<_>>())
+        .unwrap_or_default();
+    section == [SyntaxKind::PARAGRAPH]
+}
+
+/// Canonical form of a Markdown body: LF line endings, no trailing
+/// whitespace on lines or at either end
+pub fn normalize_markdown(markdown: &str) -> String {
+    markdown
+        .replace("\r\n", "\n")
+        .lines()
+        .map(str::trim_end)
+        .collect::<Vec<_>>()
+        .join("\n")
+        .trim_matches('\n')
+        .to_string()
+}
+
+/// Traverser writing GitHub Flavored Markdown
+#[derive(Default)]
+struct GfmExport {
+    output: String,
+    /// Written at the start of every line: quote markers and list indentation
+    prefix: Vec<String>,
+    /// A block just ended, so the next one may need a blank line first
+    block_ended: bool,
+    /// The block that just ended was a paragraph (a list may follow directly)
+    after_paragraph: bool,
+    /// The last line written was blank
+    blank: bool,
+    /// A list marker was just written, so the item's first block goes after it
+    item_start: bool,
+    /// Cells of the table row being written, and where the current one starts
+    cells: Vec<String>,
+    cell_start: usize,
+    in_cell: bool,
+    table_rows: usize,
+    /// Plain text not yet written; orgize splits a run into several tokens
+    text: String,
+}
+
+impl GfmExport {
+    fn at_line_start(&self) -> bool {
+        self.output.is_empty() || self.output.ends_with('\n')
+    }
+
+    fn write_prefix(&mut self) {
+        if self.at_line_start() {
+            let prefix = self.prefix.concat();
+            self.output.push_str(&prefix);
+        }
+    }
+
+    fn newline(&mut self) {
+        if self.at_line_start() {
+            self.output.push_str(self.prefix.concat().trim_end());
+            self.blank = true;
+        }
+        self.output.push('\n');
+    }
+
+    fn ensure_newline(&mut self) {
+        if !self.at_line_start() {
+            self.output.push('\n');
+        }
+    }
+
+    /// Write inline text, re-indenting continuation lines with the prefix
+    fn write(&mut self, text: &str) {
+        for (i, line) in text.split('\n').enumerate() {
+            if i > 0 {
+                self.newline();
+            }
+            let line = if self.at_line_start() {
+                line.trim_start()
+            } else {
+                line
+            };
+            if !line.is_empty() {
+                self.write_prefix();
+                self.output.push_str(line);
+                self.blank = false;
+            }
+        }
+    }
+
+    /// Write pending plain text, escaped as a whole
+    fn flush_text(&mut self) {
+        if !self.text.is_empty() {
+            let text = std::mem::take(&mut self.text);
+            let before = self.output.chars().last();
+            self.write(&escape_markdown(&strip_guards(&text, before), before));
+        }
+    }
+
+    /// Write lines as they are (code), keeping their indentation
+    fn write_lines(&mut self, text: &str) {
+        for line in text.lines() {
+            if line.is_empty() {
+                self.newline();
+            } else {
+                self.write_prefix();
+                self.output.push_str(line);
+                self.output.push('\n');
+                self.blank = false;
+            }
+        }
+    }
+
+    /// Separate a new block from the previous one with a blank line
+    ///
+    /// Markdown would otherwise continue a list, quote or table into the
+    /// next block. Lists may follow a paragraph directly, as in org.
+    fn start_block(&mut self, is_list: bool) {
+        if std::mem::take(&mut self.item_start) {
+            return;
+        }
+        self.ensure_newline();
+        if self.block_ended && !self.blank && !(is_list && self.after_paragraph) {
+            self.newline();
+        }
+        self.block_ended = false;
+    }
+
+    /// Finish a block, keeping the blank lines that followed it in org
+    fn end_block(&mut self, node: &SyntaxNode, is_paragraph: bool) {
+        self.ensure_newline();
+        for _ in 0..blank_lines(node) {
+            self.newline();
+        }
+        self.block_ended = true;
+        self.after_paragraph = is_paragraph;
+    }
+
+    /// Write a fenced code block
+    fn fence(&mut self, language: &str, code: &str) {
+        let fence = if code.contains("```") { "````" } else { "```" };
+        self.write_prefix();
+        self.output.push_str(fence);
+        self.output.push_str(language);
+        self.output.push('\n');
+        self.write_lines(&dedent(code));
+        self.write_prefix();
+        self.output.push_str(fence);
+        self.output.push('\n');
+        self.blank = false;
+    }
+
+    /// Write fixed-width lines as an indented code block
+    ///
+    /// Read from the org text, as orgize drops the indentation after `: `.
+    fn indented_code(&mut self, fixed: &str) {
+        let lines: Vec<String> = fixed
+            .lines()
+            .filter_map(|line| line.trim_start().strip_prefix(':'))
+            .map(|line| match line.strip_prefix(' ') {
+                Some(code) if !code.trim().is_empty() => format!("    {}", code),
+                _ => String::new(),
+            })
+            .collect();
+        self.write_lines(&lines.join("\n"));
+        self.blank = false;
+    }
+
+    /// Write inline code; in a table cell `\vert{}` stands for the `|`
+    /// org cannot hold there
+    fn code(&mut self, code: &str) {
+        if self.in_cell {
+            self.write(&code_span(&code.replace("\\vert{}", "|")));
+        } else {
+            self.write(&code_span(code));
+        }
+    }
+
+    fn list_item(&mut self, item: &ListItem, ctx: &mut TraversalContext) {
+        self.ensure_newline();
+        self.block_ended = false;
+
+        let bullet = item.bullet();
+        let bullet = bullet.trim();
+        let marker = match bullet.strip_suffix(['.', ')']) {
+            Some(number) if number.chars().all(|c| c.is_ascii_digit()) => format!("{}.", number),
+            _ => "-".to_string(),
+        };
+        self.write_prefix();
+        self.output.push_str(&marker);
+        self.output.push(' ');
+        self.blank = false;
+        match item.checkbox().as_deref() {
+            Some("X") | Some("x") => self.output.push_str("[x] "),
+            Some(_) => self.output.push_str("[ ] "),
+            None => {}
+        }
+        if item.tag().next().is_some() {
+            for elem in item.tag() {
+                self.element(elem, ctx);
+            }
+            self.output.push_str(" :: ");
+        }
+        self.prefix.push(" ".repeat(marker.len() + 1));
+        self.item_start = true;
+    }
+
+    fn link(&mut self, link: &Link, ctx: &mut TraversalContext) {
+        let path = link.path();
+        let path = path.trim_start_matches("file:");
+        // GitHub uploads have no file extension but are images all the same
+        if link.is_image() || (!link.has_description() && path.contains(GITHUB_ASSETS)) {
+            self.write(&format!("![]({})", path));
+            ctx.skip();
+        } else if !link.has_description() {
+            if path.starts_with("http://") || path.starts_with("https://") {
+                self.write(&format!("<{}>", path));
+            } else {
+                self.write(&format!("[{}]({})", path, path));
+            }
+            ctx.skip();
+        } else {
+            self.write("[");
+        }
+    }
+
+    fn table_row(&mut self, row: &OrgTableRow, ctx: &mut TraversalContext) {
+        // The header delimiter is written after the first row
+        if row.is_rule() {
+            ctx.skip();
+            return;
+        }
+        self.ensure_newline();
+        self.write_prefix();
+        self.cells.clear();
+    }
+
+    fn end_table_row(&mut self) {
+        if self.cells.is_empty() {
+            return;
+        }
+        let cells = std::mem::take(&mut self.cells);
+        self.output
+            .push_str(&format!("| {} |\n", cells.join(" | ")));
+        if self.table_rows == 0 {
+            let delimiter = vec!["---"; cells.len()].join(" | ");
+            self.write_prefix();
+            self.output.push_str(&format!("| {} |\n", delimiter));
+        }
+        self.table_rows += 1;
+        self.blank = false;
+    }
+}
+
+impl Traverser for GfmExport {
+    fn event(&mut self, event: Event, ctx: &mut TraversalContext) {
+        if !matches!(event, Event::Text(_)) {
+            self.flush_text();
+        }
+        match event {
+            Event::Enter(Container::Paragraph(_)) => self.start_block(false),
+            Event::Leave(Container::Paragraph(p)) => self.end_block(p.syntax(), true),
+
+            Event::Enter(Container::List(_)) => self.start_block(true),
+            Event::Leave(Container::List(list)) => self.end_block(list.syntax(), false),
+            Event::Enter(Container::ListItem(item)) => self.list_item(&item, ctx),
+            Event::Leave(Container::ListItem(item)) => {
+                self.prefix.pop();
+                self.item_start = false;
+                self.ensure_newline();
+                for _ in 0..blank_lines(item.syntax()) {
+                    self.newline();
+                }
+                self.block_ended = false;
+            }
+
+            Event::Enter(Container::SourceBlock(block)) => {
+                self.start_block(false);
+                let language = block.language().map(|l| l.to_string()).unwrap_or_default();
+                self.fence(&language, &block.value());
+                ctx.skip();
+                self.end_block(block.syntax(), false);
+            }
+            Event::Enter(Container::ExampleBlock(block)) => {
+                self.start_block(false);
+                self.fence("", &block_content(block.syntax()));
+                ctx.skip();
+                self.end_block(block.syntax(), false);
+            }
+            Event::Enter(Container::FixedWidth(fixed)) => {
+                self.start_block(false);
+                self.indented_code(&fixed.syntax().to_string());
+                ctx.skip();
+                self.end_block(fixed.syntax(), false);
+            }
+            Event::Enter(Container::ExportBlock(block)) => {
+                let ty = block.ty().map(|t| t.to_lowercase()).unwrap_or_default();
+                if matches!(ty.as_str(), "html" | "markdown" | "md") {
+                    self.start_block(false);
+                    self.write_lines(&block.value());
+                    self.end_block(block.syntax(), false);
+                }
+                ctx.skip();
+            }
+
+            Event::Enter(Container::QuoteBlock(_)) => {
+                self.start_block(false);
+                self.prefix.push("> ".to_string());
+            }
+            Event::Leave(Container::QuoteBlock(quote)) => {
+                self.prefix.pop();
+                self.end_block(quote.syntax(), false);
+            }
+
+            Event::Enter(Container::Comment(comment)) => {
+                self.start_block(false);
+                for line in comment.value().lines() {
+                    self.write_lines(&format!("<!-- {} -->", line.trim()));
+                }
+                ctx.skip();
+                self.end_block(comment.syntax(), false);
+            }
+            Event::Enter(Container::CommentBlock(block)) => {
+                self.start_block(false);
+                self.write_lines(&format!(
+                    "<!--\n{}-->",
+                    dedent(&block_content(block.syntax()))
+                ));
+                ctx.skip();
+                self.end_block(block.syntax(), false);
+            }
+
+            Event::Enter(Container::OrgTable(_)) => {
+                self.start_block(false);
+                self.table_rows = 0;
+            }
+            Event::Leave(Container::OrgTable(table)) => self.end_block(table.syntax(), false),
+            Event::Enter(Container::OrgTableRow(row)) => self.table_row(&row, ctx),
+            Event::Leave(Container::OrgTableRow(_)) => self.end_table_row(),
+            Event::Enter(Container::OrgTableCell(_)) => {
+                self.cell_start = self.output.len();
+                self.in_cell = true;
+            }
+            Event::Leave(Container::OrgTableCell(_)) => {
+                self.in_cell = false;
+                let cell = self.output.split_off(self.cell_start);
+                self.cells.push(cell.trim().replace('|', "\\|"));
+            }
+
+            Event::Rule(rule) => {
+                self.start_block(false);
+                // A blank line first, or `---` would underline the paragraph
+                if !self.blank && !self.output.is_empty() {
+                    self.newline();
+                }
+                self.write_lines("---");
+                self.end_block(rule.syntax(), false);
+            }
+
+            Event::Enter(Container::Bold(_)) | Event::Leave(Container::Bold(_)) => self.write("**"),
+            Event::Enter(Container::Italic(_)) | Event::Leave(Container::Italic(_)) => {
+                self.write("*")
+            }
+            Event::Enter(Container::Strike(_)) | Event::Leave(Container::Strike(_)) => {
+                self.write("~~")
+            }
+            Event::Enter(Container::Underline(_)) => self.write("<ins>"),
+            Event::Leave(Container::Underline(_)) => self.write("</ins>"),
+            Event::Enter(Container::Verbatim(v)) => {
+                self.code(&inner_text(v.syntax()));
+                ctx.skip();
+            }
+            Event::Enter(Container::Code(c)) => {
+                self.code(&inner_text(c.syntax()));
+                ctx.skip();
+            }
+            Event::InlineSrc(src) => self.write(&code_span(&src.value())),
+
+            Event::Enter(Container::Link(link)) => self.link(&link, ctx),
+            Event::Leave(Container::Link(link)) => {
+                let path = link.path();
+                self.write(&format!("]({})", path.trim_start_matches("file:")));
+            }
+
+            Event::Text(text) => self.text.push_str(&text),
+            Event::LineBreak(_) => {
+                self.write("\\");
+                self.newline();
+            }
+            Event::Entity(entity) => self.write(entity.utf8()),
+
+            // Org-only syntax is kept as written
+            Event::Enter(Container::Subscript(node)) => {
+                self.write(&node.syntax().to_string());
+                ctx.skip();
+            }
+            Event::Enter(Container::Superscript(node)) => {
+                self.write(&node.syntax().to_string());
+                ctx.skip();
+            }
+            Event::Enter(Container::FnRef(node)) => {
+                self.write(&node.syntax().to_string());
+                ctx.skip();
+            }
+            Event::Enter(Container::Target(node)) => {
+                self.write(&node.syntax().to_string());
+                ctx.skip();
+            }
+            Event::Enter(Container::RadioTarget(node)) => {
+                self.write(&node.syntax().to_string());
+                ctx.skip();
+            }
+            Event::Timestamp(node) => self.write(&node.syntax().to_string()),
+            Event::Cookie(node) => self.write(&node.syntax().to_string()),
+            Event::Macros(node) => self.write(&node.syntax().to_string()),
+            Event::Snippet(node) => match node.backend().to_lowercase().as_str() {
+                "html" | "markdown" | "md" => self.write(&node.value()),
+                _ => self.write(&node.syntax().to_string()),
+            },
+            Event::LatexFragment(node) => self.write(&node.syntax().to_string()),
+            Event::LatexEnvironment(node) => {
+                self.start_block(false);
+                self.write_lines(&node.syntax().to_string());
+                self.end_block(node.syntax(), false);
+            }
+
+            // Sync metadata and export settings have no Markdown counterpart
+            Event::Enter(Container::Drawer(_))
+            | Event::Enter(Container::PropertyDrawer(_))
+            | Event::Enter(Container::Keyword(_))
+            | Event::Enter(Container::BabelCall(_))
+            | Event::Enter(Container::AffiliatedKeyword(_))
+            | Event::Enter(Container::Headline(_)) => ctx.skip(),
+
+            _ => {}
+        }
+    }
+}
+
+/// Drop the [`GUARD`]s that [`from_markdown`] puts at the start of lines
+/// and around markup characters
+fn strip_guards(text: &str, before: Option<char>) -> String {
+    let chars: Vec<char> = text.chars().collect();
+    let mut out = String::new();
+    for (i, &c) in chars.iter().enumerate() {
+        let prev = i.checked_sub(1).map(|p| chars[p]).or(before);
+        let next = chars.get(i + 1).copied();
+        let guard = c == GUARD
+            && (prev.is_none_or(|p| p.is_whitespace() || GUARDED.contains(p))
+                || next.is_some_and(|n| GUARDED.contains(n)));
+        if !guard {
+            out.push(c);
+        }
+    }
+    out
+}
+
+/// Escape plain text that Markdown would otherwise read as markup
+///
+/// `*`, backticks and brackets are always escaped; `_`, `<` and `\\` only
+/// where they could start markup, and block markers only at the start of a
+/// line. `before` is the character already written ahead of the text.
+fn escape_markdown(text: &str, before: Option<char>) -> String {
+    let mut out = String::new();
+    for (n, line) in text.split('\n').enumerate() {
+        if n > 0 {
+            out.push('\n');
+        }
+        let start = n > 0 || before.is_none_or(|c| c == '\n');
+        let trimmed = if start { line.trim_start() } else { line };
+        out.push_str(&line[..line.len() - trimmed.len()]);
+        let chars: Vec<char> = trimmed.chars().collect();
+        let marker = if start { block_marker(&chars) } else { None };
+        for (i, &c) in chars.iter().enumerate() {
+            let prev = match i.checked_sub(1) {
+                Some(p) => Some(chars[p]),
+                None if n == 0 => before,
+                None => None,
+            };
+            let next = chars.get(i + 1).copied();
+            let escape = match c {
+                '*' | '`' | '[' | ']' => true,
+                '\\' => next.is_none_or(|n| n.is_ascii_punctuation()),
+                '_' => {
+                    !(prev.is_some_and(char::is_alphanumeric)
+                        && next.is_some_and(char::is_alphanumeric))
+                }
+                '<' => next.is_some_and(|n| n.is_ascii_alphabetic() || "/!?".contains(n)),
+                '>' => i == 0 && start,
+                _ => marker == Some(i),
+            };
+            if escape {
+                out.push('\\');
+            }
+            out.push(c);
+        }
+    }
+    out
+}
+
+/// Position of the character that makes a line a Markdown heading, list
+/// item or setext underline
+fn block_marker(chars: &[char]) -> Option<usize> {
+    let first = *chars.first()?;
+    let spaced = |i: usize| chars.get(i).is_none_or(|c| *c == ' ');
+    let digits = chars.iter().take_while(|c| c.is_ascii_digit()).count();
+    if first == '#' {
+        let level = chars.iter().take_while(|&&c| c == '#').count();
+        (level <= 6 && spaced(level)).then_some(0)
+    } else if (matches!(first, '-' | '+') && spaced(1))
+        || (matches!(first, '-' | '=') && chars.iter().all(|&c| c == first || c == ' '))
+    {
+        Some(0)
+    } else if (1..=9).contains(&digits) && matches!(chars.get(digits), Some('.' | ')')) {
+        spaced(digits + 1).then_some(digits)
+    } else {
+        None
+    }
+}
+
+/// Convert a GitHub Markdown body to org markup
+///
+/// Markdown headings cannot live inside an org section, so they become
+/// bold lines, and indented code becomes fixed-width lines. HTML blocks
+/// are kept in an export block, and inline HTML and images with alt text
+/// in export snippets. Plain text that org would read as markup gets a
+/// zero-width space [`GUARD`], which [`to_markdown`] drops again.
+pub fn from_markdown(markdown: &str) -> String {
+    let markdown = normalize_markdown(markdown);
+    let lines: Vec<String> = markdown.lines().map(str::to_string).collect();
+    render_org(&parse_blocks(&lines), "").join("\n")
+}
+
+/// Convert a Markdown issue title to org (inline markup only)
+pub fn title_from_markdown(title: &str) -> String {
+    inline_to_org(title.trim())
+}
+
+/// A Markdown block, as far as the org conversion cares
+#[derive(Debug)]
+enum Block {
+    Blank,
+    Paragraph(Vec<String>),
+    Heading(String),
+    Code {
+        language: String,
+        lines: Vec<String>,
+    },
+    IndentedCode(Vec<String>),
+    Quote(Vec<Block>),
+    List(Vec<Item>),
+    Table(Vec<Vec<String>>),
+    Rule,
+    Comment(Vec<String>),
+    Html(Vec<String>),
+}
+
+#[derive(Debug)]
+struct Item {
+    /// `-` or an ordered marker such as `2.`
+    marker: String,
+    checkbox: Option<bool>,
+    blocks: Vec<Block>,
+}
+
+fn parse_blocks(lines: &[String]) -> Vec<Block> {
+    let mut blocks = Vec::new();
+    let mut i = 0;
+    while i < lines.len() {
+        let line = &lines[i];
+
+        if line.trim().is_empty() {
+            blocks.push(Block::Blank);
+            i += 1;
+        } else if indent_of(line) >= 4 {
+            let mut code = Vec::new();
+            while i < lines.len() && (indent_of(&lines[i]) >= 4 || lines[i].trim().is_empty()) {
+                code.push(lines[i].get(4..).unwrap_or("").to_string());
+                i += 1;
+            }
+            // Blank lines after the code are not part of it
+            let trailing = code.iter().rev().take_while(|l| l.is_empty()).count();
+            code.truncate(code.len() - trailing);
+            i -= trailing;
+            blocks.push(Block::IndentedCode(code));
+        } else if let Some((fence, language)) = fence_start(line) {
+            let indent = indent_of(line);
+            let mut code = Vec::new();
+            i += 1;
+            while i < lines.len() {
+                let l = &lines[i];
+                i += 1;
+                if l.trim_start().starts_with(&fence)
+                    && l.trim().trim_matches(fence_char(&fence)).is_empty()
+                {
+                    break;
+                }
+                code.push(l.chars().skip(indent.min(indent_of(l))).collect());
+            }
+            blocks.push(Block::Code {
+                language,
+                lines: code,
+            });
+        } else if let Some(text) = atx_heading(line) {
+            blocks.push(Block::Heading(text));
+            i += 1;
+        } else if is_rule(line) {
+            blocks.push(Block::Rule);
+            i += 1;
+        } else if line.trim_start().starts_with('>') {
+            let mut inner = Vec::new();
+            while i < lines.len() && lines[i].trim_start().starts_with('>') {
+                let l = &lines[i].trim_start()[1..];
+                inner.push(l.strip_prefix(' ').unwrap_or(l).to_string());
+                i += 1;
+            }
+            blocks.push(Block::Quote(parse_blocks(&inner)));
+        } else if list_marker(line).is_some() {
+            let (list, next) = parse_list(lines, i);
+            blocks.push(list);
+            i = next;
+        } else if line.contains('|') && lines.get(i + 1).is_some_and(|l| is_table_delimiter(l)) {
+            let mut rows = vec![table_cells(line)];
+            i += 2;
+            while i < lines.len() && lines[i].contains('|') && !lines[i].trim().is_empty() {
+                rows.push(table_cells(&lines[i]));
+                i += 1;
+            }
+            blocks.push(Block::Table(rows));
+        } else if line.trim_start().starts_with("<!--") {
+            let mut text = Vec::new();
+            while i < lines.len() {
+                let l = lines[i].trim();
+                i += 1;
+                text.push(l.to_string());
+                if l.ends_with("-->") {
+                    break;
+                }
+            }
+            let text = text.join("\n");
+            let text = text.trim_start_matches("<!--").trim_end_matches("-->");
+            blocks.push(Block::Comment(
+                text.trim_matches(' ').lines().map(str::to_string).collect(),
+            ));
+        } else if is_html_start(line) {
+            let mut html = Vec::new();
+            while i < lines.len() && !lines[i].trim().is_empty() {
+                html.push(lines[i].clone());
+                i += 1;
+            }
+            blocks.push(Block::Html(html));
+        } else {
+            let mut paragraph = vec![line.trim().to_string()];
+            let mut underlined = false;
+            i += 1;
+            while i < lines.len() && !lines[i].trim().is_empty() && !interrupts_paragraph(&lines[i])
+            {
+                // Setext heading underline
+                let t = lines[i].trim();
+                if t.chars().all(|c| c == '=') || t.chars().all(|c| c == '-') {
+                    underlined = true;
+                    i += 1;
+                    break;
+                }
+                paragraph.push(t.to_string());
+                i += 1;
+            }
+            if underlined {
+                blocks.push(Block::Heading(paragraph.join(" ")));
+            } else {
+                blocks.push(Block::Paragraph(paragraph));
+            }
+        }
+    }
+    blocks
+}
+
+/// Parse the list starting at `start`, returning it and the next line
+fn parse_list(lines: &[String], start: usize) -> (Block, usize) {
+    let (indent, first_marker, _) = list_marker(&lines[start]).expect("list item");
+    let ordered = is_ordered(&first_marker);
+    let mut items = Vec::new();
+    let mut i = start;
+
+    while i < lines.len() {
+        let Some((item_indent, marker, offset)) = list_marker(&lines[i]) else {
+            break;
+        };
+        if item_indent != indent || is_ordered(&marker) != ordered {
+            break;
+        }
+
+        let mut item_lines = vec![lines[i].chars().skip(offset).collect::<String>()];
+        i += 1;
+        while i < lines.len() {
+            let line = &lines[i];
+            if line.trim().is_empty() {
+                let next = (i..lines.len()).find(|&j| !lines[j].trim().is_empty());
+                match next {
+                    // Blank lines inside the item
+                    Some(j) if indent_of(&lines[j]) >= offset => {
+                        item_lines.push(String::new());
+                        i += 1;
+                    }
+                    // Blank lines before the next item stay with this one
+                    Some(j)
+                        if list_marker(&lines[j])
+                            .is_some_and(|(n, m, _)| n == indent && is_ordered(&m) == ordered) =>
+                    {
+                        item_lines.extend((i..j).map(|_| String::new()));
+                        i = j;
+                        break;
+                    }
+                    _ => break,
+                }
+            } else if indent_of(line) >= offset {
+                item_lines.push(line.chars().skip(offset).collect());
+                i += 1;
+            } else if list_marker(line).is_none()
+                && !interrupts_paragraph(line)
+                && item_lines.last().is_some_and(|l| !l.trim().is_empty())
+            {
+                // Lazy continuation of the item's paragraph
+                item_lines.push(line.trim().to_string());
+                i += 1;
+            } else {
+                break;
+            }
+        }
+
+        let first = &item_lines[0];
+        let checkbox = if first.starts_with("[ ] ") || first == "[ ]" {
+            Some(false)
+        } else if first.starts_with("[x] ") || first.starts_with("[X] ") {
+            Some(true)
+        } else {
+            None
+        };
+        if checkbox.is_some() {
+            item_lines[0] = first.chars().skip(4).collect();
+        }
+
+        let marker = if ordered {
+            format!("{}.", marker.trim_end_matches(['.', ')']))
+        } else {
+            "-".to_string()
+        };
+        items.push(Item {
+            marker,
+            checkbox,
+            blocks: parse_blocks(&item_lines),
+        });
+    }
+
+    (Block::List(items), i)
+}
+
+/// Render blocks as org lines, each starting with `indent`
+fn render_org(blocks: &[Block], indent: &str) -> Vec<String> {
+    let mut out = Vec::new();
+    for block in blocks {
+        match block {
+            Block::Blank => out.push(String::new()),
+            Block::Paragraph(lines) => {
+                for line in lines {
+                    // Backslash at the end of a line is a hard break
+                    match line.strip_suffix('\\') {
+                        Some(text) if !text.ends_with('\\') => {
+                            out.push(format!("{}{}\\\\", indent, paragraph_line(text)))
+                        }
+                        _ => out.push(format!("{}{}", indent, paragraph_line(line))),
+                    }
+                }
+            }
+            Block::Heading(text) => out.push(format!("{}*{}*", indent, inline_org(text, true))),
+            Block::Code { language, lines } => {
+                if language.is_empty() {
+                    out.push(format!("{}#+begin_example", indent));
+                } else {
+                    out.push(format!("{}#+begin_src {}", indent, language));
+                }
+                for line in lines {
+                    if line.trim().is_empty() {
+                        out.push(String::new());
+                    } else {
+                        out.push(format!("{}{}", indent, escape_block_line(line)));
+                    }
+                }
+                if language.is_empty() {
+                    out.push(format!("{}#+end_example", indent));
+                } else {
+                    out.push(format!("{}#+end_src", indent));
+                }
+            }
+            Block::IndentedCode(lines) => {
+                for line in lines {
+                    if line.trim().is_empty() {
+                        out.push(format!("{}:", indent));
+                    } else {
+                        out.push(format!("{}: {}", indent, line));
+                    }
+                }
+            }
+            Block::Quote(inner) => {
+                out.push(format!("{}#+begin_quote", indent));
+                out.extend(render_org(inner, indent));
+                out.push(format!("{}#+end_quote", indent));
+            }
+            Block::List(items) => {
+                for item in items {
+                    let child_indent = format!("{}{}", indent, " ".repeat(item.marker.len() + 1));
+                    let mut lines = render_org(&item.blocks, &child_indent);
+                    let checkbox = match item.checkbox {
+                        Some(true) => "[X] ",
+                        Some(false) => "[ ] ",
+                        None => "",
+                    };
+                    let head = format!("{}{} {}", indent, item.marker, checkbox);
+                    match lines.first_mut() {
+                        Some(first) if !first.is_empty() => {
+                            *first = format!("{}{}", head, &first[child_indent.len()..]);
+                        }
+                        _ => lines.insert(0, head.trim_end().to_string()),
+                    }
+                    out.extend(lines);
+                }
+            }
+            Block::Table(rows) => {
+                for (n, row) in rows.iter().enumerate() {
+                    // Org cells cannot hold a `|`, not even in code
+                    let cells: Vec<String> = row
+                        .iter()
+                        .map(|c| inline_to_org(&c.replace("\\|", "|")).replace('|', "\\vert{}"))
+                        .collect();
+                    out.push(format!("{}| {} |", indent, cells.join(" | ")));
+                    if n == 0 {
+                        let rule = vec!["---"; row.len()].join("+");
+                        out.push(format!("{}|{}|", indent, rule));
+                    }
+                }
+            }
+            Block::Rule => out.push(format!("{}-----", indent)),
+            Block::Comment(lines) if lines.len() == 1 => {
+                out.push(format!("{}# {}", indent, lines[0].trim()))
+            }
+            Block::Comment(lines) => {
+                out.push(format!("{}#+begin_comment", indent));
+                out.extend(
+                    lines
+                        .iter()
+                        .map(|l| format!("{}{}", indent, escape_block_line(l))),
+                );
+                out.push(format!("{}#+end_comment", indent));
+            }
+            Block::Html(lines) => {
+                out.push(format!("{}#+begin_export html", indent));
+                out.extend(
+                    lines
+                        .iter()
+                        .map(|l| format!("{}{}", indent, escape_block_line(l))),
+                );
+                out.push(format!("{}#+end_export", indent));
+            }
+        }
+    }
+    out
+}
+
+/// A paragraph line in org, guarded when org would read it as a heading,
+/// list item, table, keyword or drawer
+fn paragraph_line(text: &str) -> String {
+    let line = inline_to_org(text);
+    if is_paragraph(&line) && !is_drawer_line(&line) {
+        line
+    } else {
+        format!("{}{}", GUARD, line)
+    }
+}
+
+/// Convert Markdown inline markup (emphasis, code, links) to org
+fn inline_to_org(text: &str) -> String {
+    inline_org(text, false)
+}
+
+/// [`inline_to_org`], `nested` inside org emphasis that plain markers
+/// could close early
+fn inline_org(text: &str, nested: bool) -> String {
+    let chars: Vec<char> = text.chars().collect();
+    let mut out = String::new();
+    let mut i = 0;
+
+    while i < chars.len() {
+        let c = chars[i];
+        match c {
+            '\\' if chars.get(i + 1).is_some_and(|n| n.is_ascii_punctuation()) => {
+                push_plain(&mut out, chars[i + 1], unescaped(&chars, i + 2), nested);
+                i += 2;
+            }
+            '`' => {
+                let run = run_length(&chars, i);
+                match find_run(&chars, i + run, '`', run)
+                    .filter(|&end| org_markup_fits(&out, chars.get(end + run).copied()))
+                {
+                    Some(end) => {
+                        let code: String = chars[i + run..end].iter().collect();
+                        let code = code.trim();
+                        let marker = if code.contains('=') { '~' } else { '=' };
+                        out.push_str(&format!("{}{}{}", marker, code, marker));
+                        i = end + run;
+                    }
+                    None => {
+                        out.extend(&chars[i..i + run]);
+                        i += run;
+                    }
+                }
+            }
+            '!' if chars.get(i + 1) == Some(&'[') => match parse_link(&chars, i + 1) {
+                Some((alt, url, end)) if alt.is_empty() => {
+                    out.push_str(&format!("[[{}]]", url));
+                    i = end;
+                }
+                // Org images have no alt text
+                Some((_, _, end)) if snippet(&chars[i..end]).is_some() => {
+                    out.push_str(&snippet(&chars[i..end]).expect("snippet"));
+                    i = end;
+                }
+                _ => {
+                    out.push(c);
+                    i += 1;
+                }
+            },
+            '[' => match parse_link(&chars, i) {
+                // Nor can org links hold an image
+                Some((label, _, end))
+                    if label.contains("![") && snippet(&chars[i..end]).is_some() =>
+                {
+                    out.push_str(&snippet(&chars[i..end]).expect("snippet"));
+                    i = end;
+                }
+                Some((label, url, end)) => {
+                    out.push_str(&format!("[[{}][{}]]", url, inline_to_org(&label)));
+                    i = end;
+                }
+                None => {
+                    push_plain(&mut out, c, unescaped(&chars, i + 1), nested);
+                    i += 1;
+                }
+            },
+            '<' => {
+                let rest: String = chars[i..].iter().collect();
+                if let Some(end) = rest
+                    .find('>')
+                    .filter(|_| rest.starts_with("<http://") || rest.starts_with("<https://"))
+                {
+                    out.push_str(&format!("[[{}]]", &rest[1..end]));
+                    i += rest[..=end].chars().count();
+                } else if let Some(inner) = rest
+                    .strip_prefix("<ins>")
+                    .and_then(|r| r.find("</ins>").map(|end| &r[..end]))
+                    .filter(|inner| {
+                        let after = rest["<ins></ins>".len() + inner.len()..].chars().next();
+                        org_markup_fits(&out, after)
+                    })
+                {
+                    out.push_str(&format!("_{}_", inline_org(inner, true)));
+                    i += "<ins></ins>".len() + inner.chars().count();
+                } else if let Some(tag) = html_tag(&rest) {
+                    out.push_str(&format!("@@html:{}@@", tag));
+                    i += tag.chars().count();
+                } else {
+                    out.push(c);
+                    i += 1;
+                }
+            }
+            '*' | '_' | '~' => {
+                let run = run_length(&chars, i);
+                let width = match (c, run) {
+                    ('~', 2) => 2,
+                    ('~', _) => 0,
+                    (_, 1) => 1,
+                    (_, _) => 2,
+                };
+                let end = emphasis_end(&chars, i, width)
+                    .filter(|&end| org_markup_fits(&out, chars.get(end + width).copied()));
+                match end {
+                    Some(end) if width > 0 => {
+                        let inner: String = chars[i + width..end].iter().collect();
+                        let marker = match (c, width) {
+                            ('~', _) => '+',
+                            (_, 2) => '*',
+                            _ => '/',
+                        };
+                        out.push_str(&format!("{}{}{}", marker, inline_org(&inner, true), marker));
+                        i = end + width;
+                    }
+                    _ => {
+                        for j in i..i + run {
+                            push_plain(&mut out, chars[j], unescaped(&chars, j + 1), nested);
+                        }
+                        i += run;
+                    }
+                }
+            }
+            _ => {
+                push_plain(&mut out, c, unescaped(&chars, i + 1), nested);
+                i += 1;
+            }
+        }
+    }
+    out
+}
+
+/// Markdown kept as it is in an `@@markdown:...@@` snippet, unless it
+/// would close the snippet early
+fn snippet(markdown: &[char]) -> Option<String> {
+    let markdown: String = markdown.iter().collect();
+    (!markdown.contains("@@")).then(|| format!("@@markdown:{}@@", markdown))
+}
+
+/// An inline HTML tag at the start of `text`, if it is one of [`INLINE_HTML`]
+fn html_tag(text: &str) -> Option<&str> {
+    let name = text.strip_prefix('<')?;
+    let name = name.strip_prefix('/').unwrap_or(name);
+    let len = name.find(|c: char| !c.is_ascii_alphanumeric())?;
+    if !INLINE_HTML.contains(&&name[..len]) || !name[len..].starts_with([' ', '/', '>']) {
+        return None;
+    }
+    let end = text.find('>')?;
+    let tag = &text[..=end];
+    (!tag.contains("@@")).then_some(tag)
+}
+
+/// Write a character org must read as plain text, guarding emphasis
+/// markers that could open (or, `nested`, close) markup and the openings
+/// of links and entities
+fn push_plain(out: &mut String, c: char, next: Option<char>, nested: bool) {
+    let prev = out.chars().last();
+    let emphasis = "*/_=~+".contains(c);
+    let opens = emphasis && org_pre(prev) && next.is_some_and(|n| !n.is_whitespace());
+    let closes = emphasis && nested && prev.is_some_and(|p| !p.is_whitespace()) && org_post(next);
+    if opens {
+        out.push(GUARD);
+    }
+    out.push(c);
+    if closes
+        || (c == '[' && next == Some('['))
+        || (c == '\\' && next.is_some_and(|n| n.is_ascii_alphabetic()))
+    {
+        out.push(GUARD);
+    }
+}
+
+/// The character at `i` as text, past a backslash escape
+fn unescaped(chars: &[char], i: usize) -> Option<char> {
+    match chars.get(i..i + 2) {
+        Some(['\\', c]) if c.is_ascii_punctuation() => Some(*c),
+        _ => chars.get(i).copied(),
+    }
+}
+
+/// Whether org markup written after `out` and followed by `after` would be
+/// read as markup: org wants a space or punctuation on either side
+fn org_markup_fits(out: &str, after: Option<char>) -> bool {
+    org_pre(out.chars().last()) && org_post(after)
+}
+
+/// Whether org emphasis may open after `c`
+fn org_pre(c: Option<char>) -> bool {
+    c.is_none_or(|c| c.is_whitespace() || "-({\"\\".contains(c))
+}
+
+/// Whether org emphasis may close before `c`
+fn org_post(c: Option<char>) -> bool {
+    c.is_none_or(|c| c.is_whitespace() || "-.,;:!?')}[".contains(c))
+}
+
+/// Number of times the character at `start` repeats from there
+fn run_length(chars: &[char], start: usize) -> usize {
+    chars[start..]
+        .iter()
+        .take_while(|&&c| c == chars[start])
+        .count()
+}
+
+/// Start of the next run of exactly `len` `c` characters at or after `from`
+fn find_run(chars: &[char], from: usize, c: char, len: usize) -> Option<usize> {
+    let mut i = from;
+    while i < chars.len() {
+        if chars[i] == c {
+            let run = run_length(chars, i);
+            if run == len {
+                return Some(i);
+            }
+            i += run;
+        } else {
+            i += 1;
+        }
+    }
+    None
+}
+
+/// Where emphasis opened by `width` delimiters at `start` closes
+///
+/// Delimiters must hug the text, and `_` only counts between words.
+fn emphasis_end(chars: &[char], start: usize, width: usize) -> Option<usize> {
+    let c = chars[start];
+    let after = *chars.get(start + width)?;
+    let before = start.checked_sub(1).map(|p| chars[p]);
+    if after.is_whitespace() || (c == '_' && before.is_some_and(|b| b.is_alphanumeric())) {
+        return None;
+    }
+    let end = find_run(chars, start + width + 1, c, width)?;
+    let open_after = c == '_' && chars.get(end + width).is_some_and(|a| a.is_alphanumeric());
+    (!chars[end - 1].is_whitespace() && !open_after).then_some(end)
+}
+
+/// Parse `[label](url "title")` at `start`, returning label, url and the end
+fn parse_link(chars: &[char], start: usize) -> Option<(String, String, usize)> {
+    let mut depth = 0;
+    let mut close = None;
+    let mut escaped = false;
+    for (i, &c) in chars.iter().enumerate().skip(start) {
+        match c {
+            _ if std::mem::take(&mut escaped) => {}
+            '\\' => escaped = true,
+            '[' => depth += 1,
+            ']' => {
+                depth -= 1;
+                if depth == 0 {
+                    close = Some(i);
+                    break;
+                }
+            }
+            _ => {}
+        }
+    }
+    let close = close?;
+    if chars.get(close + 1) != Some(&'(') {
+        return None;
+    }
+    let end = chars[close + 2..].iter().position(|&c| c == ')')? + close + 2;
+    let target: String = chars[close + 2..end].iter().collect();
+    let url = target.split_whitespace().next()?.to_string();
+    let label = chars[start + 1..close].iter().collect();
+    Some((label, url, end + 1))
+}
+
+fn indent_of(line: &str) -> usize {
+    line.len() - line.trim_start_matches(' ').len()
+}
+
+/// Opening code fence and its language
+fn fence_start(line: &str) -> Option<(String, String)> {
+    if indent_of(line) > 3 {
+        return None;
+    }
+    let trimmed = line.trim_start();
+    let c = trimmed.chars().next().filter(|&c| c == '`' || c == '~')?;
+    let len = trimmed.chars().take_while(|&x| x == c).count();
+    let info = trimmed[len..].trim();
+    if len < 3 || (c == '`' && info.contains('`')) {
+        return None;
+    }
+    let language = info.split_whitespace().next().unwrap_or("").to_string();
+    Some((trimmed[..len].to_string(), language))
+}
+
+fn fence_char(fence: &str) -> char {
+    fence.chars().next().unwrap_or('`')
+}
+
+fn atx_heading(line: &str) -> Option<String> {
+    let trimmed = line.trim_start();
+    let level = trimmed.chars().take_while(|&c| c == '#').count();
+    let rest = &trimmed[level..];
+    if indent_of(line) > 3
+        || !(1..=6).contains(&level)
+        || !(rest.is_empty() || rest.starts_with(' '))
+    {
+        return None;
+    }
+    Some(rest.trim().trim_end_matches('#').trim_end().to_string())
+}
+
+fn is_rule(line: &str) -> bool {
+    let chars: Vec<char> = line.chars().filter(|c| !c.is_whitespace()).collect();
+    chars.len() >= 3 && ['-', '*', '_'].contains(&chars[0]) && chars.iter().all(|&c| c == chars[0])
+}
+
+/// Indentation, marker and content offset of a list item line
+fn list_marker(line: &str) -> Option<(usize, String, usize)> {
+    let indent = indent_of(line);
+    let rest = &line[indent..];
+    let marker_len = if rest.starts_with(['-', '*', '+']) {
+        1
+    } else {
+        let digits = rest.chars().take_while(char::is_ascii_digit).count();
+        if !(1..=9).contains(&digits) || !rest[digits..].starts_with(['.', ')']) {
+            return None;
+        }
+        digits + 1
+    };
+    let after = &rest[marker_len..];
+    if !(after.is_empty() || after.starts_with(' ')) {
+        return None;
+    }
+    let spaces = after.len() - after.trim_start_matches(' ').len();
+    let spaces = if (1..=4).contains(&spaces) && !after.trim().is_empty() {
+        spaces
+    } else {
+        1
+    };
+    Some((
+        indent,
+        rest[..marker_len].to_string(),
+        indent + marker_len + spaces,
+    ))
+}
+
+fn is_ordered(marker: &str) -> bool {
+    marker.starts_with(|c: char| c.is_ascii_digit())
+}
+
+/// Whether a line starts a block that ends the paragraph before it
+fn interrupts_paragraph(line: &str) -> bool {
+    let trimmed = line.trim_start();
+    fence_start(line).is_some()
+        || atx_heading(line).is_some()
+        || trimmed.starts_with('>')
+        || trimmed.starts_with("<!--")
+        || (is_rule(line) && !trimmed.starts_with('-'))
+        || list_marker(line).is_some_and(|(_, m, _)| {
+            !line.trim().eq(m.as_str()) && (!is_ordered(&m) || m.starts_with("1"))
+        })
+}
+
+fn is_table_delimiter(line: &str) -> bool {
+    let trimmed = line.trim().trim_matches('|');
+    trimmed.contains('-')
+        && trimmed.split('|').all(|cell| {
+            let cell = cell.trim().trim_start_matches(':').trim_end_matches(':');
+            !cell.is_empty() && cell.chars().all(|c| c == '-')
+        })
+}
+
+fn table_cells(line: &str) -> Vec<String> {
+    let trimmed = line.trim();
+    let trimmed = trimmed.strip_prefix('|').unwrap_or(trimmed);
+    let trimmed = match trimmed.strip_suffix('|') {
+        Some(t) if !t.ends_with('\\') => t,
+        _ => trimmed,
+    };
+    let mut cells = vec![String::new()];
+    let mut escaped = false;
+    for c in trimmed.chars() {
+        if c == '|' && !escaped {
+            cells.push(String::new());
+        } else {
+            cells.last_mut().expect("cell").push(c);
+        }
+        escaped = c == '\\';
+    }
+    cells.into_iter().map(|c| c.trim().to_string()).collect()
+}
+
+/// Whether org would read a line as the start or end of a drawer
+fn is_drawer_line(line: &str) -> bool {
+    line.trim()
+        .strip_prefix(':')
+        .and_then(|l| l.strip_suffix(':'))
+        .is_some_and(|name| {
+            !name.is_empty()
+                && name
+                    .chars()
+                    .all(|c| c.is_alphanumeric() || c == '-' || c == '_')
+        })
+}
+
+fn is_html_start(line: &str) -> bool {
+    let trimmed = line.trim_start();
+    trimmed.starts_with('<')
+        && trimmed[1..]
+            .trim_start_matches('/')
+            .starts_with(|c: char| c.is_ascii_alphabetic())
+}
+
+/// Escape a line inside an org block that org would otherwise read as
+/// a heading or block keyword
+fn escape_block_line(line: &str) -> String {
+    let trimmed = line.trim_start();
+    if trimmed.starts_with('*')
+        || trimmed.starts_with("#+")
+        || trimmed.starts_with(",*")
+        || trimmed.starts_with(",#+")
+    {
+        let indent = line.len() - trimmed.len();
+        format!("{},{}", &line[..indent], trimmed)
+    } else {
+        line.to_string()
+    }
+}
+
+/// Blank lines that followed an element in the org text
+fn blank_lines(node: &SyntaxNode) -> usize {
+    node.children_with_tokens()
+        .filter(|n| n.kind() == SyntaxKind::BLANK_LINE)
+        .count()
+}
+
+/// Text between the markers of `=verbatim=` or `~code~`
+fn inner_text(node: &SyntaxNode) -> String {
+    node.children_with_tokens()
+        .filter(|n| n.kind() == SyntaxKind::TEXT)
+        .map(|n| n.to_string())
+        .collect()
+}
+
+/// Unescaped contents of a `#+begin_...` block
+fn block_content(node: &SyntaxNode) -> String {
+    node.children()
+        .filter(|n| n.kind() == SyntaxKind::BLOCK_CONTENT)
+        .flat_map(|n| n.children_with_tokens())
+        .filter(|n| n.kind() == SyntaxKind::TEXT)
+        .map(|n| n.to_string())
+        .collect()
+}
+
+/// Inline code, with a longer fence when the code holds backticks
+fn code_span(code: &str) -> String {
+    if code.contains('`') {
+        format!("`` {} ``", code)
+    } else {
+        format!("`{}`", code)
+    }
+}
+
+/// Remove the indentation common to all non-blank lines
+fn dedent(text: &str) -> String {
+    let indent = text
+        .lines()
+        .filter(|l| !l.trim().is_empty())
+        .map(|l| l.len() - l.trim_start().len())
+        .min()
+        .unwrap_or(0);
+    text.lines()
+        .map(|l| l.get(indent..).unwrap_or("").trim_end())
+        .collect::<Vec<_>>()
+        .join("\n")
+        + "\n"
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_inline_markup() {
+        assert_eq!(
+            to_markdown("Some *bold*, /italic/, =verbatim=, ~code~ and +strike+ text"),
+            "Some **bold**, *italic*, `verbatim`, `code` and ~~strike~~ text"
+        );
+    }
+
+    #[test]
+    fn test_plain_text_unchanged() {
+        let body = "First line\nsecond line of the same paragraph\n\nNext paragraph";
+        assert_eq!(to_markdown(body), body);
+        assert_eq!(
+            to_markdown("snake_case_name and 2 < 3"),
+            "snake_case_name and 2 < 3"
+        );
+    }
+
+    #[test]
+    fn test_markdown_escapes() {
+        assert_eq!(to_markdown("Vec<String>"), "Vec\\<String>");
+        assert_eq!(to_markdown("2*3*4"), "2\\*3\\*4");
+        assert_eq!(
+            to_markdown("[x] `a` _b \\* C:\\path"),
+            "\\[x\\] \\`a\\` \\_b \\\\\\* C:\\path"
+        );
+        assert_eq!(to_markdown("a\n> b\n=="), "a\n\\> b\n\\==");
+        assert_eq!(
+            title_to_markdown("Return Vec<String>"),
+            "Return Vec\\<String>"
+        );
+        for org in ["Vec<String>", "2*3*4", "a [b] `c` d_ \\* e\\", "a\n> b"] {
+            assert_eq!(from_markdown(&to_markdown(org)), org);
+        }
+        assert_eq!(
+            title_from_markdown("Return Vec\\<String>"),
+            "Return Vec<String>"
+        );
+        assert_eq!(title_from_markdown("Multiply 2\\*3\\*4"), "Multiply 2*3*4");
+    }
+
+    #[test]
+    fn test_links() {
+        assert_eq!(
+            to_markdown("See [[https://example.com][the docs]] or [[https://example.com/raw]]."),
+            "See [the docs](https://example.com) or <https://example.com/raw>."
+        );
+    }
+
+    #[test]
+    fn test_source_block() {
+        let org = "Run this:\n\n#+begin_src rust\nfn main() {\n    println!(\"hi\");\n}\n#+end_src\n\nDone.";
+        assert_eq!(
+            to_markdown(org),
+            "Run this:\n\n```rust\nfn main() {\n    println!(\"hi\");\n}\n```\n\nDone."
+        );
+    }
+
+    #[test]
+    fn test_lists_and_checkboxes() {
+        let org = "Steps:\n- [ ] one\n- [X] two\n  still two\n  1. nested\n+ plain\n\nAfter";
+        assert_eq!(
+            to_markdown(org),
+            "Steps:\n- [ ] one\n- [x] two\n  still two\n  1. nested\n- plain\n\nAfter"
+        );
+    }
+
+    #[test]
+    fn test_list_followed_by_paragraph() {
+        // Markdown would carry the paragraph into the last item
+        assert_eq!(to_markdown("- a\n- b\nText"), "- a\n- b\n\nText");
+    }
+
+    #[test]
+    fn test_table() {
+        let org = "| Name | Count |\n|------+-------|\n| a    |     1 |\n| b    |     2 |";
+        assert_eq!(
+            to_markdown(org),
+            "| Name | Count |\n| --- | --- |\n| a | 1 |\n| b | 2 |"
+        );
+    }
+
+    #[test]
+    fn test_quote_and_rule() {
+        let org = "#+begin_quote\nQuoted\n\nagain\n#+end_quote\nText\n-----\nEnd";
+        assert_eq!(
+            to_markdown(org),
+            "> Quoted\n>\n> again\n\nText\n\n---\n\nEnd"
+        );
+    }
+
+    #[test]
+    fn test_title_to_markdown() {
+        assert_eq!(
+            title_to_markdown("Fix =parse()= panic"),
+            "Fix `parse()` panic"
+        );
+        assert_eq!(title_to_markdown("- not a list"), "- not a list");
+    }
+
+    #[test]
+    fn test_normalize_markdown() {
+        assert_eq!(
+            normalize_markdown("\r\nline  \r\nnext\r\n\r\n"),
+            "line\nnext"
+        );
+    }
+
+    #[test]
+    fn test_from_markdown_inline() {
+        assert_eq!(
+            from_markdown("Some **bold**, *italic*, `code`, ~~strike~~ and __strong__ text"),
+            "Some *bold*, /italic/, =code=, +strike+ and *strong* text"
+        );
+        assert_eq!(
+            from_markdown("snake_case_name and 2 * 3"),
+            "snake_case_name and 2 * 3"
+        );
+        assert_eq!(from_markdown("`a = b`"), "~a = b~");
+        assert_eq!(
+            from_markdown(
+                "See [the docs](https://example.com \"Docs\") or <https://example.com/raw>"
+            ),
+            "See [[https://example.com][the docs]] or [[https://example.com/raw]]"
+        );
+    }
+
+    #[test]
+    fn test_from_markdown_blocks() {
+        let md = "## Steps\n\n1. one\n2. two\n\n~~~\n* not a heading\n~~~\n\n<details>\n<summary>Log</summary>\n</details>\n\n<!-- hidden -->";
+        assert_eq!(
+            from_markdown(md),
+            "*Steps*\n\n1. one\n2. two\n\n#+begin_example\n,* not a heading\n#+end_example\n\n#+begin_export html\n<details>\n<summary>Log</summary>\n</details>\n#+end_export\n\n# hidden"
+        );
+        assert_eq!(from_markdown("Title\n=====\nText"), "*Title*\nText");
+    }
+
+    #[test]
+    fn test_markdown_round_trip() {
+        // Markdown as to_markdown writes it comes back unchanged
+        for md in [
+            "Some **bold**, *italic*, `code` and ~~strike~~ text",
+            "See [the docs](https://example.com) or <https://example.com/raw>.",
+            "Run this:\n\n```rust\nfn main() {\n    println!(\"hi\");\n}\n```\n\nDone.",
+            "Steps:\n- [ ] one\n- [x] two\n  still two\n  1. nested\n- plain\n\nAfter",
+            "- a\n\n- b\n\nText",
+            "| Name | Count |\n| --- | --- |\n| a | 1 |\n| b \\| c | 2 |",
+            "> Quoted\n>\n> again\n\nText\n\n---\n\nEnd",
+            "Line one\\\nline two",
+            "<!-- a comment -->",
+            "![](https://github.com/user-attachments/assets/1234)",
+        ] {
+            assert_eq!(to_markdown(&from_markdown(md)), md);
+        }
+    }
+
+    #[test]
+    fn test_markdown_only_constructs_round_trip() {
+        for (md, org) in [
+            (
+                "Run:\n\n    cargo build\n\n      --release\n\nDone",
+                "Run:\n\n: cargo build\n:\n:   --release\n\nDone",
+            ),
+            (
+                "[![build](https://example.com/badge.svg)](https://example.com/ci)",
+                "@@markdown:[![build](https://example.com/badge.svg)](https://example.com/ci)@@",
+            ),
+            (
+                "| Op | Code |\n| --- | --- |\n| or | `a\\|b` |",
+                "| Op | Code |\n|---+---|\n| or | =a\\vert{}b= |",
+            ),
+            (
+                "Press <kbd>Ctrl</kbd> for <b>bold</b>",
+                "Press @@html:<kbd>@@Ctrl@@html:</kbd>@@ for @@html:<b>@@bold@@html:</b>@@",
+            ),
+            (
+                "![screenshot](https://example.com/a.png)",
+                "@@markdown:![screenshot](https://example.com/a.png)@@",
+            ),
+            (
+                ":PROPERTIES:\n:ID: 1\n:END:",
+                "\u{200b}:PROPERTIES:\n:ID: 1\n\u{200b}:END:",
+            ),
+        ] {
+            assert_eq!(from_markdown(md), org);
+            assert_eq!(to_markdown(&from_markdown(md)), md);
+        }
+    }
+
+    #[test]
+    fn test_org_round_trip() {
+        // Org that converts to Markdown converts back unchanged
+        for org in [
+            "Some *bold*, /italic/, =code= and +strike+ text",
+            "See [[https://example.com][the docs]] or [[https://example.com/raw]].",
+            "#+begin_src rust\nfn main() {}\n#+end_src",
+            "- [ ] one\n- [X] two\n  1. nested\n\nAfter",
+            "| Name | Count |\n|---+---|\n| a | 1 |",
+            "#+begin_quote\nQuoted\n#+end_quote\n\n-----\n\nEnd",
+            ": cargo build\n:\n:   --release",
+            "- item\n\n  : code",
+        ] {
+            assert_eq!(from_markdown(&to_markdown(org)), org);
+        }
+    }
+
+    #[test]
+    fn test_import_guards() {
+        // Plain Markdown text that org would read as markup
+        for (md, org) in [
+            (
+                "Support /usr/local/ paths",
+                "Support \u{200b}/usr/local/ paths",
+            ),
+            ("=x=", "\u{200b}=x="),
+            ("~x~", "\u{200b}~x~"),
+            ("\\*x\\*", "\u{200b}*x*"),
+        ] {
+            assert_eq!(title_from_markdown(md), org);
+            assert_eq!(title_to_markdown(org), md);
+            assert_eq!(from_markdown(md), org);
+            assert_eq!(to_markdown(org), md);
+        }
+        // Org has no intraword emphasis, so the characters are kept
+        assert_eq!(title_from_markdown("Handle a*b*c"), "Handle a*b*c");
+        assert_eq!(title_to_markdown("Handle a*b*c"), "Handle a\\*b\\*c");
+        assert_eq!(title_from_markdown("Handle a\\*b\\*c"), "Handle a*b*c");
+
+        let md = "\\* not a heading\n\\# not a comment\n1\\. not a list\n\\[\\[x\\]\\] \\alpha";
+        assert_eq!(
+            from_markdown(md),
+            "\u{200b}* not a heading\n\u{200b}# not a comment\n\u{200b}1. not a list\n[\u{200b}[x]] \\\u{200b}alpha"
+        );
+        assert_eq!(to_markdown(&from_markdown(md)), md);
+        assert_eq!(from_markdown("*a/ b*"), "/a/\u{200b} b/");
+        assert_eq!(to_markdown("/a/\u{200b} b/"), "*a/ b*");
+    }
+
+    #[test]
+    fn test_title_from_markdown() {
+        assert_eq!(
+            title_from_markdown("Fix `parse()` panic"),
+            "Fix =parse()= panic"
+        );
+    }
+}
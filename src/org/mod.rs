@@ -1,7 +1,8 @@
+pub mod markdown;
 pub mod model;
 pub mod parser;
 pub mod writer;
 
-pub use model::{OrgFile, OrgItem, TodoState};
-pub use parser::parse_file;
+pub use model::{OrgFile, OrgItem, OrgNote, TodoKeywords, TodoState};
+pub use parser::{parse_file, parse_file_with};
 pub use writer::write_file;
@@ -1,4 +1,5 @@
-use chrono::{DateTime, Utc};
+use crate::config::OrgConfig;
+use chrono::{DateTime, NaiveDate, Utc};
 use serde::{Deserialize, Serialize};
 
 /// Represents a parsed org file with its syncable items
@@ -8,6 +9,16 @@ pub struct OrgFile {
     pub path: std::path::PathBuf,
     /// GitHub repository (from #+GH_REPO:)
     pub repo: Option<String>,
+    /// Prefix for labels from this file (from #+GH_LABEL_PREFIX:)
+    pub label_prefix: Option<String>,
+    /// Labels for new issues (from #+GH_DEFAULT_LABELS:, comma-separated)
+    pub default_labels: Vec<String>,
+    /// Filter for issues to import (from #+GH_IMPORT:, may be empty)
+    pub import: Option<String>,
+    /// Where archived subtrees go (from #+ARCHIVE:, e.g. `%s_archive::`)
+    pub archive: Option<String>,
+    /// TODO keywords in effect (in-file #+TODO: lines plus config)
+    pub todo_keywords: TodoKeywords,
     /// Raw content for writing back
     pub content: String,
     /// Syncable headings (those with TODO keywords)
@@ -17,7 +28,7 @@ pub struct OrgFile {
 /// A syncable org heading
 #[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct OrgItem {
-    /// Stable identifier (CUSTOM_ID or slugified heading)
+    /// Stable identifier (`:ID:`, `:CUSTOM_ID:` or slugified heading)
     pub id: String,
     /// Heading text (without TODO keyword)
     pub title: String,
@@ -25,72 +36,353 @@ pub struct OrgItem {
     pub body: String,
     /// TODO state
     pub state: TodoState,
+    /// Priority cookie (`[#A]` → `A`)
+    pub priority: Option<String>,
     /// GitHub issue number (from :GH_ISSUE:)
     pub gh_issue: Option<u64>,
     /// GitHub issue URL (from :GH_URL:)
     pub gh_url: Option<String>,
     /// Assignees (from :ASSIGNEE:, comma-separated)
     pub assignees: Vec<String>,
-    /// Labels (from :LABELS:, comma-separated)
+    /// Labels (from :LABELS:, comma-separated, plus tags when
+    /// `tags_as_labels` is set)
     pub labels: Vec<String>,
+    /// Tags on the headline itself
+    pub tags: Vec<String>,
+    /// Tags from `#+FILETAGS` and enclosing headings
+    pub inherited_tags: Vec<String>,
     /// When the item was created
     pub created: Option<DateTime<Utc>>,
     /// Last update timestamp
     pub updated: Option<DateTime<Utc>>,
+    /// SCHEDULED date from the planning line
+    pub scheduled: Option<NaiveDate>,
+    /// DEADLINE date from the planning line
+    pub deadline: Option<NaiveDate>,
     /// Byte range in the original content (for modifications)
     pub span: std::ops::Range<usize>,
     /// Property drawer byte range (for property updates)
     pub properties_span: Option<std::ops::Range<usize>>,
+    /// Notes from the LOGBOOK drawer (`- Note taken on [...]`)
+    pub notes: Vec<OrgNote>,
+    /// Whether a `:GH_CONFLICT:` drawer is waiting to be resolved
+    pub has_conflict: bool,
+    /// Archived: tagged `ARCHIVE` (itself or through an enclosing heading),
+    /// or carrying the `:ARCHIVE_FILE:` property org adds when archiving
+    pub archived: bool,
+    /// The `:ID:` property, as org-id assigns it
+    pub org_id: Option<String>,
+    /// Index among the file's items of the nearest enclosing heading that is
+    /// also an item (ids repeat when titles do)
+    pub parent: Option<usize>,
 }
 
-#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
-pub enum TodoState {
-    /// Open, not started (TODO)
-    Todo,
-    /// In progress (DOING)
-    Doing,
-    /// Blocked by something (BLOCKED)
-    Blocked,
-    /// Waiting for external input (WAITING)
-    Waiting,
-    /// Completed (DONE)
-    Done,
-    /// Cancelled/won't do (CANCELLED, WONTFIX)
-    Cancelled,
+/// A note added with `org-add-note`, as recorded in the LOGBOOK drawer
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct OrgNote {
+    /// Inactive timestamp from the note heading, e.g. `[2026-01-10 Sat 09:15]`
+    pub timestamp: String,
+    /// Note text with the list indentation removed
+    pub body: String,
+}
+
+/// TODO keyword of a heading and whether it counts as open or closed
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct TodoState {
+    keyword: String,
+    done: bool,
+    /// Closed without being completed (CANCELLED, WONTFIX)
+    #[serde(default)]
+    cancelled: bool,
 }
 
 impl TodoState {
+    /// An open (not done) state such as TODO or NEXT
+    pub fn open(keyword: impl Into<String>) -> Self {
+        Self {
+            keyword: keyword.into(),
+            done: false,
+            cancelled: false,
+        }
+    }
+
+    /// A closed (done) state such as DONE
+    pub fn closed(keyword: impl Into<String>) -> Self {
+        Self {
+            keyword: keyword.into(),
+            done: true,
+            cancelled: false,
+        }
+    }
+
+    /// A closed state for work that won't be done, such as CANCELLED
+    pub fn cancelled(keyword: impl Into<String>) -> Self {
+        Self {
+            keyword: keyword.into(),
+            done: true,
+            cancelled: true,
+        }
+    }
+
     pub fn is_open(&self) -> bool {
-        matches!(
-            self,
-            TodoState::Todo | TodoState::Doing | TodoState::Blocked | TodoState::Waiting
-        )
+        !self.done
     }
 
     pub fn is_closed(&self) -> bool {
-        matches!(self, TodoState::Done | TodoState::Cancelled)
-    }
-
-    pub fn from_keyword(keyword: &str) -> Option<Self> {
-        match keyword.to_uppercase().as_str() {
-            "TODO" => Some(TodoState::Todo),
-            "DOING" => Some(TodoState::Doing),
-            "BLOCKED" => Some(TodoState::Blocked),
-            "WAITING" => Some(TodoState::Waiting),
-            "DONE" => Some(TodoState::Done),
-            "CANCELLED" | "CANCELED" | "WONTFIX" => Some(TodoState::Cancelled),
-            _ => None,
+        self.done
+    }
+
+    pub fn is_cancelled(&self) -> bool {
+        self.cancelled
+    }
+
+    /// The keyword as written in the headline
+    pub fn keyword(&self) -> &str {
+        &self.keyword
+    }
+}
+
+/// The TODO keywords in effect for a file
+///
+/// Built from in-file `#+TODO:`/`#+SEQ_TODO:`/`#+TYP_TODO:` lines, or the
+/// configured keyword lists when the file has none. Keywords match
+/// case-insensitively and resolve to their spelling in the list.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct TodoKeywords {
+    /// Open keywords, in order of precedence
+    pub todo: Vec<String>,
+    /// Closed keywords, in order of precedence
+    pub done: Vec<String>,
+    /// Closed keywords that mean "not planned" rather than completed
+    pub cancelled: Vec<String>,
+}
+
+impl Default for TodoKeywords {
+    fn default() -> Self {
+        Self::from_config(&OrgConfig::default())
+    }
+}
+
+impl TodoKeywords {
+    pub fn from_config(config: &OrgConfig) -> Self {
+        let mut keywords = Self {
+            todo: Vec::new(),
+            done: Vec::new(),
+            cancelled: config.cancelled_keywords.clone(),
+        };
+        keywords.extend(&config.todo_keywords, &config.done_keywords);
+        keywords
+    }
+
+    /// Keywords for a file: its in-file sequences, which replace the
+    /// configured lists as in org, or the configured lists without any
+    pub fn for_content(content: &str, config: &OrgConfig) -> Self {
+        let mut keywords = Self {
+            todo: Vec::new(),
+            done: Vec::new(),
+            cancelled: config.cancelled_keywords.clone(),
+        };
+        for line in content.lines() {
+            if let Some((todo, done)) = parse_todo_line(line) {
+                keywords.extend(&todo, &done);
+            }
+        }
+        if keywords.todo.is_empty() && keywords.done.is_empty() {
+            keywords.extend(&config.todo_keywords, &config.done_keywords);
         }
+        keywords
     }
 
-    pub fn to_keyword(&self) -> &'static str {
-        match self {
-            TodoState::Todo => "TODO",
-            TodoState::Doing => "DOING",
-            TodoState::Blocked => "BLOCKED",
-            TodoState::Waiting => "WAITING",
-            TodoState::Done => "DONE",
-            TodoState::Cancelled => "CANCELLED",
+    /// Add keywords not already known (a keyword keeps its first classification)
+    fn extend(&mut self, todo: &[String], done: &[String]) {
+        for kw in todo {
+            if !self.contains(kw) {
+                self.todo.push(kw.clone());
+            }
         }
+        for kw in done {
+            if !self.contains(kw) {
+                self.done.push(kw.clone());
+            }
+        }
+    }
+
+    pub fn contains(&self, keyword: &str) -> bool {
+        self.todo
+            .iter()
+            .chain(&self.done)
+            .any(|k| k.eq_ignore_ascii_case(keyword))
+    }
+
+    /// Classify a headline keyword, or `None` if it is not a TODO keyword
+    pub fn state(&self, keyword: &str) -> Option<TodoState> {
+        let find = |list: &[String]| -> Option<String> {
+            list.iter()
+                .find(|k| k.eq_ignore_ascii_case(keyword))
+                .cloned()
+        };
+        if let Some(keyword) = find(&self.todo) {
+            Some(TodoState::open(keyword))
+        } else if let Some(keyword) = find(&self.done) {
+            if find(&self.cancelled).is_some() {
+                Some(TodoState::cancelled(keyword))
+            } else {
+                Some(TodoState::closed(keyword))
+            }
+        } else {
+            None
+        }
+    }
+
+    /// Whether `keyword` is one of the cancelled keywords, in any case
+    fn is_cancelled(&self, keyword: &str) -> bool {
+        self.cancelled
+            .iter()
+            .any(|k| k.eq_ignore_ascii_case(keyword))
+    }
+
+    /// Keyword used when an issue is (re)opened on GitHub
+    pub fn default_open(&self) -> TodoState {
+        TodoState::open(self.todo.first().map(String::as_str).unwrap_or("TODO"))
+    }
+
+    /// Keyword used when an issue is closed as completed on GitHub
+    pub fn default_closed(&self) -> TodoState {
+        let keyword = self
+            .done
+            .iter()
+            .find(|k| !self.is_cancelled(k))
+            .or(self.done.first());
+        TodoState::closed(keyword.map(String::as_str).unwrap_or("DONE"))
+    }
+
+    /// Keyword used when an issue is closed as not planned on GitHub
+    ///
+    /// Falls back to the completed keyword if the file has no cancelled one.
+    pub fn default_cancelled(&self) -> TodoState {
+        match self.done.iter().find(|k| self.is_cancelled(k)) {
+            Some(keyword) => TodoState::cancelled(keyword.as_str()),
+            None => self.default_closed(),
+        }
+    }
+}
+
+/// Parse a `#+TODO:`, `#+SEQ_TODO:` or `#+TYP_TODO:` line into (todo, done)
+///
+/// Keywords after `|` are done states; without a `|`, the last keyword is
+/// the only done state. Fast-access keys like `DONE(d!)` are stripped.
+fn parse_todo_line(line: &str) -> Option<(Vec<String>, Vec<String>)> {
+    let line = line.trim();
+    let (key, value) = line.strip_prefix("#+")?.split_once(':')?;
+    if !["TODO", "SEQ_TODO", "TYP_TODO"]
+        .iter()
+        .any(|k| key.eq_ignore_ascii_case(k))
+    {
+        return None;
+    }
+
+    let strip_key = |word: &str| -> String {
+        word.split_once('(')
+            .map(|(kw, _)| kw)
+            .unwrap_or(word)
+            .to_string()
+    };
+
+    let (todo, done): (Vec<String>, Vec<String>) = match value.split_once('|') {
+        Some((todo, done)) => (
+            todo.split_whitespace().map(strip_key).collect(),
+            done.split_whitespace().map(strip_key).collect(),
+        ),
+        None => {
+            let mut words: Vec<String> = value.split_whitespace().map(strip_key).collect();
+            let last = words.pop();
+            (words, last.into_iter().collect())
+        }
+    };
+
+    Some((todo, done))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_parse_todo_line() {
+        assert_eq!(
+            parse_todo_line("#+TODO: NEXT(n) REVIEW | SHIPPED(s!)"),
+            Some((
+                vec!["NEXT".to_string(), "REVIEW".to_string()],
+                vec!["SHIPPED".to_string()]
+            ))
+        );
+        assert_eq!(
+            parse_todo_line("#+seq_todo: TODO DOING DONE"),
+            Some((
+                vec!["TODO".to_string(), "DOING".to_string()],
+                vec!["DONE".to_string()]
+            ))
+        );
+        assert_eq!(parse_todo_line("#+TITLE: Roadmap"), None);
+    }
+
+    #[test]
+    fn test_keywords_for_content() {
+        let keywords = TodoKeywords::for_content(
+            "#+TODO: NEXT | SHIPPED\n* NEXT Item\n",
+            &OrgConfig::default(),
+        );
+        assert_eq!(keywords.state("NEXT"), Some(TodoState::open("NEXT")));
+        assert_eq!(
+            keywords.state("SHIPPED"),
+            Some(TodoState::closed("SHIPPED"))
+        );
+        // The file's sequence replaces the configured keywords
+        assert_eq!(keywords.state("TODO"), None);
+        assert_eq!(keywords.state("DONE"), None);
+        assert_eq!(keywords.state("next"), Some(TodoState::open("NEXT")));
+        assert_eq!(keywords.default_open(), TodoState::open("NEXT"));
+        assert_eq!(keywords.default_closed(), TodoState::closed("SHIPPED"));
+        assert_eq!(keywords.default_cancelled(), TodoState::closed("SHIPPED"));
+    }
+
+    #[test]
+    fn test_cancelled_keywords() {
+        let keywords =
+            TodoKeywords::for_content("#+TODO: TODO | DONE CANCELED\n", &OrgConfig::default());
+        assert_eq!(keywords.state("DONE"), Some(TodoState::closed("DONE")));
+        assert_eq!(
+            keywords.state("CANCELED"),
+            Some(TodoState::cancelled("CANCELED"))
+        );
+        assert_eq!(
+            keywords.default_cancelled(),
+            TodoState::cancelled("CANCELED")
+        );
+
+        // No cancelled keyword in use: not planned falls back to DONE
+        let config = OrgConfig {
+            todo_keywords: vec!["TODO".to_string()],
+            done_keywords: vec!["DONE".to_string()],
+            ..Default::default()
+        };
+        let keywords = TodoKeywords::from_config(&config);
+        assert_eq!(keywords.default_cancelled(), TodoState::closed("DONE"));
+
+        // Configured cancelled keywords match in any case
+        let config = OrgConfig {
+            cancelled_keywords: vec!["cancelled".to_string()],
+            ..Default::default()
+        };
+        let keywords = TodoKeywords::for_content(
+            "#+TODO: TODO | CANCELLED DONE
+",
+            &config,
+        );
+        assert_eq!(keywords.default_closed(), TodoState::closed("DONE"));
+        assert_eq!(
+            keywords.default_cancelled(),
+            TodoState::cancelled("CANCELLED")
+        );
     }
 }
@@ -1,30 +1,75 @@
+use crate::config::OrgConfig;
 use crate::error::Result;
-use crate::org::model::{OrgFile, OrgItem, TodoState};
-use orgize::ast::Headline;
+use crate::org::model::{OrgFile, OrgItem, OrgNote, TodoKeywords, TodoState};
+use orgize::ast::{Drawer, Headline, Timestamp};
 use orgize::export::{Container, Event, TraversalContext, Traverser};
 use orgize::rowan::ast::AstNode;
-use orgize::Org;
+use orgize::{Org, ParseConfig};
 use std::path::Path;
 
+/// Tag org puts on headings archived in place; never a label
+pub const ARCHIVE_TAG: &str = "ARCHIVE";
+
 /// Parse an org file and extract syncable items
 pub fn parse_file(path: &Path) -> Result<OrgFile> {
+    parse_file_with(path, &OrgConfig::default())
+}
+
+/// Parse an org file using the configured TODO keywords
+pub fn parse_file_with(path: &Path, config: &OrgConfig) -> Result<OrgFile> {
     let content = std::fs::read_to_string(path)?;
-    parse_content(path, &content)
+    parse_content_with(path, &content, config)
 }
 
 /// Parse org content string
 pub fn parse_content(path: &Path, content: &str) -> Result<OrgFile> {
-    let org = Org::parse(content);
+    parse_content_with(path, content, &OrgConfig::default())
+}
+
+/// Parse org content string using the configured TODO keywords
+///
+/// In-file `#+TODO:` lines replace the configured keywords.
+pub fn parse_content_with(path: &Path, content: &str, config: &OrgConfig) -> Result<OrgFile> {
+    let todo_keywords = TodoKeywords::for_content(content, config);
+    let org = ParseConfig {
+        todo_keywords: (todo_keywords.todo.clone(), todo_keywords.done.clone()),
+        ..Default::default()
+    }
+    .parse(content);
 
     // Extract file-level properties
     let repo = extract_file_property(&org, "GH_REPO");
+    let label_prefix = extract_file_property(&org, "GH_LABEL_PREFIX").filter(|p| !p.is_empty());
+    let default_labels = extract_file_property(&org, "GH_DEFAULT_LABELS")
+        .map(|labels| split_list(&labels))
+        .unwrap_or_default();
+    let import = extract_file_property(&org, "GH_IMPORT");
+    let archive = extract_file_property(&org, "ARCHIVE").filter(|a| !a.is_empty());
+    let file_tags = extract_file_property(&org, "FILETAGS")
+        .map(|tags| split_tags(&tags))
+        .unwrap_or_default();
 
     // Extract syncable items (headings with TODO keywords)
-    let items = extract_items(&org, content);
+    let mut items = extract_items(&org, content, &todo_keywords, &file_tags);
+    if config.tags_as_labels {
+        for item in &mut items {
+            let tags = item.inherited_tags.iter().chain(&item.tags);
+            for tag in tags.filter(|t| *t != ARCHIVE_TAG) {
+                if !item.labels.contains(tag) {
+                    item.labels.push(tag.clone());
+                }
+            }
+        }
+    }
 
     Ok(OrgFile {
         path: path.to_path_buf(),
         repo,
+        label_prefix,
+        default_labels,
+        import,
+        archive,
+        todo_keywords,
         content: content.to_string(),
         items,
     })
@@ -56,33 +101,76 @@ fn extract_file_property(org: &Org, key: &str) -> Option<String> {
 }
 
 /// Extract all syncable items from the org document
-fn extract_items(org: &Org, content: &str) -> Vec<OrgItem> {
+fn extract_items(
+    org: &Org,
+    content: &str,
+    keywords: &TodoKeywords,
+    file_tags: &[String],
+) -> Vec<OrgItem> {
     let mut items = Vec::new();
 
+    /// An enclosing headline: its level, item index (if it is an item) and tags
+    struct Ancestor {
+        level: usize,
+        item: Option<usize>,
+        tags: Vec<String>,
+    }
+
     struct HeadlineCollector<'a> {
         content: &'a str,
+        keywords: &'a TodoKeywords,
+        file_tags: &'a [String],
         items: &'a mut Vec<OrgItem>,
+        ancestors: Vec<Ancestor>,
     }
 
     impl Traverser for HeadlineCollector<'_> {
         fn event(&mut self, event: Event, _ctx: &mut TraversalContext) {
             if let Event::Enter(Container::Headline(headline)) = event {
+                let level = headline.level();
+                while self.ancestors.last().is_some_and(|a| a.level >= level) {
+                    self.ancestors.pop();
+                }
+                let tags: Vec<String> = headline.tags().map(|t| t.to_string()).collect();
+
+                let mut item_index = None;
                 if let Some(todo_kw) = headline.todo_keyword() {
                     // Token derefs to str
                     let kw_text: &str = &todo_kw;
-                    if let Some(state) = TodoState::from_keyword(kw_text) {
-                        if let Some(item) = parse_headline(&headline, state, self.content) {
+                    if let Some(state) = self.keywords.state(kw_text) {
+                        if let Some(mut item) = parse_headline(&headline, state, self.content) {
+                            item.parent = self.ancestors.iter().rev().find_map(|a| a.item);
+                            for tag in self
+                                .file_tags
+                                .iter()
+                                .chain(self.ancestors.iter().flat_map(|a| &a.tags))
+                            {
+                                if !item.inherited_tags.contains(tag) && !tags.contains(tag) {
+                                    item.inherited_tags.push(tag.clone());
+                                }
+                            }
+                            item.archived |= item.inherited_tags.iter().any(|t| t == ARCHIVE_TAG);
+                            item_index = Some(self.items.len());
                             self.items.push(item);
                         }
                     }
                 }
+
+                self.ancestors.push(Ancestor {
+                    level,
+                    item: item_index,
+                    tags,
+                });
             }
         }
     }
 
     let mut collector = HeadlineCollector {
         content,
+        keywords,
+        file_tags,
         items: &mut items,
+        ancestors: Vec::new(),
     };
     org.traverse(&mut collector);
     items
@@ -91,27 +179,31 @@ fn extract_items(org: &Org, content: &str) -> Vec<OrgItem> {
 /// Parse a single headline into an OrgItem
 fn parse_headline(headline: &Headline, state: TodoState, content: &str) -> Option<OrgItem> {
     // title() returns an iterator of syntax elements, collect to string
-    let title: String = headline
-        .title()
-        .map(|t| t.to_string())
-        .collect::<String>()
-        .trim()
-        .to_string();
+    let title: String = headline.title().map(|t| t.to_string()).collect::<String>();
+    let title = strip_statistics_cookies(title.trim()).to_string();
 
-    // Generate stable ID from CUSTOM_ID property or slugified title
-    let id = get_property(headline, "CUSTOM_ID").unwrap_or_else(|| slugify(&title));
+    // Stable ID from the org-id ID property, CUSTOM_ID or slugified title
+    let org_id = get_property(headline, "ID").filter(|id| !id.is_empty());
+    let id = org_id
+        .clone()
+        .or_else(|| get_property(headline, "CUSTOM_ID"))
+        .unwrap_or_else(|| slugify(&title));
 
     // Extract properties
     let gh_issue = get_property(headline, "GH_ISSUE").and_then(|s| s.parse().ok());
     let gh_url = get_property(headline, "GH_URL");
     let assignees = get_property(headline, "ASSIGNEE")
-        .map(|s| s.split(',').map(|a| a.trim().to_string()).collect())
+        .map(|s| split_list(&s))
         .unwrap_or_default();
     let labels = get_property(headline, "LABELS")
-        .map(|s| s.split(',').map(|l| l.trim().to_string()).collect())
+        .map(|s| split_list(&s))
         .unwrap_or_default();
+    let priority = headline.priority().map(|p| p.to_string());
+    let tags: Vec<String> = headline.tags().map(|t| t.to_string()).collect();
     let created = get_property(headline, "CREATED").and_then(|s| parse_datetime(&s));
     let updated = get_property(headline, "UPDATED").and_then(|s| parse_datetime(&s));
+    let scheduled = headline.scheduled().and_then(|ts| timestamp_date(&ts));
+    let deadline = headline.deadline().and_then(|ts| timestamp_date(&ts));
 
     // Get text range for the headline
     let range = headline.syntax().text_range();
@@ -119,6 +211,10 @@ fn parse_headline(headline: &Headline, state: TodoState, content: &str) -> Optio
 
     // Extract body (section content)
     let body = extract_body(headline, content);
+    let notes = extract_notes(headline, content);
+    let has_conflict = has_drawer(headline, "GH_CONFLICT");
+    let archived =
+        tags.iter().any(|t| t == ARCHIVE_TAG) || get_property(headline, "ARCHIVE_FILE").is_some();
 
     // Get property drawer span
     let properties_span = headline.properties().map(|props| {
@@ -131,14 +227,24 @@ fn parse_headline(headline: &Headline, state: TodoState, content: &str) -> Optio
         title,
         body,
         state,
+        priority,
         gh_issue,
         gh_url,
         assignees,
         labels,
+        tags,
+        inherited_tags: Vec::new(),
         created,
         updated,
+        scheduled,
+        deadline,
         span,
         properties_span,
+        notes,
+        has_conflict,
+        archived,
+        org_id,
+        parent: None,
     })
 }
 
@@ -156,20 +262,164 @@ fn get_property(headline: &Headline, key: &str) -> Option<String> {
 }
 
 /// Extract body content from a headline section
+///
+/// Drawers (LOGBOOK and friends) are sync metadata rather than issue text,
+/// so they are left out of the body.
 fn extract_body(headline: &Headline, content: &str) -> String {
-    if let Some(section) = headline.section() {
-        let range = section.syntax().text_range();
+    let Some(section) = headline.section() else {
+        return String::new();
+    };
+
+    let mut body = String::new();
+    for child in section.syntax().children_with_tokens() {
+        if child.as_node().is_some_and(|n| Drawer::can_cast(n.kind())) {
+            continue;
+        }
+        let range = child.text_range();
         let start = usize::from(range.start());
         let end = usize::from(range.end());
         if end <= content.len() {
-            return content[start..end].trim().to_string();
+            body.push_str(&content[start..end]);
+        }
+    }
+    body.trim().to_string()
+}
+
+/// Split a comma-separated property value, dropping empty entries
+pub fn split_list(value: &str) -> Vec<String> {
+    value
+        .split(',')
+        .map(str::trim)
+        .filter(|v| !v.is_empty())
+        .map(str::to_string)
+        .collect()
+}
+
+/// Extract org notes from the headline's LOGBOOK drawer
+fn extract_notes(headline: &Headline, content: &str) -> Vec<OrgNote> {
+    let Some(section) = headline.section() else {
+        return Vec::new();
+    };
+
+    section
+        .syntax()
+        .children()
+        .filter_map(Drawer::cast)
+        .filter(|drawer| drawer.name().eq_ignore_ascii_case("LOGBOOK"))
+        .flat_map(|drawer| {
+            let start = usize::from(drawer.content_start());
+            let end = usize::from(drawer.content_end()).min(content.len());
+            parse_notes(content.get(start..end).unwrap_or(""))
+        })
+        .collect()
+}
+
+/// Whether the headline's own section has a drawer called `name`
+fn has_drawer(headline: &Headline, name: &str) -> bool {
+    headline.section().is_some_and(|section| {
+        section
+            .syntax()
+            .children()
+            .filter_map(Drawer::cast)
+            .any(|drawer| drawer.name().eq_ignore_ascii_case(name))
+    })
+}
+
+/// Parse `- Note taken on [timestamp] \\` list items from LOGBOOK text
+///
+/// The note text is everything indented under the item, up to the next
+/// item at the same level.
+fn parse_notes(text: &str) -> Vec<OrgNote> {
+    let mut notes = Vec::new();
+    let mut lines = text.lines().peekable();
+
+    while let Some(line) = lines.next() {
+        let indent = line.len() - line.trim_start().len();
+        let Some(rest) = line.trim_start().strip_prefix("- Note taken on ") else {
+            continue;
+        };
+        let Some(close) = rest.find(']').filter(|_| rest.starts_with('[')) else {
+            continue;
+        };
+
+        let timestamp = rest[..=close].to_string();
+        let mut body_lines = Vec::new();
+        let first = rest[close + 1..].trim().trim_end_matches("\\\\").trim();
+        if !first.is_empty() {
+            body_lines.push(first.to_string());
         }
+
+        while let Some(next) = lines.peek() {
+            let next_indent = next.len() - next.trim_start().len();
+            if !next.trim().is_empty() && next_indent <= indent {
+                break;
+            }
+            let next = lines.next().unwrap_or("");
+            body_lines.push(next.get(indent + 2..).unwrap_or(next.trim()).to_string());
+        }
+
+        notes.push(OrgNote {
+            timestamp,
+            body: body_lines.join("\n").trim().to_string(),
+        });
+    }
+
+    notes
+}
+
+/// Split a tag group such as `:infra:urgent:` into its tags
+pub fn split_tags(value: &str) -> Vec<String> {
+    value
+        .split([':', ' '])
+        .filter(|tag| !tag.is_empty())
+        .map(str::to_string)
+        .collect()
+}
+
+/// Whether `tag` can be written as an org tag
+pub fn is_valid_tag(tag: &str) -> bool {
+    !tag.is_empty()
+        && tag
+            .chars()
+            .all(|c| c.is_alphanumeric() || matches!(c, '_' | '@' | '#' | '%'))
+}
+
+/// Whether a word is a statistics cookie such as `[2/5]` or `[40%]`
+pub(crate) fn is_statistics_cookie(word: &str) -> bool {
+    let Some(inner) = word.strip_prefix('[').and_then(|w| w.strip_suffix(']')) else {
+        return false;
+    };
+    let digits = |s: &str| s.chars().all(|c| c.is_ascii_digit());
+    match inner.split_once('/') {
+        Some((done, total)) => digits(done) && digits(total),
+        None => inner.strip_suffix('%').is_some_and(digits),
+    }
+}
+
+/// Drop statistics cookies from the start and end of a title
+///
+/// Org keeps the cookie up to date; GitHub shows task list progress itself.
+pub(crate) fn strip_statistics_cookies(title: &str) -> &str {
+    let mut title = title;
+    if let Some((first, rest)) = title.split_once(char::is_whitespace) {
+        if is_statistics_cookie(first) {
+            title = rest.trim_start();
+        }
+    }
+    if let Some((rest, last)) = title.rsplit_once(char::is_whitespace) {
+        if is_statistics_cookie(last) {
+            title = rest.trim_end();
+        }
+    }
+    if is_statistics_cookie(title) {
+        ""
+    } else {
+        title
     }
-    String::new()
 }
 
 /// Convert a title to a URL-safe slug
-fn slugify(title: &str) -> String {
+pub(crate) fn slugify(title: &str) -> String {
     title
         .to_lowercase()
         .chars()
@@ -181,6 +431,18 @@ fn slugify(title: &str) -> String {
         .join("-")
 }
 
+/// Calendar date of an org timestamp
+///
+/// Like [`parse_datetime`], the date is taken as written; a time of day or
+/// repeater does not move it.
+fn timestamp_date(ts: &Timestamp) -> Option<chrono::NaiveDate> {
+    chrono::NaiveDate::from_ymd_opt(
+        ts.year_start()?.parse().ok()?,
+        ts.month_start()?.parse().ok()?,
+        ts.day_start()?.parse().ok()?,
+    )
+}
+
 /// Parse various datetime formats
 fn parse_datetime(s: &str) -> Option<chrono::DateTime<chrono::Utc>> {
     use chrono::{NaiveDate, NaiveDateTime, TimeZone, Utc};
@@ -233,7 +495,266 @@ Some body content.
         assert_eq!(file.repo, Some("owner/repo".to_string()));
         assert_eq!(file.items.len(), 2);
         assert_eq!(file.items[0].title, "First task");
-        assert_eq!(file.items[0].state, TodoState::Todo);
-        assert_eq!(file.items[1].state, TodoState::Done);
+        assert_eq!(file.items[0].state, TodoState::open("TODO"));
+        assert_eq!(file.items[1].state, TodoState::closed("DONE"));
+    }
+
+    #[test]
+    fn test_label_keywords() {
+        let content = "#+GH_REPO: owner/repo\n#+GH_LABEL_PREFIX: roadmap-\n#+GH_DEFAULT_LABELS: org-gh-managed, needs triage\n* TODO Item\n";
+        let file = parse_content(Path::new("test.org"), content).unwrap();
+        assert_eq!(file.label_prefix.as_deref(), Some("roadmap-"));
+        assert_eq!(file.default_labels, vec!["org-gh-managed", "needs triage"]);
+    }
+
+    #[test]
+    fn test_parent_items() {
+        let content = r#"* Tier 1
+** TODO Epic
+*** Notes
+**** TODO Task
+** TODO Sibling
+* TODO Top
+** TODO Task
+"#;
+        let file = parse_content(Path::new("test.org"), content).unwrap();
+        let parents: Vec<Option<usize>> = file.items.iter().map(|i| i.parent).collect();
+        assert_eq!(parents, vec![None, Some(0), None, None, Some(3)]);
+    }
+
+    #[test]
+    fn test_tags() {
+        let content = r#"#+FILETAGS: :roadmap:
+* Tier 1                                                          :infra:
+** TODO [#A] Fix outage   :urgent:infra:
+:PROPERTIES:
+:LABELS: good first issue
+:END:
+"#;
+        let file = parse_content(Path::new("test.org"), content).unwrap();
+        let item = &file.items[0];
+        assert_eq!(item.title, "Fix outage");
+        assert_eq!(item.tags, vec!["urgent", "infra"]);
+        assert_eq!(item.inherited_tags, vec!["roadmap"]);
+        assert_eq!(item.labels, vec!["good first issue"]);
+
+        let config = OrgConfig {
+            tags_as_labels: true,
+            ..Default::default()
+        };
+        let file = parse_content_with(Path::new("test.org"), content, &config).unwrap();
+        assert_eq!(
+            file.items[0].labels,
+            vec!["good first issue", "roadmap", "urgent", "infra"]
+        );
+    }
+
+    #[test]
+    fn test_planning_dates() {
+        let content = "* TODO Ship\nSCHEDULED: <2026-01-12 Mon 09:00 +1w> DEADLINE: <2026-02-01 Sun>\nBody\n* TODO Undated\n";
+        let file = parse_content(Path::new("test.org"), content).unwrap();
+        assert_eq!(
+            file.items[0].scheduled,
+            chrono::NaiveDate::from_ymd_opt(2026, 1, 12)
+        );
+        assert_eq!(
+            file.items[0].deadline,
+            chrono::NaiveDate::from_ymd_opt(2026, 2, 1)
+        );
+        assert_eq!(file.items[0].body, "Body");
+        assert_eq!(file.items[1].scheduled, None);
+        assert_eq!(file.items[1].deadline, None);
+    }
+
+    #[test]
+    fn test_priority() {
+        let content = "* TODO [#A] Fix outage\n* DONE [#C] Old\n* TODO No cookie\n";
+        let file = parse_content(Path::new("test.org"), content).unwrap();
+        let priorities: Vec<Option<&str>> =
+            file.items.iter().map(|i| i.priority.as_deref()).collect();
+        assert_eq!(priorities, vec![Some("A"), Some("C"), None]);
+        assert_eq!(file.items[0].title, "Fix outage");
+    }
+
+    #[test]
+    fn test_parse_configured_keywords() {
+        let content = r#"* DOING In progress
+* WONTFIX Rejected
+* IDEA Not a keyword
+"#;
+        let file = parse_content(Path::new("test.org"), content).unwrap();
+        assert_eq!(file.items.len(), 2);
+        assert_eq!(file.items[0].title, "In progress");
+        assert_eq!(file.items[0].state, TodoState::open("DOING"));
+        assert_eq!(file.items[1].state, TodoState::cancelled("WONTFIX"));
+
+        let config = OrgConfig {
+            todo_keywords: vec!["IDEA".to_string()],
+            done_keywords: vec!["DONE".to_string()],
+            ..Default::default()
+        };
+        let file = parse_content_with(Path::new("test.org"), content, &config).unwrap();
+        assert_eq!(file.items.len(), 1);
+        assert_eq!(file.items[0].title, "Not a keyword");
+        assert_eq!(file.items[0].state, TodoState::open("IDEA"));
+    }
+
+    #[test]
+    fn test_parse_in_file_todo_keywords() {
+        let content = r#"#+TODO: NEXT(n) REVIEW | SHIPPED(s!)
+#+TYP_TODO: ALICE BOB | FINISHED
+
+* NEXT Plan it
+* REVIEW Check it
+* SHIPPED Ship it
+* BOB Hand it off
+* FINISHED Handed off
+"#;
+        let file = parse_content(Path::new("test.org"), content).unwrap();
+        let states: Vec<_> = file.items.iter().map(|i| i.state.clone()).collect();
+        assert_eq!(
+            states,
+            vec![
+                TodoState::open("NEXT"),
+                TodoState::open("REVIEW"),
+                TodoState::closed("SHIPPED"),
+                TodoState::open("BOB"),
+                TodoState::closed("FINISHED"),
+            ]
+        );
+        assert_eq!(file.items[0].title, "Plan it");
+        assert_eq!(file.todo_keywords.default_open(), TodoState::open("NEXT"));
+
+        // Configured keywords no longer apply once the file declares its own
+        let content =
+            "#+TODO: NEXT | SHIPPED\n\n* TODO Not synced\n* DONE Nor this\n* NEXT Synced\n";
+        let file = parse_content(Path::new("test.org"), content).unwrap();
+        let titles: Vec<_> = file.items.iter().map(|i| i.title.as_str()).collect();
+        assert_eq!(titles, vec!["Synced"]);
+    }
+
+    #[test]
+    fn test_title_without_statistics_cookie() {
+        let content = "* TODO Tier 1 [2/5]\n* TODO [50%] Half done\n* TODO Step [1/2] of many\n";
+        let file = parse_content(Path::new("test.org"), content).unwrap();
+        assert_eq!(file.items[0].title, "Tier 1");
+        assert_eq!(file.items[1].title, "Half done");
+        assert_eq!(file.items[2].title, "Step [1/2] of many");
+        assert!(!is_statistics_cookie("[#A]"));
+        assert!(is_statistics_cookie("[/]"));
+    }
+
+    #[test]
+    fn test_body_excludes_logbook() {
+        let content = r#"* TODO Task
+:PROPERTIES:
+:GH_ISSUE: 1
+:END:
+:LOGBOOK:
+- Comment by @someone [2026-01-10T09:15:00Z]:
+  Hello
+:END:
+Actual body.
+"#;
+        let file = parse_content(Path::new("test.org"), content).unwrap();
+        assert_eq!(file.items[0].body, "Actual body.");
+    }
+
+    #[test]
+    fn test_conflict_drawer() {
+        let content = r#"* TODO Marked
+:GH_CONFLICT:
+- title
+  - org: Marked
+  - github: Renamed
+:END:
+Body.
+* TODO Clean
+Body.
+"#;
+        let file = parse_content(Path::new("test.org"), content).unwrap();
+        assert!(file.items[0].has_conflict);
+        assert_eq!(file.items[0].body, "Body.");
+        assert!(!file.items[1].has_conflict);
+    }
+
+    #[test]
+    fn test_heading_ids() {
+        let content = r#"* TODO Both
+:PROPERTIES:
+:CUSTOM_ID: both
+:ID: 6f1c1c52-6b5e-4a57-9d8e-2a4f3f0c9b11
+:END:
+* TODO Custom
+:PROPERTIES:
+:CUSTOM_ID: custom
+:END:
+* TODO Plain title
+"#;
+        let file = parse_content(Path::new("test.org"), content).unwrap();
+        let ids: Vec<&str> = file.items.iter().map(|i| i.id.as_str()).collect();
+        assert_eq!(
+            ids,
+            vec![
+                "6f1c1c52-6b5e-4a57-9d8e-2a4f3f0c9b11",
+                "custom",
+                "plain-title"
+            ]
+        );
+        assert_eq!(file.items[0].org_id.as_deref(), Some(ids[0]));
+        assert_eq!(file.items[1].org_id, None);
+    }
+
+    #[test]
+    fn test_archived_headings() {
+        let content = r#"* Old  :ARCHIVE:
+** DONE Inherited  :infra:
+* DONE Moved out
+:PROPERTIES:
+:ARCHIVE_FILE: ~/notes/todo.org
+:END:
+* TODO Live
+"#;
+        let config = OrgConfig {
+            tags_as_labels: true,
+            ..OrgConfig::default()
+        };
+        let file = parse_content_with(Path::new("test.org"), content, &config).unwrap();
+        let archived: Vec<bool> = file.items.iter().map(|i| i.archived).collect();
+        assert_eq!(archived, vec![true, true, false]);
+        // ARCHIVE is org's, not a label
+        assert_eq!(file.items[0].labels, vec!["infra"]);
+    }
+
+    #[test]
+    fn test_parse_logbook_notes() {
+        let content = r#"* TODO Task
+:LOGBOOK:
+- Note taken on [2026-01-10 Sat 09:15] \\
+  Talked to ops.
+
+  They want Redis checks too.
+- State "TODO"       from "DOING"      [2026-01-11 Sun 10:00]
+- Comment by @someone [2026-01-12T09:15:00Z]:
+  Hello
+- Note taken on [2026-01-13 Tue 08:00] \\
+  Short one
+:END:
+Body.
+"#;
+        let file = parse_content(Path::new("test.org"), content).unwrap();
+        assert_eq!(
+            file.items[0].notes,
+            vec![
+                OrgNote {
+                    timestamp: "[2026-01-10 Sat 09:15]".to_string(),
+                    body: "Talked to ops.\n\nThey want Redis checks too.".to_string(),
+                },
+                OrgNote {
+                    timestamp: "[2026-01-13 Tue 08:00]".to_string(),
+                    body: "Short one".to_string(),
+                },
+            ]
+        );
     }
 }
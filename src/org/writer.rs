@@ -1,5 +1,12 @@
 use crate::error::Result;
 use crate::org::model::{OrgFile, OrgItem};
+use crate::org::parser::is_statistics_cookie;
+use chrono::{DateTime, NaiveDate, Utc};
+
+/// A new org-id `:ID:`, a random UUID as `org-id-new` makes
+pub fn new_id() -> String {
+    uuid::Uuid::new_v4().to_string()
+}
 
 /// Write modifications back to an org file
 pub fn write_file(file: &OrgFile) -> Result<()> {
@@ -93,7 +100,11 @@ fn update_property_in_drawer(drawer: &str, key: &str, value: &str) -> String {
         result.push((*line).to_string());
     }
 
-    result.join("\n")
+    let mut new_drawer = result.join("\n");
+    if drawer.ends_with('\n') {
+        new_drawer.push('\n');
+    }
+    new_drawer
 }
 
 /// Insert a new property drawer after a headline
@@ -103,15 +114,20 @@ fn insert_property_drawer(content: &str, item: &OrgItem, key: &str, value: &str)
 
 /// Insert a new property drawer with multiple properties
 fn insert_property_drawer_multi(content: &str, item: &OrgItem, props: &[(&str, &str)]) -> String {
-    // Find the end of the headline line
-    let headline_start = item.span.start;
-    let headline_end = content[headline_start..]
+    // The drawer goes after the headline and its planning line
+    let headline_end = content[item.span.start..]
         .find('\n')
-        .map(|i| headline_start + i)
+        .map(|i| item.span.start + i)
         .unwrap_or(content.len());
+    let planning = planning_line(content, item);
+    let insert_at = if planning.is_empty() {
+        headline_end
+    } else {
+        planning.end - usize::from(content[..planning.end].ends_with('\n'))
+    };
 
-    let before = &content[..headline_end];
-    let after = &content[headline_end..];
+    let before = &content[..insert_at];
+    let after = &content[insert_at..];
 
     let props_str: Vec<String> = props
         .iter()
@@ -135,7 +151,7 @@ pub fn set_todo_state(content: &str, item: &OrgItem, new_state: &str) -> String
     let headline = &content[headline_start..headline_end];
 
     // Find and replace the TODO keyword
-    let old_keyword = item.state.to_keyword();
+    let old_keyword = item.state.keyword();
     if let Some(pos) = headline.find(old_keyword) {
         let before = &content[..headline_start + pos];
         let after = &content[headline_start + pos + old_keyword.len()..];
@@ -145,45 +161,802 @@ pub fn set_todo_state(content: &str, item: &OrgItem, new_state: &str) -> String
     }
 }
 
+/// Replace the title text of an item's headline
+///
+/// The stars, TODO keyword, priority cookie and tags are kept as-is.
+pub fn set_title(content: &str, item: &OrgItem, new_title: &str) -> String {
+    let headline_start = item.span.start;
+    let headline_end = content[headline_start..]
+        .find('\n')
+        .map(|i| headline_start + i)
+        .unwrap_or(content.len());
+
+    let headline = &content[headline_start..headline_end];
+    let title = headline_title_range(headline, item.state.keyword());
+
+    let before = &content[..headline_start + title.start];
+    let after = &content[headline_start + title.end..];
+    format!("{}{}{}", before, new_title.trim(), after)
+}
+
+/// Replace the tags on an item's headline (an empty list removes them)
+///
+/// The whitespace before an existing tag group is kept, so aligned tags
+/// stay aligned.
+pub fn set_tags(content: &str, item: &OrgItem, tags: &[String]) -> String {
+    let headline_start = item.span.start;
+    let headline_end = content[headline_start..]
+        .find('\n')
+        .map(|i| headline_start + i)
+        .unwrap_or(content.len());
+    let headline = content[headline_start..headline_end].trim_end();
+
+    let (text, gap) = match headline.rsplit_once(char::is_whitespace) {
+        Some((_, last)) if is_tag_group(last) => {
+            let tags_start = headline.len() - last.len();
+            let text_end = headline[..tags_start].trim_end().len();
+            (&headline[..text_end], &headline[text_end..tags_start])
+        }
+        _ => (headline, " "),
+    };
+    let new_headline = if tags.is_empty() {
+        text.to_string()
+    } else {
+        format!("{}{}:{}:", text, gap, tags.join(":"))
+    };
+
+    format!(
+        "{}{}{}",
+        &content[..headline_start],
+        new_headline,
+        &content[headline_end..]
+    )
+}
+
+/// Set or remove the priority cookie on an item's headline
+pub fn set_priority(content: &str, item: &OrgItem, priority: Option<&str>) -> String {
+    let headline_start = item.span.start;
+    let headline_end = content[headline_start..]
+        .find('\n')
+        .map(|i| headline_start + i)
+        .unwrap_or(content.len());
+    let headline = &content[headline_start..headline_end];
+
+    let start = priority_start(headline, item.state.keyword());
+    let end = match priority_cookie_len(&headline[start..]) {
+        // Drop the space after a removed cookie along with it
+        Some(cookie) if priority.is_none() => {
+            let after = &headline[start + cookie..];
+            start + cookie + (after.len() - after.trim_start().len())
+        }
+        Some(cookie) => start + cookie,
+        None => start,
+    };
+    let cookie = match priority {
+        Some(p) if end == start => format!("[#{}] ", p),
+        Some(p) => format!("[#{}]", p),
+        None => String::new(),
+    };
+
+    format!(
+        "{}{}{}",
+        &content[..headline_start + start],
+        cookie,
+        &content[headline_start + end..]
+    )
+}
+
+/// Set or remove a SCHEDULED or DEADLINE date on an item's planning line
+///
+/// An existing timestamp keeps its time of day, repeater and brackets; a new
+/// one is written as an active date. The planning line is added when needed
+/// and dropped once nothing is left on it.
+pub fn set_planning(
+    content: &str,
+    item: &OrgItem,
+    keyword: &str,
+    date: Option<NaiveDate>,
+) -> String {
+    let range = planning_line(content, item);
+    let line = content[range.clone()].trim_end_matches('\n');
+    let marker = format!("{}:", keyword);
+
+    let new_line = match (line.find(&marker), date) {
+        (Some(pos), date) => {
+            let after_marker = pos + marker.len();
+            let ts_start = after_marker
+                + (line[after_marker..].len() - line[after_marker..].trim_start().len());
+            let ts_end = line[ts_start..]
+                .find(['>', ']'])
+                .map(|i| ts_start + i + 1)
+                .unwrap_or(line.len());
+            match date {
+                Some(date) => format!(
+                    "{}{}{}",
+                    &line[..ts_start],
+                    redate_timestamp(&line[ts_start..ts_end], date),
+                    &line[ts_end..]
+                ),
+                None => {
+                    let rest = line[ts_end..].trim_start();
+                    format!("{}{}", &line[..pos], rest).trim_end().to_string()
+                }
+            }
+        }
+        (None, Some(date)) if line.is_empty() => format!("{} <{}>", marker, org_date(date)),
+        (None, Some(date)) => format!("{} {} <{}>", line, marker, org_date(date)),
+        (None, None) => return content.to_string(),
+    };
+
+    let new_line = if new_line.trim().is_empty() {
+        String::new()
+    } else if range.is_empty() && range.start == content.len() && !content.ends_with('\n') {
+        format!("\n{}", new_line)
+    } else {
+        format!("{}\n", new_line)
+    };
+    format!(
+        "{}{}{}",
+        &content[..range.start],
+        new_line,
+        &content[range.end..]
+    )
+}
+
+/// A date as org writes it in a timestamp, e.g. `2026-01-20 Tue`
+fn org_date(date: NaiveDate) -> String {
+    date.format("%Y-%m-%d %a").to_string()
+}
+
+/// Replace the date (and day name) in a timestamp, keeping everything else
+fn redate_timestamp(timestamp: &str, date: NaiveDate) -> String {
+    let Some(open) = timestamp.chars().next().filter(|c| matches!(c, '<' | '[')) else {
+        return format!("<{}>", org_date(date));
+    };
+    let inner = &timestamp[1..];
+    let date_end = inner
+        .find(|c: char| !c.is_ascii_digit() && c != '-')
+        .unwrap_or(inner.len());
+    let rest = &inner[date_end..];
+    // Drop the old day name, which may be in any language
+    let after_day = rest.trim_start();
+    let after_day = match after_day.find(|c: char| c.is_whitespace() || matches!(c, '>' | ']')) {
+        Some(end) if !after_day.starts_with(|c: char| c.is_ascii_digit()) => &after_day[end..],
+        _ => rest,
+    };
+    format!("{}{}{}", open, org_date(date), after_day)
+}
+
+/// Locate the title text within a headline line
+///
+/// Skips the stars, the TODO keyword and a priority cookie at the front, and a
+/// tag group (`:a:b:`) plus the whitespace before it at the back. Statistics
+/// cookies at either end of the title are left out as well.
+fn headline_title_range(headline: &str, keyword: &str) -> std::ops::Range<usize> {
+    let mut start = priority_start(headline, keyword);
+    if let Some(cookie) = priority_cookie_len(&headline[start..]) {
+        start += cookie;
+        start += headline[start..].len() - headline[start..].trim_start().len();
+    }
+
+    let mut end = headline.trim_end().len().max(start);
+    if let Some(tags_start) = headline[start..end].rfind(char::is_whitespace) {
+        let candidate = &headline[start + tags_start + 1..end];
+        if is_tag_group(candidate) {
+            end = start + tags_start;
+        }
+    } else if is_tag_group(&headline[start..end]) {
+        end = start;
+    }
+    end = start + headline[start..end].trim_end().len();
+
+    if let Some(first) = headline[start..end].split_whitespace().next() {
+        if is_statistics_cookie(first) {
+            start += first.len();
+            start += headline[start..end].len() - headline[start..end].trim_start().len();
+        }
+    }
+    if let Some(last) = headline[start..end].split_whitespace().last() {
+        if is_statistics_cookie(last) {
+            end = start + headline[start..end - last.len()].trim_end().len();
+        }
+    }
+
+    start..end
+}
+
+/// Offset just past the stars and TODO keyword of a headline, where a
+/// priority cookie would start
+fn priority_start(headline: &str, keyword: &str) -> usize {
+    let mut start = headline.len() - headline.trim_start_matches('*').len();
+    start += headline[start..].len() - headline[start..].trim_start().len();
+
+    let rest = &headline[start..];
+    if rest.starts_with(keyword)
+        && rest[keyword.len()..]
+            .chars()
+            .next()
+            .is_none_or(char::is_whitespace)
+    {
+        start += keyword.len();
+        start += headline[start..].len() - headline[start..].trim_start().len();
+    }
+    start
+}
+
+/// Length of a priority cookie (`[#A]`) at the start of `text`
+fn priority_cookie_len(text: &str) -> Option<usize> {
+    if !text.starts_with("[#") {
+        return None;
+    }
+    text.find(']').map(|close| close + 1)
+}
+
+/// Refresh the statistics cookies in an item's headline from its body's
+/// checkboxes
+///
+/// Only top-level checkboxes count, as with org's default
+/// `org-checkbox-hierarchical-statistics`. Headlines whose body has no
+/// checkboxes are left alone, since their cookie may count child TODOs.
+pub fn update_statistics_cookies(content: &str, item: &OrgItem, body: &str) -> String {
+    let (done, total) = checkbox_counts(body);
+    let headline_start = item.span.start;
+    let headline_end = content[headline_start..]
+        .find('\n')
+        .map(|i| headline_start + i)
+        .unwrap_or(content.len());
+    let headline = &content[headline_start..headline_end];
+    if total == 0 || !headline.split_whitespace().any(is_statistics_cookie) {
+        return content.to_string();
+    }
+
+    let headline: Vec<String> = headline
+        .split(' ')
+        .map(|word| match word {
+            w if !is_statistics_cookie(w) => w.to_string(),
+            w if w.ends_with("%]") => format!("[{}%]", done * 100 / total),
+            _ => format!("[{}/{}]", done, total),
+        })
+        .collect();
+    format!(
+        "{}{}{}",
+        &content[..headline_start],
+        headline.join(" "),
+        &content[headline_end..]
+    )
+}
+
+/// Checked and total top-level checkboxes in an org body
+fn checkbox_counts(body: &str) -> (usize, usize) {
+    let boxes: Vec<(usize, bool)> = body
+        .lines()
+        .filter_map(|line| {
+            let text = line.trim_start();
+            let indent = line.len() - text.len();
+            let (bullet, rest) = text.split_once(' ')?;
+            let is_bullet = matches!(bullet, "-" | "+")
+                || (bullet == "*" && indent > 0)
+                || (bullet.len() > 1
+                    && bullet[..bullet.len() - 1]
+                        .chars()
+                        .all(|c| c.is_ascii_digit())
+                    && bullet.ends_with(['.', ')']));
+            let checked = match rest.trim_start().get(..3)? {
+                "[ ]" | "[-]" => false,
+                "[X]" | "[x]" => true,
+                _ => return None,
+            };
+            is_bullet.then_some((indent, checked))
+        })
+        .collect();
+    let Some(top) = boxes.iter().map(|b| b.0).min() else {
+        return (0, 0);
+    };
+    let top: Vec<bool> = boxes.iter().filter(|b| b.0 == top).map(|b| b.1).collect();
+    (top.iter().filter(|&&checked| checked).count(), top.len())
+}
+
+/// Whether a word is an org tag group such as `:infra:urgent:`
+fn is_tag_group(word: &str) -> bool {
+    word.len() > 2
+        && word.starts_with(':')
+        && word.ends_with(':')
+        && word[1..word.len() - 1].split(':').all(|tag| {
+            !tag.is_empty()
+                && tag
+                    .chars()
+                    .all(|c| c.is_alphanumeric() || matches!(c, '_' | '@' | '#' | '%'))
+        })
+}
+
+/// A heading to add to a file, with its property drawer and body
+#[derive(Debug, Clone, Default)]
+pub struct NewHeading {
+    pub level: usize,
+    pub keyword: String,
+    pub priority: Option<String>,
+    pub title: String,
+    pub tags: Vec<String>,
+    pub properties: Vec<(String, String)>,
+    /// LOGBOOK entries, oldest first
+    pub logbook: Vec<String>,
+    pub body: String,
+}
+
+impl NewHeading {
+    /// The heading as org text, ending in a newline
+    pub fn to_org(&self) -> String {
+        let mut headline = format!("{} {}", "*".repeat(self.level.max(1)), self.keyword);
+        if let Some(priority) = &self.priority {
+            headline.push_str(&format!(" [#{}]", priority));
+        }
+        headline.push(' ');
+        headline.push_str(self.title.trim());
+        if !self.tags.is_empty() {
+            headline.push_str(&format!(" :{}:", self.tags.join(":")));
+        }
+
+        let mut out = headline.trim_end().to_string();
+        out.push('\n');
+        if !self.properties.is_empty() {
+            out.push_str(":PROPERTIES:\n");
+            for (key, value) in &self.properties {
+                out.push_str(&format!(":{}: {}\n", key.to_uppercase(), value));
+            }
+            out.push_str(":END:\n");
+        }
+        if !self.logbook.is_empty() {
+            out.push_str(&format!(":LOGBOOK:\n{}\n:END:\n", self.logbook.join("\n")));
+        }
+        let body = escape_body(self.body.trim());
+        if !body.is_empty() {
+            out.push_str(&body);
+            out.push('\n');
+        }
+        out
+    }
+}
+
+/// Add headings as the last children of the heading titled `parent`
+///
+/// The parent heading is added at the end of the file when there is none.
+/// The headings' levels are set to one below the parent.
+pub fn append_under_heading(content: &str, parent: &str, headings: &[NewHeading]) -> String {
+    let lines: Vec<(usize, &str)> = std::iter::once(0)
+        .chain(content.match_indices('\n').map(|(i, _)| i + 1))
+        .filter(|&start| start < content.len())
+        .map(|start| (start, content[start..].lines().next().unwrap_or("")))
+        .collect();
+    let level_of =
+        |line: &str| is_heading_line(line).then(|| line.len() - line.trim_start_matches('*').len());
+
+    let target = lines.iter().enumerate().find_map(|(i, (_, line))| {
+        let level = level_of(line)?;
+        let text = line[level..].trim();
+        let title = match text.rsplit_once(char::is_whitespace) {
+            Some((title, tags)) if is_tag_group(tags) => title.trim_end(),
+            _ => text,
+        };
+        (title == parent).then_some((i, level))
+    });
+
+    let (mut out, insert_at, level) = match target {
+        Some((i, level)) => {
+            let end = lines[i + 1..]
+                .iter()
+                .find(|(_, line)| level_of(line).is_some_and(|l| l <= level))
+                .map(|(start, _)| *start)
+                .unwrap_or(content.len());
+            // Before any blank lines that end the subtree
+            let text_end = content[..end].trim_end().len();
+            let end = content[text_end..end]
+                .find('\n')
+                .map(|i| text_end + i + 1)
+                .unwrap_or(end);
+            (content[..end].to_string(), end, level)
+        }
+        None => {
+            let mut out = content.to_string();
+            if !out.is_empty() && !out.ends_with('\n') {
+                out.push('\n');
+            }
+            out.push_str(&format!("* {}\n", parent));
+            (out, content.len(), 1)
+        }
+    };
+
+    if !out.is_empty() && !out.ends_with('\n') {
+        out.push('\n');
+    }
+    for heading in headings {
+        let heading = NewHeading {
+            level: level + 1,
+            ..heading.clone()
+        };
+        out.push_str(&heading.to_org());
+    }
+    out.push_str(&content[insert_at..]);
+    out
+}
+
+/// Cut an item's subtree out of `content` to file it in an archive
+///
+/// Returns the remaining content and the subtree, promoted to level 1 with
+/// `props` added to its property drawer, as org's archive command leaves it.
+pub fn archive_subtree(content: &str, item: &OrgItem, props: &[(&str, &str)]) -> (String, String) {
+    let offset = item.span.start;
+    let rebased = OrgItem {
+        span: 0..item.span.len(),
+        properties_span: item
+            .properties_span
+            .as_ref()
+            .map(|span| span.start - offset..span.end - offset),
+        ..item.clone()
+    };
+    let subtree = set_properties(&content[item.span.clone()], &rebased, props);
+
+    let level = subtree.len() - subtree.trim_start_matches('*').len();
+    let mut promoted: String = subtree
+        .split_inclusive('\n')
+        .map(|line| {
+            if is_heading_line(line) {
+                &line[level.saturating_sub(1)..]
+            } else {
+                line
+            }
+        })
+        .collect();
+    if !promoted.ends_with('\n') {
+        promoted.push('\n');
+    }
+
+    let remaining = format!(
+        "{}{}",
+        &content[..item.span.start],
+        &content[item.span.end..]
+    );
+    (remaining, promoted)
+}
+
+/// Replace the body text of an item's section
+///
+/// Planning lines, the property drawer and any other drawers (such as
+/// LOGBOOK) are preserved, as are child headings and the blank lines that
+/// separate the section from the next heading.
+pub fn set_body(content: &str, item: &OrgItem, new_body: &str) -> String {
+    let section_start = section_start(content, item);
+    let section_end = section_end(content, section_start);
+    let section_start = section_start.min(section_end);
+
+    let section = &content[section_start..section_end];
+    let lines: Vec<&str> = section.split_inclusive('\n').collect();
+
+    // Split the section into leading drawers, body text and later drawers
+    let mut leading = String::new();
+    let mut trailing_drawers = String::new();
+    let mut seen_text = false;
+    let mut i = 0;
+    while i < lines.len() {
+        let trimmed = lines[i].trim();
+        if is_drawer_start(trimmed) {
+            let end = lines[i..]
+                .iter()
+                .position(|l| l.trim().eq_ignore_ascii_case(":END:"))
+                .map(|p| i + p + 1)
+                .unwrap_or(lines.len());
+            let drawer: String = lines[i..end].concat();
+            if seen_text {
+                trailing_drawers.push_str(&drawer);
+            } else {
+                leading.push_str(&drawer);
+            }
+            i = end;
+            continue;
+        }
+        if !trimmed.is_empty() {
+            seen_text = true;
+        }
+        i += 1;
+    }
+
+    // Blank lines between the end of the section and the next heading
+    let trailing_blank: String = {
+        let content_lines = lines
+            .iter()
+            .rposition(|l| !l.trim().is_empty())
+            .map(|p| p + 1)
+            .unwrap_or(0);
+        lines[content_lines..].concat()
+    };
+
+    let mut new_section = leading;
+    if section_start > 0 && !content[..section_start].ends_with('\n') {
+        new_section.insert(0, '\n');
+    }
+    let body = escape_body(new_body.trim());
+    if !body.is_empty() {
+        new_section.push_str(&body);
+        new_section.push('\n');
+    }
+    new_section.push_str(&trailing_drawers);
+    new_section.push_str(&trailing_blank);
+
+    format!(
+        "{}{}{}",
+        &content[..section_start],
+        new_section,
+        &content[section_end..]
+    )
+}
+
+/// Offset where an item's section begins (after headline, planning and properties)
+fn section_start(content: &str, item: &OrgItem) -> usize {
+    let mut pos = content[item.span.start..]
+        .find('\n')
+        .map(|i| item.span.start + i + 1)
+        .unwrap_or(content.len());
+
+    let next_line = |pos: usize| -> usize {
+        content[pos..]
+            .find('\n')
+            .map(|i| pos + i + 1)
+            .unwrap_or(content.len())
+    };
+
+    // Planning line (SCHEDULED/DEADLINE/CLOSED)
+    pos = pos.max(planning_line(content, item).end);
+
+    // Property drawer
+    let line = content[pos..].lines().next().unwrap_or("").trim();
+    if line.eq_ignore_ascii_case(":PROPERTIES:") {
+        while pos < content.len() {
+            let line = content[pos..].lines().next().unwrap_or("").trim();
+            pos = next_line(pos);
+            if line.eq_ignore_ascii_case(":END:") {
+                break;
+            }
+        }
+    }
+
+    pos
+}
+
+/// Byte range of an item's planning line, newline included
+///
+/// Empty, at the start of the line after the headline, when there is none.
+fn planning_line(content: &str, item: &OrgItem) -> std::ops::Range<usize> {
+    let start = content[item.span.start..]
+        .find('\n')
+        .map(|i| item.span.start + i + 1)
+        .unwrap_or(content.len());
+    let line = content[start..].lines().next().unwrap_or("").trim_start();
+    let is_planning = ["SCHEDULED:", "DEADLINE:", "CLOSED:"]
+        .iter()
+        .any(|p| line.starts_with(p));
+    if !is_planning {
+        return start..start;
+    }
+    let end = content[start..]
+        .find('\n')
+        .map(|i| start + i + 1)
+        .unwrap_or(content.len());
+    start..end
+}
+
+/// Offset of the first heading line at or after `section_start`
+fn section_end(content: &str, section_start: usize) -> usize {
+    std::iter::once(section_start)
+        .chain(
+            content[section_start..]
+                .match_indices('\n')
+                .map(|(i, _)| section_start + i + 1),
+        )
+        .find(|&pos| is_heading_line(&content[pos..]))
+        .unwrap_or(content.len())
+}
+
+fn is_heading_line(line: &str) -> bool {
+    let stars = line.len() - line.trim_start_matches('*').len();
+    stars > 0 && line[stars..].starts_with([' ', '\t', '\n'])
+}
+
+fn is_drawer_start(trimmed: &str) -> bool {
+    trimmed.len() > 2
+        && trimmed.starts_with(':')
+        && trimmed.ends_with(':')
+        && !trimmed.eq_ignore_ascii_case(":END:")
+        && trimmed[1..trimmed.len() - 1]
+            .chars()
+            .all(|c| c.is_alphanumeric() || c == '_' || c == '-')
+}
+
+/// Indent body lines that org would otherwise parse as headings
+fn escape_body(body: &str) -> String {
+    body.lines()
+        .map(|line| {
+            if is_heading_line(line) || (!line.is_empty() && line.chars().all(|c| c == '*')) {
+                format!(" {}", line)
+            } else {
+                line.to_string()
+            }
+        })
+        .collect::<Vec<_>>()
+        .join("\n")
+}
+
 /// Append an entry to the LOGBOOK drawer
+///
+/// Only the heading's own section is searched, so a child heading's
+/// LOGBOOK is never touched. A missing drawer is created at the top of the
+/// section.
 pub fn append_to_logbook(content: &str, item: &OrgItem, entry: &str) -> String {
-    // Find existing LOGBOOK drawer or insert new one
-    let section_start = item
-        .properties_span
-        .as_ref()
-        .map(|s| s.end)
-        .unwrap_or(item.span.start);
-
-    let search_area = &content[section_start..item.span.end.min(content.len())];
-
-    if let Some(logbook_start) = search_area.find(":LOGBOOK:") {
-        // Find :END: after :LOGBOOK:
-        let abs_start = section_start + logbook_start;
-        if let Some(end_pos) = content[abs_start..].find(":END:") {
-            let insert_pos = abs_start + end_pos;
-            let before = &content[..insert_pos];
-            let after = &content[insert_pos..];
-            return format!("{}{}\n{}", before, entry, after);
-        }
-    }
-
-    // No LOGBOOK found, create one after properties
-    let insert_pos = item
-        .properties_span
-        .as_ref()
-        .map(|s| s.end)
-        .unwrap_or_else(|| {
-            // After headline line
-            content[item.span.start..]
-                .find('\n')
-                .map(|i| item.span.start + i + 1)
-                .unwrap_or(item.span.end)
-        });
+    let start = section_start(content, item);
+    let end = section_end(content, start).max(start);
+
+    let mut pos = start;
+    let mut in_logbook = false;
+    for line in content[start..end].split_inclusive('\n') {
+        let trimmed = line.trim();
+        if !in_logbook && trimmed.eq_ignore_ascii_case(":LOGBOOK:") {
+            in_logbook = true;
+        } else if in_logbook && trimmed.eq_ignore_ascii_case(":END:") {
+            return format!("{}{}\n{}", &content[..pos], entry, &content[pos..]);
+        }
+        pos += line.len();
+    }
+
+    // No LOGBOOK found, create one after planning and properties
+    let newline = if start > 0 && !content[..start].ends_with('\n') {
+        "\n"
+    } else {
+        ""
+    };
+    format!(
+        "{}{}:LOGBOOK:\n{}\n:END:\n{}",
+        &content[..start],
+        newline,
+        entry,
+        &content[start..]
+    )
+}
+
+/// Append entries to the LOGBOOK drawer, skipping any already recorded
+///
+/// An entry counts as recorded when its first line is already in the
+/// heading's section, so re-running a sync never duplicates it.
+pub fn append_logbook_entries(content: &str, item: &OrgItem, entries: &[String]) -> String {
+    let mut content = content.to_string();
+    for entry in entries {
+        let start = section_start(&content, item);
+        let end = section_end(&content, start).max(start);
+        let first_line = entry.lines().next().unwrap_or("");
+        if content[start..end]
+            .lines()
+            .any(|l| l.trim() == first_line.trim())
+        {
+            continue;
+        }
+        content = append_to_logbook(&content, item, entry);
+    }
+    content
+}
 
-    let before = &content[..insert_pos];
-    let after = &content[insert_pos..];
+/// Format a GitHub comment as a LOGBOOK entry
+///
+/// The body is indented under the list item; lines that would end the
+/// drawer or start a heading are escaped.
+pub fn comment_entry(author: &str, created_at: DateTime<Utc>, body: &str) -> String {
+    let mut entry = format!(
+        "- Comment by @{} [{}]:",
+        author,
+        created_at.format("%Y-%m-%dT%H:%M:%SZ")
+    );
+    let mut previous_blank = false;
+    for line in body.trim().lines() {
+        let line = line.trim_end();
+        // Two blank lines in a row would end the list item
+        if line.is_empty() {
+            if !previous_blank {
+                entry.push('\n');
+            }
+            previous_blank = true;
+            continue;
+        }
+        previous_blank = false;
+        entry.push('\n');
+        entry.push_str("  ");
+        if line.trim().eq_ignore_ascii_case(":END:") {
+            entry.push(',');
+        }
+        entry.push_str(line);
+    }
+    entry
+}
+
+/// Replace the drawer called `name` in an item's own section with `entries`
+///
+/// Empty `entries` remove the drawer. A missing drawer is created at the
+/// top of the section, like a LOGBOOK.
+pub fn set_drawer(content: &str, item: &OrgItem, name: &str, entries: &[String]) -> String {
+    let start = section_start(content, item);
+    let end = section_end(content, start).max(start);
+    let open = format!(":{}:", name);
+
+    let mut existing = None;
+    let mut drawer_start = None;
+    let mut pos = start;
+    for line in content[start..end].split_inclusive('\n') {
+        let trimmed = line.trim();
+        match drawer_start {
+            None if trimmed.eq_ignore_ascii_case(&open) => drawer_start = Some(pos),
+            Some(from) if trimmed.eq_ignore_ascii_case(":END:") => {
+                existing = Some(from..pos + line.len());
+                break;
+            }
+            _ => {}
+        }
+        pos += line.len();
+    }
+
+    let drawer = if entries.is_empty() {
+        String::new()
+    } else {
+        format!("{}\n{}\n:END:\n", open, entries.join("\n"))
+    };
+    match existing {
+        Some(range) => format!(
+            "{}{}{}",
+            &content[..range.start],
+            drawer,
+            &content[range.end..]
+        ),
+        None if drawer.is_empty() => content.to_string(),
+        None => {
+            let newline = if start > 0 && !content[..start].ends_with('\n') {
+                "\n"
+            } else {
+                ""
+            };
+            format!(
+                "{}{}{}{}",
+                &content[..start],
+                newline,
+                drawer,
+                &content[start..]
+            )
+        }
+    }
+}
 
-    format!("{}:LOGBOOK:\n{}\n:END:\n{}", before, entry, after)
+/// Format one conflicting field as a `:GH_CONFLICT:` drawer entry
+///
+/// Each value is a sub-item; multi-line values are indented below it.
+/// `base` is left out when the last synced value is unknown.
+pub fn conflict_entry(field: &str, org: &str, github: &str, base: Option<&str>) -> String {
+    let mut entry = format!("- {}", field);
+    let values = [("org", Some(org)), ("github", Some(github)), ("base", base)];
+    for (side, value) in values {
+        let Some(value) = value else { continue };
+        entry.push_str(&format!("\n  - {}:", side));
+        let value = value.trim();
+        if value.lines().count() == 1 {
+            entry.push(' ');
+            entry.push_str(value);
+            continue;
+        }
+        for line in value.lines() {
+            entry.push('\n');
+            if line.trim().is_empty() {
+                continue;
+            }
+            entry.push_str("    ");
+            if line.trim().eq_ignore_ascii_case(":END:") {
+                entry.push(',');
+            }
+            entry.push_str(line.trim_end());
+        }
+    }
+    entry
 }
 
 #[cfg(test)]
@@ -191,21 +964,30 @@ mod tests {
     use super::*;
     use crate::org::model::TodoState;
 
-    #[allow(dead_code)]
     fn make_test_item() -> OrgItem {
         OrgItem {
             id: "test".to_string(),
             title: "Test item".to_string(),
             body: String::new(),
-            state: TodoState::Todo,
+            state: TodoState::open("TODO"),
+            priority: None,
             gh_issue: None,
             gh_url: None,
             assignees: vec![],
             labels: vec![],
+            tags: vec![],
+            inherited_tags: vec![],
             created: None,
             updated: None,
+            scheduled: None,
+            deadline: None,
             span: 0..50,
             properties_span: Some(20..45),
+            notes: vec![],
+            has_conflict: false,
+            archived: false,
+            org_id: None,
+            parent: None,
         }
     }
 
@@ -216,10 +998,298 @@ mod tests {
         assert!(result.contains(":GH_ISSUE: 99"));
     }
 
+    #[test]
+    fn test_headline_title_range() {
+        let line = "** TODO [#A] Fix outage   :infra:urgent:";
+        let range = headline_title_range(line, "TODO");
+        assert_eq!(&line[range], "Fix outage");
+
+        let line = "* DONE Plain title";
+        let range = headline_title_range(line, "DONE");
+        assert_eq!(&line[range], "Plain title");
+
+        let line = "* TODO [33%] Tier 1 [1/3] :roadmap:";
+        let range = headline_title_range(line, "TODO");
+        assert_eq!(&line[range], "Tier 1");
+    }
+
+    #[test]
+    fn test_set_tags() {
+        let content = "* TODO Fix outage      :infra:\nBody\n";
+        let item = OrgItem {
+            span: 0..content.len(),
+            properties_span: None,
+            ..make_test_item()
+        };
+        let tags = vec!["infra".to_string(), "urgent".to_string()];
+        assert_eq!(
+            set_tags(content, &item, &tags),
+            "* TODO Fix outage      :infra:urgent:\nBody\n"
+        );
+        assert_eq!(set_tags(content, &item, &[]), "* TODO Fix outage\nBody\n");
+        assert_eq!(
+            set_tags("* TODO Plain\n", &item, &tags),
+            "* TODO Plain :infra:urgent:\n"
+        );
+    }
+
+    #[test]
+    fn test_set_planning() {
+        let item = OrgItem {
+            span: 0..10,
+            properties_span: None,
+            ..make_test_item()
+        };
+        let date = NaiveDate::from_ymd_opt(2026, 2, 1);
+
+        // Added above the property drawer
+        let content = "* TODO Ship\n:PROPERTIES:\n:GH_ISSUE: 1\n:END:\nBody\n";
+        let with_deadline = set_planning(content, &item, "DEADLINE", date);
+        assert_eq!(
+            with_deadline,
+            "* TODO Ship\nDEADLINE: <2026-02-01 Sun>\n:PROPERTIES:\n:GH_ISSUE: 1\n:END:\nBody\n"
+        );
+        let both = set_planning(&with_deadline, &item, "SCHEDULED", date);
+        assert_eq!(
+            both.lines().nth(1),
+            Some("DEADLINE: <2026-02-01 Sun> SCHEDULED: <2026-02-01 Sun>")
+        );
+
+        // Time of day and repeater survive a new date
+        let content =
+            "* TODO Ship\nSCHEDULED: <2026-01-20 Tue 10:00 +1w> DEADLINE: <2026-01-30 Fri>\n";
+        assert_eq!(
+            set_planning(content, &item, "SCHEDULED", date),
+            "* TODO Ship\nSCHEDULED: <2026-02-01 Sun 10:00 +1w> DEADLINE: <2026-01-30 Fri>\n"
+        );
+        assert_eq!(
+            set_planning(content, &item, "SCHEDULED", None),
+            "* TODO Ship\nDEADLINE: <2026-01-30 Fri>\n"
+        );
+
+        // The line goes once it is empty
+        let content = "* TODO Ship\nDEADLINE: <2026-01-30 Fri>\nBody\n";
+        assert_eq!(
+            set_planning(content, &item, "DEADLINE", None),
+            "* TODO Ship\nBody\n"
+        );
+        assert_eq!(
+            set_planning("* TODO Ship", &item, "DEADLINE", None),
+            "* TODO Ship"
+        );
+        assert_eq!(
+            set_planning("* TODO Ship", &item, "DEADLINE", date),
+            "* TODO Ship\nDEADLINE: <2026-02-01 Sun>"
+        );
+    }
+
+    #[test]
+    fn test_insert_property_drawer_after_planning() {
+        let content = "* TODO Ship\nDEADLINE: <2026-01-30 Fri>\nBody\n";
+        let item = OrgItem {
+            span: 0..content.len(),
+            properties_span: None,
+            ..make_test_item()
+        };
+        assert_eq!(
+            set_properties(content, &item, &[("GH_ISSUE", "7")]),
+            "* TODO Ship\nDEADLINE: <2026-01-30 Fri>\n:PROPERTIES:\n:GH_ISSUE: 7\n:END:\nBody\n"
+        );
+    }
+
+    #[test]
+    fn test_append_under_heading() {
+        let heading = NewHeading {
+            keyword: "TODO".to_string(),
+            priority: Some("A".to_string()),
+            title: "Imported".to_string(),
+            tags: vec!["bug".to_string()],
+            properties: vec![("GH_ISSUE".to_string(), "7".to_string())],
+            body: "* not a heading".to_string(),
+            ..Default::default()
+        };
+        let imported =
+            "** TODO [#A] Imported :bug:\n:PROPERTIES:\n:GH_ISSUE: 7\n:END:\n * not a heading\n";
+
+        let content = "* Inbox   :triage:\n** TODO Old\n\n* Done\n";
+        assert_eq!(
+            append_under_heading(content, "Inbox", std::slice::from_ref(&heading)),
+            format!("* Inbox   :triage:\n** TODO Old\n{}\n* Done\n", imported)
+        );
+
+        // Missing parents are added at the end
+        assert_eq!(
+            append_under_heading("* Done", "Inbox", &[heading]),
+            format!("* Done\n* Inbox\n{}", imported)
+        );
+    }
+
+    #[test]
+    fn test_archive_subtree() {
+        let subtree = "** DONE Ship\n:PROPERTIES:\n:GH_ISSUE: 7\n:END:\n*** TODO Step\n";
+        let content = format!("* Project\n{}** TODO Next\n", subtree);
+        let item = OrgItem {
+            span: 10..10 + subtree.len(),
+            properties_span: Some(23..55),
+            ..make_test_item()
+        };
+        let (remaining, archived) =
+            archive_subtree(&content, &item, &[("ARCHIVE_FILE", "/notes/todo.org")]);
+        assert_eq!(remaining, "* Project\n** TODO Next\n");
+        assert_eq!(
+            archived,
+            "* DONE Ship\n:PROPERTIES:\n:GH_ISSUE: 7\n:ARCHIVE_FILE: /notes/todo.org\n:END:\n** TODO Step\n"
+        );
+    }
+
+    #[test]
+    fn test_set_priority() {
+        let content = "** TODO [#B] Fix outage   :infra:\nBody\n";
+        let item = OrgItem {
+            span: 0..content.len(),
+            properties_span: None,
+            ..make_test_item()
+        };
+        assert_eq!(
+            set_priority(content, &item, Some("A")),
+            "** TODO [#A] Fix outage   :infra:\nBody\n"
+        );
+        assert_eq!(
+            set_priority(content, &item, None),
+            "** TODO Fix outage   :infra:\nBody\n"
+        );
+        assert_eq!(
+            set_priority("* TODO Plain\n", &item, Some("C")),
+            "* TODO [#C] Plain\n"
+        );
+        assert_eq!(
+            set_priority("* TODO Plain\n", &item, None),
+            "* TODO Plain\n"
+        );
+    }
+
+    #[test]
+    fn test_update_statistics_cookies() {
+        let content = "* TODO Tier 1 [0/3] [0%]\n- [X] one\n  - [ ] nested\n- [ ] two\n- [x] three\n* TODO Next [1/2]\n";
+        let item = OrgItem {
+            span: 0..content.len(),
+            properties_span: None,
+            ..make_test_item()
+        };
+        let body = "- [X] one\n  - [ ] nested\n- [ ] two\n- [x] three";
+        assert_eq!(
+            update_statistics_cookies(content, &item, body),
+            content.replacen("[0/3] [0%]", "[2/3] [66%]", 1)
+        );
+
+        // No checkboxes: the cookie may be counting child headings
+        assert_eq!(update_statistics_cookies(content, &item, "Text"), content);
+    }
+
+    #[test]
+    fn test_escape_body() {
+        assert_eq!(escape_body("* item\ntext"), " * item\ntext");
+        assert_eq!(escape_body("*bold* text"), "*bold* text");
+    }
+
+    #[test]
+    fn test_comment_entry() {
+        let at = "2026-01-10T09:15:00Z".parse().unwrap();
+        let entry = comment_entry(
+            "teammate",
+            at,
+            "Can we add Redis?\n\n\n* not a heading\n:END:\n",
+        );
+        assert_eq!(
+            entry,
+            "- Comment by @teammate [2026-01-10T09:15:00Z]:\n  Can we add Redis?\n\n  * not a heading\n  ,:END:"
+        );
+    }
+
+    #[test]
+    fn test_append_logbook_entries_skips_recorded() {
+        let content = "* TODO Parent\n:LOGBOOK:\n- Comment by @a [2026-01-10T09:15:00Z]:\n  Hi\n:END:\nBody\n** TODO Child\n:LOGBOOK:\n:END:\n";
+        let item = OrgItem {
+            span: 0..content.len(),
+            properties_span: None,
+            ..make_test_item()
+        };
+        let entries = vec![
+            "- Comment by @a [2026-01-10T09:15:00Z]:\n  Hi".to_string(),
+            "- Comment by @b [2026-01-11T10:00:00Z]:\n  Hello".to_string(),
+        ];
+        let result = append_logbook_entries(content, &item, &entries);
+        assert_eq!(
+            result,
+            "* TODO Parent\n:LOGBOOK:\n- Comment by @a [2026-01-10T09:15:00Z]:\n  Hi\n- Comment by @b [2026-01-11T10:00:00Z]:\n  Hello\n:END:\nBody\n** TODO Child\n:LOGBOOK:\n:END:\n"
+        );
+    }
+
+    #[test]
+    fn test_append_to_logbook_ignores_child_logbook() {
+        let content =
+            "* TODO Parent\nSCHEDULED: <2026-01-12 Mon>\nBody\n** TODO Child\n:LOGBOOK:\n:END:\n";
+        let item = OrgItem {
+            span: 0..content.len(),
+            properties_span: None,
+            ..make_test_item()
+        };
+        let result = append_to_logbook(content, &item, "- Entry");
+        assert_eq!(
+            result,
+            "* TODO Parent\nSCHEDULED: <2026-01-12 Mon>\n:LOGBOOK:\n- Entry\n:END:\nBody\n** TODO Child\n:LOGBOOK:\n:END:\n"
+        );
+    }
+
     #[test]
     fn test_add_property_to_drawer() {
         let drawer = ":PROPERTIES:\n:END:";
         let result = update_property_in_drawer(drawer, "GH_ISSUE", "42");
         assert!(result.contains(":GH_ISSUE: 42"));
     }
+
+    #[test]
+    fn test_update_property_keeps_trailing_newline() {
+        let drawer = ":PROPERTIES:\n:GH_ISSUE: 2\n:END:\n";
+        let result = update_property_in_drawer(drawer, "ASSIGNEE", "tftio");
+        assert_eq!(
+            result,
+            ":PROPERTIES:\n:GH_ISSUE: 2\n:ASSIGNEE: tftio\n:END:\n"
+        );
+    }
+
+    #[test]
+    fn test_set_drawer_inserts_replaces_and_removes() {
+        let content = "* TODO Task\n:PROPERTIES:\n:GH_ISSUE: 1\n:END:\nBody\n** TODO Child\n";
+        let item = OrgItem {
+            span: 0..content.len(),
+            properties_span: None,
+            ..make_test_item()
+        };
+
+        let added = set_drawer(content, &item, "GH_CONFLICT", &["- title".to_string()]);
+        assert_eq!(
+            added,
+            "* TODO Task\n:PROPERTIES:\n:GH_ISSUE: 1\n:END:\n:GH_CONFLICT:\n- title\n:END:\nBody\n** TODO Child\n"
+        );
+
+        let replaced = set_drawer(&added, &item, "GH_CONFLICT", &["- body".to_string()]);
+        assert!(replaced.contains(":GH_CONFLICT:\n- body\n:END:\nBody"));
+        assert!(!replaced.contains("- title"));
+
+        assert_eq!(set_drawer(&replaced, &item, "GH_CONFLICT", &[]), content);
+        assert_eq!(set_drawer(content, &item, "GH_CONFLICT", &[]), content);
+    }
+
+    #[test]
+    fn test_conflict_entry() {
+        assert_eq!(
+            conflict_entry("title", "Org title", "GitHub title", Some("Old title")),
+            "- title\n  - org: Org title\n  - github: GitHub title\n  - base: Old title"
+        );
+        assert_eq!(
+            conflict_entry("body", "one\n\n:END:", "gh", None),
+            "- body\n  - org:\n    one\n\n    ,:END:\n  - github: gh"
+        );
+    }
 }
@@ -96,6 +96,23 @@ impl Output for InitOutput {
     }
 }
 
+/// Output for `org-gh clone`
+#[derive(Debug, Serialize)]
+pub struct CloneOutput {
+    pub file: String,
+    pub repo: String,
+    pub issues: usize,
+}
+
+impl Output for CloneOutput {
+    fn human(&self) -> String {
+        format!(
+            "Cloned {} issues from {} into {}",
+            self.issues, self.repo, self.file
+        )
+    }
+}
+
 /// Output for `org-gh status`
 #[derive(Debug, Serialize)]
 pub struct StatusOutput {
@@ -106,6 +123,8 @@ pub struct StatusOutput {
     pub pending_creates: Vec<String>,
     pub local_changes: Vec<String>,
     pub remote_changes: Vec<String>,
+    /// Pull requests that close a linked issue
+    pub linked_pull_requests: Vec<String>,
 }
 
 impl Output for StatusOutput {
@@ -151,6 +170,14 @@ impl Output for StatusOutput {
             }
         }
 
+        if !self.linked_pull_requests.is_empty() {
+            writeln!(out).unwrap();
+            writeln!(out, "Linked pull requests:").unwrap();
+            for pr in &self.linked_pull_requests {
+                writeln!(out, "  - {}", pr).unwrap();
+            }
+        }
+
         if self.local_changes.is_empty()
             && self.remote_changes.is_empty()
             && self.pending_creates.is_empty()
@@ -360,6 +387,23 @@ mod tests {
         assert_eq!(sexp, "(\"a\" \"b\" \"c\")");
     }
 
+    #[test]
+    fn test_status_lists_linked_pull_requests() {
+        let status = StatusOutput {
+            file: "roadmap.org".to_string(),
+            repo: "owner/repo".to_string(),
+            last_sync: None,
+            synced_count: 1,
+            pending_creates: vec![],
+            local_changes: vec![],
+            remote_changes: vec![],
+            linked_pull_requests: vec!["#1: PR #7 (open) Fix the bug".to_string()],
+        };
+        let human = status.human();
+        assert!(human.contains("Linked pull requests:\n  - #1: PR #7 (open) Fix the bug\n"));
+        assert!(to_sexp(&status).contains("(linked-pull-requests . (\"#1: PR #7"));
+    }
+
     #[test]
     fn test_escape_string() {
         assert_eq!(escape_string("hello"), "hello");
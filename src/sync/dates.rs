@@ -0,0 +1,374 @@
+//! SCHEDULED and DEADLINE as date fields on a GitHub Projects board
+//!
+//! Each planning date maps to a date field named under `[sync.project]`.
+//! The issue's item on the board holds the GitHub side and the sync state
+//! the value both sides last agreed on. Dates are calendar dates: a time of
+//! day in org is neither sent nor changed.
+
+use crate::config::ProjectConfig;
+use crate::error::{Error, Result};
+use crate::github::model::{ProjectBoard, ProjectItem};
+use crate::github::GitHubClient;
+use crate::org::model::OrgItem;
+use crate::sync::diff::FieldChange;
+use crate::sync::engine::{Direction, SyncAction};
+use crate::sync::pending::PendingUpdate;
+use crate::sync::state::SyncedItem;
+use chrono::NaiveDate;
+
+/// A planning date synced with a board date field
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum PlanningDate {
+    Scheduled,
+    Deadline,
+}
+
+impl PlanningDate {
+    pub const ALL: [PlanningDate; 2] = [PlanningDate::Scheduled, PlanningDate::Deadline];
+
+    /// Planning keyword in org
+    pub fn keyword(self) -> &'static str {
+        match self {
+            PlanningDate::Scheduled => "SCHEDULED",
+            PlanningDate::Deadline => "DEADLINE",
+        }
+    }
+
+    /// Board field configured for this date, if any
+    pub fn field(self, project: &ProjectConfig) -> Option<&str> {
+        match self {
+            PlanningDate::Scheduled => project.scheduled_field.as_deref(),
+            PlanningDate::Deadline => project.deadline_field.as_deref(),
+        }
+    }
+
+    /// The date on an org item
+    pub fn of_item(self, item: &OrgItem) -> Option<NaiveDate> {
+        match self {
+            PlanningDate::Scheduled => item.scheduled,
+            PlanningDate::Deadline => item.deadline,
+        }
+    }
+
+    /// The date last synced
+    pub fn of_base(self, base: &SyncedItem) -> Option<NaiveDate> {
+        match self {
+            PlanningDate::Scheduled => base.scheduled,
+            PlanningDate::Deadline => base.deadline,
+        }
+    }
+
+    /// Record the date both sides now have
+    pub fn set_base(self, base: &mut SyncedItem, date: Option<NaiveDate>) {
+        match self {
+            PlanningDate::Scheduled => base.scheduled = date,
+            PlanningDate::Deadline => base.deadline = date,
+        }
+    }
+}
+
+/// A date to bring in line, and the value both sides end up with
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct DateChange {
+    pub date: PlanningDate,
+    /// `Push` to set the board field, `Pull` to rewrite the planning line
+    pub direction: Direction,
+    pub value: Option<NaiveDate>,
+}
+
+/// Three-way comparison of one date
+///
+/// Without a base a date set on only one side counts as added there, and
+/// dates that differ count as changed on GitHub.
+pub fn diff_date(
+    org: Option<NaiveDate>,
+    board: Option<NaiveDate>,
+    base: Option<Option<NaiveDate>>,
+) -> FieldChange {
+    let Some(base) = base else {
+        return match (org, board) {
+            _ if org == board => FieldChange::None,
+            (Some(_), None) => FieldChange::OrgChanged,
+            _ => FieldChange::GitHubChanged,
+        };
+    };
+
+    match (org != base, board != base) {
+        (false, false) => FieldChange::None,
+        (true, false) => FieldChange::OrgChanged,
+        (false, true) => FieldChange::GitHubChanged,
+        // Both moved to the same date: push to settle the base
+        (true, true) if org == board => FieldChange::OrgChanged,
+        (true, true) => FieldChange::Conflict,
+    }
+}
+
+/// Date changes for a linked item, and the dates changed on both sides
+///
+/// Conflicting dates go in `force`'s direction when given.
+pub fn plan_dates(
+    item: &OrgItem,
+    board: Option<&ProjectItem>,
+    base: Option<&SyncedItem>,
+    project: &ProjectConfig,
+    force: Option<&Direction>,
+) -> (Vec<DateChange>, Vec<PlanningDate>) {
+    let mut changes = Vec::new();
+    let mut conflicts = Vec::new();
+
+    for date in PlanningDate::ALL {
+        let Some(field) = date.field(project) else {
+            continue;
+        };
+        let org = date.of_item(item);
+        let gh = board.and_then(|b| b.dates.get(field).copied());
+        let push = DateChange {
+            date,
+            direction: Direction::Push,
+            value: org,
+        };
+        let pull = DateChange {
+            date,
+            direction: Direction::Pull,
+            value: gh,
+        };
+
+        match diff_date(org, gh, base.map(|b| date.of_base(b))) {
+            FieldChange::None => {}
+            FieldChange::OrgChanged => changes.push(push),
+            FieldChange::GitHubChanged => changes.push(pull),
+            _ => match force {
+                Some(Direction::Push) => changes.push(push),
+                Some(Direction::Pull) => changes.push(pull),
+                _ => conflicts.push(date),
+            },
+        }
+    }
+
+    (changes, conflicts)
+}
+
+/// Actions syncing an item's planning dates with the board: the
+/// changes to make, or a warning for dates changed on both sides
+pub fn date_actions(
+    item: &OrgItem,
+    issue_num: u64,
+    base: Option<&SyncedItem>,
+    board: Option<&ProjectBoard>,
+    project: Option<&ProjectConfig>,
+    force: Option<&Direction>,
+) -> Vec<SyncAction> {
+    let (Some(board), Some(project)) = (board, project) else {
+        return Vec::new();
+    };
+
+    let board_item = board.items.get(&issue_num);
+    let (changes, conflicts) = plan_dates(item, board_item, base, project, force);
+
+    let mut actions: Vec<SyncAction> = conflicts
+        .into_iter()
+        .map(|date| SyncAction::Warn {
+            message: format!(
+                "Issue #{} ({}): {} changed in org and on the project board; use --force to pick a side",
+                issue_num,
+                item.title,
+                date.keyword()
+            ),
+        })
+        .collect();
+    if !changes.is_empty() {
+        actions.push(SyncAction::SetDates {
+            issue_number: issue_num,
+            org_item: item.clone(),
+            changes,
+        });
+    }
+    actions
+}
+
+/// Dates a newly created issue gets on the board: those set in org that
+/// have a board field; others stay unsynced until there is one
+pub fn new_item_dates(item: &OrgItem, project: Option<&ProjectConfig>) -> Vec<DateChange> {
+    PlanningDate::ALL
+        .into_iter()
+        .filter(|date| project.is_some_and(|p| date.field(p).is_some()))
+        .filter_map(|date| {
+            Some(DateChange {
+                date,
+                direction: Direction::Push,
+                value: Some(date.of_item(item)?),
+            })
+        })
+        .collect()
+}
+
+/// Fetch the configured Projects board, if any date field is synced
+pub async fn fetch_board(
+    client: &GitHubClient,
+    project: Option<&ProjectConfig>,
+) -> Result<Option<ProjectBoard>> {
+    let Some(project) = project else {
+        return Ok(None);
+    };
+    let fields: Vec<&str> = PlanningDate::ALL
+        .iter()
+        .filter_map(|date| date.field(project))
+        .collect();
+    if fields.is_empty() {
+        return Ok(None);
+    }
+
+    let board = client.fetch_project(&project.owner, project.number).await?;
+    for field in fields {
+        if !board.date_fields.contains_key(field) {
+            return Err(Error::Config(format!(
+                "Project {} of {} has no date field named \"{}\"",
+                project.number, project.owner, field
+            )));
+        }
+    }
+    Ok(Some(board))
+}
+
+/// Set one date field on the issue's board item, adding the issue to the
+/// board when needed
+pub async fn push_date(
+    client: &GitHubClient,
+    board: &mut ProjectBoard,
+    project: &ProjectConfig,
+    issue_number: u64,
+    date: PlanningDate,
+    value: Option<NaiveDate>,
+) -> Result<()> {
+    let Some(field) = date.field(project) else {
+        return Ok(());
+    };
+    let current = board.items.get(&issue_number);
+    if current.and_then(|i| i.dates.get(field)).copied() == value {
+        return Ok(());
+    }
+
+    let item_id = match current {
+        Some(item) => item.id.clone(),
+        None if value.is_none() => return Ok(()),
+        None => {
+            let id = client.add_to_project(&board.id, issue_number).await?;
+            board.items.insert(
+                issue_number,
+                ProjectItem {
+                    id: id.clone(),
+                    dates: Default::default(),
+                },
+            );
+            id
+        }
+    };
+    client
+        .set_project_date(&board.id, &item_id, &board.date_fields[field], value)
+        .await?;
+
+    if let Some(item) = board.items.get_mut(&issue_number) {
+        match value {
+            Some(value) => item.dates.insert(field.to_string(), value),
+            None => item.dates.remove(field),
+        };
+    }
+    Ok(())
+}
+
+/// The planning line edits for pulled dates, if any
+pub(crate) fn pulled_dates(item: &OrgItem, changes: &[DateChange]) -> Option<PendingUpdate> {
+    let properties: Vec<(String, String)> = changes
+        .iter()
+        .filter(|change| change.direction == Direction::Pull)
+        .map(|change| {
+            let value = change.value.map(|d| d.to_string()).unwrap_or_default();
+            (change.date.keyword().to_string(), value)
+        })
+        .collect();
+    (!properties.is_empty()).then(|| PendingUpdate {
+        item: item.clone(),
+        properties,
+        logbook: Vec::new(),
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::org::parser::parse_content;
+    use std::path::Path;
+
+    fn day(d: u32) -> Option<NaiveDate> {
+        NaiveDate::from_ymd_opt(2026, 1, d)
+    }
+
+    #[test]
+    fn test_diff_date() {
+        assert_eq!(diff_date(day(5), day(5), Some(day(5))), FieldChange::None);
+        assert_eq!(
+            diff_date(day(6), day(5), Some(day(5))),
+            FieldChange::OrgChanged
+        );
+        assert_eq!(
+            diff_date(day(5), None, Some(day(5))),
+            FieldChange::GitHubChanged
+        );
+        assert_eq!(
+            diff_date(day(6), day(6), Some(day(5))),
+            FieldChange::OrgChanged
+        );
+        assert_eq!(
+            diff_date(day(6), day(7), Some(day(5))),
+            FieldChange::Conflict
+        );
+
+        // No base: a date on one side only is new there
+        assert_eq!(diff_date(day(5), None, None), FieldChange::OrgChanged);
+        assert_eq!(diff_date(None, day(5), None), FieldChange::GitHubChanged);
+        assert_eq!(diff_date(day(6), day(5), None), FieldChange::GitHubChanged);
+    }
+
+    #[test]
+    fn test_new_and_pulled_dates() {
+        let content = "* TODO Task\nSCHEDULED: <2026-01-05 Mon> DEADLINE: <2026-01-09 Fri>\n";
+        let org_file = parse_content(Path::new("todo.org"), content).unwrap();
+        let item = &org_file.items[0];
+        let project = ProjectConfig {
+            owner: "o".to_string(),
+            number: 1,
+            scheduled_field: Some("Start date".to_string()),
+            deadline_field: None,
+        };
+
+        // Only dates with a board field are pushed
+        assert_eq!(
+            new_item_dates(item, Some(&project)),
+            vec![DateChange {
+                date: PlanningDate::Scheduled,
+                direction: Direction::Push,
+                value: day(5),
+            }]
+        );
+        assert!(new_item_dates(item, None).is_empty());
+
+        let changes = [
+            DateChange {
+                date: PlanningDate::Scheduled,
+                direction: Direction::Push,
+                value: day(5),
+            },
+            DateChange {
+                date: PlanningDate::Deadline,
+                direction: Direction::Pull,
+                value: None,
+            },
+        ];
+        let update = pulled_dates(item, &changes).unwrap();
+        assert_eq!(
+            update.properties,
+            vec![("DEADLINE".to_string(), String::new())]
+        );
+        assert!(pulled_dates(item, &changes[..1]).is_none());
+    }
+}
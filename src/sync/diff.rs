@@ -1,6 +1,12 @@
+use crate::config::SyncConfig;
 use crate::github::model::GhIssue;
+use crate::org::markdown::{normalize_markdown, title_to_markdown, to_markdown};
 use crate::org::model::OrgItem;
+use crate::sync::labels::{item_labels, org_labels};
 use crate::sync::state::{hash_body, SyncedItem};
+use crate::sync::status::{gh_status, org_status, without_status_labels};
+use serde::{Deserialize, Serialize};
+use std::collections::HashMap;
 
 /// Changes detected for a single field
 #[derive(Debug, Clone, PartialEq, Eq)]
@@ -13,6 +19,31 @@ pub enum FieldChange {
     GitHubChanged,
     /// Both changed (conflict)
     Conflict,
+    /// Both changed, and the changes merge cleanly into this value
+    Merged(String),
+}
+
+/// A field kept in sync between an org heading and its issue
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum Field {
+    Title,
+    Body,
+    State,
+    Assignees,
+    Labels,
+}
+
+impl Field {
+    pub fn name(self) -> &'static str {
+        match self {
+            Field::Title => "title",
+            Field::Body => "body",
+            Field::State => "state",
+            Field::Assignees => "assignees",
+            Field::Labels => "labels",
+        }
+    }
 }
 
 /// Result of comparing org item, GitHub issue, and base state
@@ -41,6 +72,17 @@ impl DiffResult {
             || self.labels != FieldChange::None
     }
 
+    /// Change for every field, in a fixed order
+    pub fn changes(&self) -> [(Field, &FieldChange); 5] {
+        [
+            (Field::Title, &self.title),
+            (Field::Body, &self.body),
+            (Field::State, &self.state),
+            (Field::Assignees, &self.assignees),
+            (Field::Labels, &self.labels),
+        ]
+    }
+
     pub fn conflict_fields(&self) -> Vec<&'static str> {
         let mut fields = Vec::new();
         if self.title == FieldChange::Conflict {
@@ -63,16 +105,79 @@ impl DiffResult {
 }
 
 /// Compute three-way diff between org item, GitHub issue, and base state
-pub fn three_way_diff(org: &OrgItem, gh: &GhIssue, base: &SyncedItem) -> DiffResult {
+///
+/// State compares workflow status (open/closed plus status labels such as
+/// DOING), so status labels are left out of the label comparison. Titles
+/// and bodies are compared as normalized Markdown, so converting org markup
+/// is not a change; bodies edited on both sides are merged line by line
+/// when the base text is known.
+pub fn three_way_diff(
+    org: &OrgItem,
+    gh: &GhIssue,
+    base: &SyncedItem,
+    config: &SyncConfig,
+) -> DiffResult {
     DiffResult {
-        title: diff_string(&org.title, &gh.title, &base.title),
-        body: diff_body(&org.body, gh.body.as_deref().unwrap_or(""), &base.body_hash),
-        state: diff_state(org, gh, base),
+        title: diff_title(&title_to_markdown(&org.title), &gh.title, base),
+        body: diff_body(
+            &to_markdown(&org.body),
+            &normalize_markdown(gh.body.as_deref().unwrap_or("")),
+            base,
+        ),
+        state: diff_state(
+            &org_status(&org.state, config),
+            &gh_status(gh, config),
+            base,
+        ),
         assignees: diff_vec(&org.assignees, &gh.assignees, &base.assignees),
-        labels: diff_vec(&org.labels, &gh.labels, &base.labels),
+        labels: diff_labels(
+            &item_labels(org, config),
+            &org_labels(&gh.labels, config),
+            base,
+            config,
+        ),
+    }
+}
+
+/// Compare an org item and a GitHub issue that have no base state yet
+///
+/// With nothing to tell which side changed, every field that differs is a
+/// conflict.
+pub fn two_way_diff(org: &OrgItem, gh: &GhIssue, config: &SyncConfig) -> DiffResult {
+    let change = |differs: bool| {
+        if differs {
+            FieldChange::Conflict
+        } else {
+            FieldChange::None
+        }
+    };
+    DiffResult {
+        title: change(title_to_markdown(&org.title) != gh.title),
+        body: change(
+            to_markdown(&org.body) != normalize_markdown(gh.body.as_deref().unwrap_or("")),
+        ),
+        state: change(org_status(&org.state, config) != gh_status(gh, config)),
+        assignees: change(!vec_eq(&org.assignees, &gh.assignees)),
+        labels: change(!vec_eq(
+            &item_labels(org, config),
+            &org_labels(&gh.labels, config),
+        )),
     }
 }
 
+/// Whether the org item differs from the last synced state on any field
+pub fn org_has_changes(org: &OrgItem, base: &SyncedItem, config: &SyncConfig) -> bool {
+    hash_body(&title_to_markdown(&org.title)) != base.org_title_hash()
+        || hash_body(&to_markdown(&org.body)) != base.org_body_hash()
+        || org_status(&org.state, config) != base.org_state()
+        || !vec_eq(&org.assignees, &base.assignees)
+        || !vec_eq(
+            &item_labels(org, config),
+            &without_status_labels(base.org_labels(), config),
+        )
+        || !base.unposted_notes(&org.notes).is_empty()
+}
+
 fn diff_string(org: &str, gh: &str, base: &str) -> FieldChange {
     let org_changed = org != base;
     let gh_changed = gh != base;
@@ -92,12 +197,34 @@ fn diff_string(org: &str, gh: &str, base: &str) -> FieldChange {
     }
 }
 
-fn diff_body(org_body: &str, gh_body: &str, base_hash: &str) -> FieldChange {
+/// Titles as [`diff_string`], with an org title unchanged since the last
+/// sync counting as the base value even when it converts to other Markdown
+fn diff_title(org_title: &str, gh_title: &str, base: &SyncedItem) -> FieldChange {
+    let org_title = if hash_body(org_title) == base.org_title_hash() {
+        &base.title
+    } else {
+        org_title
+    };
+    diff_string(org_title, gh_title, &base.title)
+}
+
+/// Statuses as [`diff_string`], with an org status unchanged since the
+/// last sync counting as the base value even when it differs from GitHub's
+fn diff_state(org_status: &str, gh_status: &str, base: &SyncedItem) -> FieldChange {
+    let org_status = if org_status == base.org_state() {
+        &base.state
+    } else {
+        org_status
+    };
+    diff_string(org_status, gh_status, &base.state)
+}
+
+fn diff_body(org_body: &str, gh_body: &str, base: &SyncedItem) -> FieldChange {
     let org_hash = hash_body(org_body);
     let gh_hash = hash_body(gh_body);
 
-    let org_changed = org_hash != base_hash;
-    let gh_changed = gh_hash != base_hash;
+    let org_changed = org_hash != base.org_body_hash();
+    let gh_changed = gh_hash != base.body_hash;
 
     match (org_changed, gh_changed) {
         (false, false) => FieldChange::None,
@@ -107,21 +234,212 @@ fn diff_body(org_body: &str, gh_body: &str, base_hash: &str) -> FieldChange {
             if org_hash == gh_hash {
                 FieldChange::OrgChanged
             } else {
-                FieldChange::Conflict
+                // State files from before base bodies were stored only have the hash
+                base.body
+                    .as_deref()
+                    .and_then(|base| merge_body(base, org_body, gh_body))
+                    .map_or(FieldChange::Conflict, FieldChange::Merged)
             }
         }
     }
 }
 
-fn diff_state(org: &OrgItem, gh: &GhIssue, base: &SyncedItem) -> FieldChange {
-    let org_state = if org.state.is_open() {
-        "open"
-    } else {
-        "closed"
-    };
-    let gh_state = if gh.state.is_open() { "open" } else { "closed" };
+/// Three-way merge of body edits, taking task list checkboxes item by item
+///
+/// Checkbox marks are set aside for the line merge, so ticking neighbouring
+/// items on both sides does not conflict; each item then takes its mark
+/// from whichever side changed it.
+pub fn merge_body(base: &str, org: &str, gh: &str) -> Option<String> {
+    let (base, base_marks) = unmark_tasks(base);
+    let (org, org_marks) = unmark_tasks(org);
+    let (gh, gh_marks) = unmark_tasks(gh);
+    let merged = merge_text(&base, &org, &gh)?;
+
+    let mut seen: HashMap<&str, usize> = HashMap::new();
+    let lines: Vec<String> = merged
+        .lines()
+        .map(|line| {
+            let Some(mark) = task_mark(line) else {
+                return line.to_string();
+            };
+            let count = seen.entry(line).or_default();
+            let key = (line.to_string(), *count);
+            *count += 1;
+
+            let base = base_marks.get(&key);
+            let checked = match (org_marks.get(&key), gh_marks.get(&key)) {
+                (Some(org), _) if Some(org) != base => *org,
+                (_, Some(gh)) if Some(gh) != base => *gh,
+                (org, gh) => *org.or(gh).or(base).unwrap_or(&false),
+            };
+            let mark_char = if checked { "x" } else { " " };
+            format!("{}{}{}", &line[..mark], mark_char, &line[mark + 1..])
+        })
+        .collect();
+
+    Some(lines.join("\n"))
+}
+
+/// Checkbox state of each task item, keyed by its unchecked line and the
+/// number of identical lines before it
+type TaskMarks = HashMap<(String, usize), bool>;
+
+/// Body text with every task list checkbox unchecked, plus the marks
+fn unmark_tasks(body: &str) -> (String, TaskMarks) {
+    let mut marks = TaskMarks::new();
+    let mut seen: HashMap<String, usize> = HashMap::new();
+    let lines: Vec<String> = body
+        .lines()
+        .map(|line| {
+            let Some(mark) = task_mark(line) else {
+                return line.to_string();
+            };
+            let unchecked = format!("{} {}", &line[..mark], &line[mark + 1..]);
+            let count = seen.entry(unchecked.clone()).or_default();
+            marks.insert((unchecked.clone(), *count), &line[mark..=mark] != " ");
+            *count += 1;
+            unchecked
+        })
+        .collect();
+    (lines.join("\n"), marks)
+}
+
+/// Byte offset of the mark inside a Markdown task item's `[ ]`
+fn task_mark(line: &str) -> Option<usize> {
+    let text = line.trim_start();
+    let indent = line.len() - text.len();
+    let (bullet, rest) = text.split_once(' ')?;
+    let ordered = bullet.len() > 1
+        && bullet.ends_with(['.', ')'])
+        && bullet[..bullet.len() - 1]
+            .chars()
+            .all(|c| c.is_ascii_digit());
+    if !matches!(bullet, "-" | "*" | "+") && !ordered {
+        return None;
+    }
+
+    let spaces = rest.len() - rest.trim_start().len();
+    let rest = rest.trim_start();
+    let is_box = matches!(rest.get(..3), Some("[ ]" | "[x]" | "[X]"))
+        && rest[3..].chars().next().is_none_or(char::is_whitespace);
+    is_box.then_some(indent + bullet.len() + 1 + spaces + 1)
+}
+
+/// Line-based three-way merge of `org` and `gh` edits to `base`
+///
+/// Returns `None` when both sides changed overlapping (or adjacent) lines
+/// differently.
+pub fn merge_text(base: &str, org: &str, gh: &str) -> Option<String> {
+    let base: Vec<&str> = base.lines().collect();
+    let org: Vec<&str> = org.lines().collect();
+    let gh: Vec<&str> = gh.lines().collect();
+
+    let mut hunks: Vec<(Hunk, bool)> = line_hunks(&base, &org)
+        .into_iter()
+        .map(|hunk| (hunk, true))
+        .chain(line_hunks(&base, &gh).into_iter().map(|hunk| (hunk, false)))
+        .collect();
+    hunks.sort_by_key(|(hunk, _)| (hunk.start, hunk.end));
+
+    let mut merged: Vec<&str> = Vec::new();
+    let mut pos = 0;
+    let mut i = 0;
+    while i < hunks.len() {
+        // Group hunks whose base ranges overlap or touch
+        let (start, mut end) = (hunks[i].0.start, hunks[i].0.end);
+        let mut j = i + 1;
+        while j < hunks.len() && hunks[j].0.start <= end {
+            end = end.max(hunks[j].0.end);
+            j += 1;
+        }
+        let group = &hunks[i..j];
+
+        merged.extend_from_slice(&base[pos..start]);
+        let org_side = apply_hunks(&base, start, end, group, true);
+        let gh_side = apply_hunks(&base, start, end, group, false);
+        if org_side == gh_side || gh_side == base[start..end] {
+            merged.extend(org_side);
+        } else if org_side == base[start..end] {
+            merged.extend(gh_side);
+        } else {
+            return None;
+        }
+
+        pos = end;
+        i = j;
+    }
+    merged.extend_from_slice(&base[pos..]);
+
+    Some(merged.join("\n"))
+}
+
+/// Base lines `start..end` replaced by `lines` on one side
+#[derive(Debug, PartialEq, Eq)]
+struct Hunk<'a> {
+    start: usize,
+    end: usize,
+    lines: Vec<&'a str>,
+}
+
+/// Hunks turning `base` into `side`, from their longest common subsequence
+fn line_hunks<'a>(base: &[&str], side: &[&'a str]) -> Vec<Hunk<'a>> {
+    // lcs[i][j]: length of the LCS of base[i..] and side[j..]
+    let mut lcs = vec![vec![0usize; side.len() + 1]; base.len() + 1];
+    for i in (0..base.len()).rev() {
+        for j in (0..side.len()).rev() {
+            lcs[i][j] = if base[i] == side[j] {
+                lcs[i + 1][j + 1] + 1
+            } else {
+                lcs[i + 1][j].max(lcs[i][j + 1])
+            };
+        }
+    }
+
+    let mut hunks = Vec::new();
+    let (mut i, mut j) = (0, 0);
+    let (mut hunk_i, mut hunk_j) = (0, 0);
+    loop {
+        let matched = i < base.len() && j < side.len() && base[i] == side[j];
+        let done = i == base.len() && j == side.len();
+        if matched || done {
+            if hunk_i < i || hunk_j < j {
+                hunks.push(Hunk {
+                    start: hunk_i,
+                    end: i,
+                    lines: side[hunk_j..j].to_vec(),
+                });
+            }
+            if done {
+                return hunks;
+            }
+            i += 1;
+            j += 1;
+            (hunk_i, hunk_j) = (i, j);
+        } else if j == side.len() || (i < base.len() && lcs[i + 1][j] >= lcs[i][j + 1]) {
+            i += 1;
+        } else {
+            j += 1;
+        }
+    }
+}
 
-    diff_string(org_state, gh_state, &base.state)
+/// Base lines `start..end` with one side's hunks from `group` applied
+fn apply_hunks<'a>(
+    base: &[&'a str],
+    start: usize,
+    end: usize,
+    group: &[(Hunk<'a>, bool)],
+    org: bool,
+) -> Vec<&'a str> {
+    let mut out = Vec::new();
+    let mut pos = start;
+    for (hunk, _) in group.iter().filter(|(_, side)| *side == org) {
+        out.extend_from_slice(&base[pos..hunk.start]);
+        out.extend_from_slice(&hunk.lines);
+        pos = hunk.end;
+    }
+    out.extend_from_slice(&base[pos..end]);
+    out
 }
 
 fn diff_vec(org: &[String], gh: &[String], base: &[String]) -> FieldChange {
@@ -144,7 +462,28 @@ fn diff_vec(org: &[String], gh: &[String], base: &[String]) -> FieldChange {
     }
 }
 
-fn vec_eq(a: &[String], b: &[String]) -> bool {
+/// Labels as [`diff_vec`], with org labels unchanged since the last sync
+/// counting as the base value even when inherited tags add to them
+fn diff_labels(
+    org_labels: &[String],
+    gh_labels: &[String],
+    base: &SyncedItem,
+    config: &SyncConfig,
+) -> FieldChange {
+    let base_labels = without_status_labels(&base.labels, config);
+    let org_labels = if vec_eq(
+        org_labels,
+        &without_status_labels(base.org_labels(), config),
+    ) {
+        &base_labels
+    } else {
+        org_labels
+    };
+    diff_vec(org_labels, gh_labels, &base_labels)
+}
+
+/// Whether two lists hold the same values, ignoring order
+pub fn vec_eq(a: &[String], b: &[String]) -> bool {
     if a.len() != b.len() {
         return false;
     }
@@ -207,4 +546,78 @@ mod tests {
         let merged = merge_labels(&org, &gh);
         assert_eq!(merged, vec!["a", "b", "c"]);
     }
+
+    #[test]
+    fn test_merge_text_separate_edits() {
+        let base = "intro\n\nstep one\nstep two\n\noutro";
+        let org = "intro, edited in org\n\nstep one\nstep two\n\noutro";
+        let gh = "intro\n\nstep one\nstep two\n\noutro\nadded on GitHub";
+        assert_eq!(
+            merge_text(base, org, gh).as_deref(),
+            Some("intro, edited in org\n\nstep one\nstep two\n\noutro\nadded on GitHub")
+        );
+    }
+
+    #[test]
+    fn test_merge_text_same_edit_on_both_sides() {
+        let base = "a\nb\nc";
+        let edited = "a\nB\nc";
+        assert_eq!(merge_text(base, edited, edited).as_deref(), Some(edited));
+    }
+
+    #[test]
+    fn test_merge_text_deletion_and_insertion() {
+        let base = "a\nb\nc\nd\ne";
+        let org = "a\nc\nd\ne";
+        let gh = "a\nb\nc\nd\nd2\ne";
+        assert_eq!(merge_text(base, org, gh).as_deref(), Some("a\nc\nd\nd2\ne"));
+    }
+
+    #[test]
+    fn test_merge_text_overlapping_edits_conflict() {
+        let base = "a\nb\nc";
+        assert_eq!(merge_text(base, "a\norg\nc", "a\ngh\nc"), None);
+        // Edits to adjacent lines also conflict
+        assert_eq!(merge_text(base, "a\norg\nc", "a\nb\ngh"), None);
+        // So do different insertions at the same place
+        assert_eq!(merge_text(base, "a\nb\nc\norg", "a\nb\nc\ngh"), None);
+    }
+
+    #[test]
+    fn test_merge_text_empty_base() {
+        assert_eq!(merge_text("", "", "new").as_deref(), Some("new"));
+        assert_eq!(merge_text("", "org", "gh"), None);
+    }
+
+    #[test]
+    fn test_merge_body_checkboxes_per_item() {
+        let base = "Tasks:\n- [ ] one\n- [ ] two\n- [x] three";
+        let org = "Tasks:\n- [x] one\n- [ ] two\n- [x] three";
+        let gh = "Tasks:\n- [ ] one\n- [X] two\n- [ ] three";
+        assert_eq!(
+            merge_body(base, org, gh).as_deref(),
+            Some("Tasks:\n- [x] one\n- [x] two\n- [ ] three")
+        );
+        // Line merge alone sees adjacent edits
+        assert_eq!(merge_text(base, org, gh), None);
+    }
+
+    #[test]
+    fn test_merge_body_new_items_and_text_edits() {
+        let base = "- [ ] one\n- [ ] two";
+        let org = "- [ ] one\n- [x] two\n- [ ] added in org";
+        let gh = "- [x] one, reworded\n- [ ] two";
+        assert_eq!(
+            merge_body(base, org, gh).as_deref(),
+            Some("- [x] one, reworded\n- [x] two\n- [ ] added in org")
+        );
+    }
+
+    #[test]
+    fn test_task_mark() {
+        assert_eq!(task_mark("- [ ] item"), Some(3));
+        assert_eq!(task_mark("  12. [x] item"), Some(7));
+        assert_eq!(task_mark("- [link](url)"), None);
+        assert_eq!(task_mark("[ ] not a list"), None);
+    }
 }
@@ -1,144 +1,673 @@
-use crate::config::{Config, ConflictResolution};
+use crate::config::{Config, ConflictResolution, SyncConfig};
 use crate::error::{Error, Result};
-use crate::github::model::{CreateIssueRequest, GhIssue, GhIssueState, UpdateIssueRequest};
+use crate::github::model::{CreateIssueRequest, GhComment, GhIssue, UpdateIssueRequest};
+use crate::github::model::{GhPullRequest, ProjectBoard};
 use crate::github::GitHubClient;
-use crate::org::model::{OrgFile, OrgItem, TodoState};
-use crate::sync::diff::{merge_labels, three_way_diff, DiffResult, FieldChange};
-use crate::sync::state::SyncState;
+use crate::org::markdown::{
+    from_markdown, normalize_markdown, title_from_markdown, title_to_markdown, to_markdown,
+};
+use crate::org::model::{OrgFile, OrgItem, TodoKeywords};
+use crate::org::parser::split_list;
+use crate::org::write_file;
+use crate::org::writer::{conflict_entry, new_id};
+use crate::output::{Conflict, PullItem, PushItem};
+use crate::sync::dates::{
+    date_actions, fetch_board, new_item_dates, pulled_dates, push_date, DateChange,
+};
+use crate::sync::diff::{
+    merge_labels, org_has_changes, three_way_diff, two_way_diff, vec_eq, Field, FieldChange,
+};
+use crate::sync::import::{file_imports, import_actions};
+use crate::sync::labels::{issue_labels, item_labels, label_changes, org_labels, written_labels};
+use crate::sync::notes::{comment_entries, commented_issues, post_notes};
+use crate::sync::parents::{parent_action, parent_issue, set_parent};
+use crate::sync::pending::{apply_pending_updates, PendingUpdate};
+use crate::sync::relink::{link_action, link_update, relinked, relinked_file};
+use crate::sync::removed::{
+    append_archive, archive_closed_action, archive_headings, archived_action, close_archived,
+    deleted_action, locate, removed_items, untrack, Gone,
+};
+use crate::sync::state::{hash_body, PendingNote, SyncState, SyncedItem};
+use crate::sync::status::{gh_status, org_state_for_status, org_status, state_change};
+use chrono::{DateTime, Utc};
 use std::collections::HashMap;
+use std::path::PathBuf;
+
+/// Which way a field change flows
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum Direction {
+    /// Org value goes to GitHub
+    Push,
+    /// GitHub value comes into org
+    Pull,
+    /// Both sides get the union of the two values (labels)
+    Merge,
+    /// Both sides get this value: text (bodies as GitHub Markdown), a TODO
+    /// keyword, or a comma-separated list
+    Edit(String),
+}
 
 /// Actions to be executed during sync
+///
+/// `SyncEngine::plan` produces these for every command; push and pull keep
+/// only their half with [`SyncAction::only`].
 #[derive(Debug)]
 pub enum SyncAction {
-    /// Create a new GitHub issue
+    /// Create a GitHub issue (or link one with the same title)
     CreateIssue { org_item: OrgItem },
-    /// Update GitHub issue from org
-    UpdateGitHub {
+    /// Bring a linked item and its issue in line, field by field
+    Update {
         issue_number: u64,
-        request: UpdateIssueRequest,
+        org_item: OrgItem,
+        gh_issue: GhIssue,
+        fields: Vec<(Field, Direction)>,
     },
-    /// Update org item from GitHub
-    UpdateOrg {
+    /// Copy new GitHub comments into the LOGBOOK
+    PullComments {
         issue_number: u64,
-        gh_issue: GhIssue,
+        org_item: OrgItem,
+        entries: Vec<String>,
+        newest_comment: u64,
     },
-    /// Update both sides (merged)
-    UpdateBoth {
+    /// Post new org notes as GitHub comments, updating the comments of
+    /// edited ones
+    PostNotes {
         issue_number: u64,
-        gh_request: UpdateIssueRequest,
-        org_changes: OrgChanges,
+        notes: Vec<PendingNote>,
     },
-    /// Conflict requiring user resolution
+    /// Make the issue a sub-issue of `parent`, or of no issue, after its
+    /// heading moved in org
+    SetParent {
+        issue_number: u64,
+        title: String,
+        parent: Option<u64>,
+        previous: Option<u64>,
+    },
+    /// File an issue no heading links to under the import heading
+    ImportIssue { gh_issue: GhIssue },
+    /// Bring SCHEDULED/DEADLINE and the issue's Projects board dates in line
+    SetDates {
+        issue_number: u64,
+        org_item: OrgItem,
+        changes: Vec<DateChange>,
+    },
+    /// Both sides changed the same fields
+    ///
+    /// `resolved` holds the item's other field changes, applied once every
+    /// conflicting field is resolved with [`SyncAction::resolve`].
     Conflict {
         issue_number: u64,
-        fields: Vec<&'static str>,
+        fields: Vec<Field>,
+        resolved: Vec<(Field, Direction)>,
+        org_item: OrgItem,
+        gh_issue: GhIssue,
+    },
+    /// Stop tracking an issue whose heading left the file, closing it as
+    /// not planned when `close` is set
+    Untrack {
+        issue_number: u64,
+        title: String,
+        close: bool,
+        /// Org file the heading was moved to
+        moved_to: Option<PathBuf>,
+    },
+    /// Write identity properties to a linked heading: `:GH_ISSUE:` when it
+    /// was re-attached through its heading id, `:ID:` under `assign_ids`
+    Link {
+        issue_number: u64,
+        org_item: OrgItem,
+        relink: bool,
+        assign_id: bool,
+    },
+    /// Close the issue of an archived heading; archived headings are
+    /// otherwise left alone
+    CloseArchived {
+        issue_number: u64,
         org_item: OrgItem,
         gh_issue: GhIssue,
     },
+    /// Archive the heading of an issue closed on GitHub, once `confirmed`
+    /// (by `archive_closed = "always"` or the user)
+    ArchiveHeading {
+        issue_number: u64,
+        org_item: OrgItem,
+        confirmed: bool,
+    },
+    /// A linked heading was deleted and `deleted_heading = "prompt"` leaves
+    /// the choice to the user; unanswered, it stays a warning
+    DeletedHeading { issue_number: u64, title: String },
     /// No action needed
     NoOp { issue_number: u64 },
     /// Warning (e.g., issue removed from org)
     Warn { message: String },
 }
 
-/// Changes to apply to an org item
-#[derive(Debug, Clone)]
-pub struct OrgChanges {
-    pub state: Option<TodoState>,
-    pub assignees: Option<Vec<String>>,
-    pub labels: Option<Vec<String>>,
-    pub comments: Vec<String>,
+impl SyncAction {
+    /// Keep only the part of the action flowing in `direction`
+    ///
+    /// An update with nothing left becomes a `NoOp`; merged fields need both
+    /// sides and are only kept by a full sync. Conflicts and warnings always
+    /// stay.
+    pub fn only(self, direction: Direction) -> Option<SyncAction> {
+        match self {
+            SyncAction::Conflict {
+                issue_number,
+                fields,
+                resolved,
+                org_item,
+                gh_issue,
+            } => Some(SyncAction::Conflict {
+                issue_number,
+                fields,
+                resolved: resolved.into_iter().filter(|f| f.1 == direction).collect(),
+                org_item,
+                gh_issue,
+            }),
+            SyncAction::Update {
+                issue_number,
+                org_item,
+                gh_issue,
+                fields,
+            } => {
+                let fields: Vec<_> = fields.into_iter().filter(|f| f.1 == direction).collect();
+                Some(if fields.is_empty() {
+                    SyncAction::NoOp { issue_number }
+                } else {
+                    SyncAction::Update {
+                        issue_number,
+                        org_item,
+                        gh_issue,
+                        fields,
+                    }
+                })
+            }
+            SyncAction::SetDates {
+                issue_number,
+                org_item,
+                changes,
+            } => {
+                let changes: Vec<_> = changes
+                    .into_iter()
+                    .filter(|c| c.direction == direction)
+                    .collect();
+                (!changes.is_empty()).then_some(SyncAction::SetDates {
+                    issue_number,
+                    org_item,
+                    changes,
+                })
+            }
+            SyncAction::CreateIssue { .. }
+            | SyncAction::PostNotes { .. }
+            | SyncAction::SetParent { .. }
+            | SyncAction::CloseArchived { .. }
+            | SyncAction::Untrack { close: true, .. } => {
+                (direction == Direction::Push).then_some(self)
+            }
+            SyncAction::PullComments { .. }
+            | SyncAction::ImportIssue { .. }
+            | SyncAction::ArchiveHeading { .. } => (direction == Direction::Pull).then_some(self),
+            SyncAction::Untrack { .. }
+            | SyncAction::Link { .. }
+            | SyncAction::DeletedHeading { .. }
+            | SyncAction::NoOp { .. }
+            | SyncAction::Warn { .. } => Some(self),
+        }
+    }
+
+    /// Resolve some of a conflict's fields
+    ///
+    /// Once no conflicting field is left the conflict becomes an `Update`.
+    /// Other actions are returned unchanged.
+    pub fn resolve(self, choices: Vec<(Field, Direction)>) -> SyncAction {
+        let SyncAction::Conflict {
+            issue_number,
+            fields,
+            mut resolved,
+            org_item,
+            gh_issue,
+        } = self
+        else {
+            return self;
+        };
+
+        let fields: Vec<Field> = fields
+            .into_iter()
+            .filter(|field| !choices.iter().any(|c| c.0 == *field))
+            .collect();
+        resolved.extend(choices);
+
+        if fields.is_empty() {
+            SyncAction::Update {
+                issue_number,
+                org_item,
+                gh_issue,
+                fields: resolved,
+            }
+        } else {
+            SyncAction::Conflict {
+                issue_number,
+                fields,
+                resolved,
+                org_item,
+                gh_issue,
+            }
+        }
+    }
+
+    /// Human-readable description, one line per change
+    pub fn describe(&self) -> String {
+        match self {
+            SyncAction::CreateIssue { org_item } => format!("Create: {}", org_item.title),
+            SyncAction::Update {
+                issue_number,
+                org_item,
+                fields,
+                ..
+            } => {
+                let mut out = format!("Sync #{}: {}", issue_number, org_item.title);
+                if fields.is_empty() && org_item.has_conflict {
+                    out.push_str(" (removing conflict marker)");
+                } else if fields.is_empty() {
+                    out.push_str(" (initializing sync state)");
+                }
+                for (field, direction) in fields {
+                    let how = match direction {
+                        Direction::Push => "push to GitHub",
+                        Direction::Pull => "pull from GitHub",
+                        Direction::Merge => "merge both sides",
+                        Direction::Edit(_) => "set on both sides",
+                    };
+                    out.push_str(&format!("\n  - {}: {}", field.name(), how));
+                }
+                out
+            }
+            SyncAction::PullComments {
+                issue_number,
+                entries,
+                ..
+            } => format!(
+                "Comments #{}: {} new on GitHub",
+                issue_number,
+                entries.len()
+            ),
+            SyncAction::PostNotes {
+                issue_number,
+                notes,
+            } => format!(
+                "Notes #{}: {} to post as comments",
+                issue_number,
+                notes.len()
+            ),
+            SyncAction::SetParent {
+                issue_number,
+                title,
+                parent,
+                previous,
+            } => match (parent, previous) {
+                (Some(parent), _) => format!(
+                    "Parent #{}: {} (sub-issue of #{})",
+                    issue_number, title, parent
+                ),
+                (None, Some(previous)) => format!(
+                    "Parent #{}: {} (no longer a sub-issue of #{})",
+                    issue_number, title, previous
+                ),
+                (None, None) => format!("Parent #{}: {} (top level)", issue_number, title),
+            },
+            SyncAction::ImportIssue { gh_issue } => {
+                format!("Import #{}: {}", gh_issue.number, gh_issue.title)
+            }
+            SyncAction::SetDates {
+                issue_number,
+                org_item,
+                changes,
+            } => {
+                let mut out = format!("Dates #{}: {}", issue_number, org_item.title);
+                for change in changes {
+                    let value = change
+                        .value
+                        .map(|d| d.to_string())
+                        .unwrap_or_else(|| "none".to_string());
+                    let how = match change.direction {
+                        Direction::Push => "set on the project board",
+                        _ => "pull from the project board",
+                    };
+                    out.push_str(&format!(
+                        "\n  - {}: {} ({})",
+                        change.date.keyword(),
+                        how,
+                        value
+                    ));
+                }
+                out
+            }
+            SyncAction::Conflict {
+                issue_number,
+                fields,
+                org_item,
+                ..
+            } => {
+                let names: Vec<&str> = fields.iter().map(|f| f.name()).collect();
+                format!(
+                    "Conflict #{}: {} (fields: {})",
+                    issue_number,
+                    org_item.title,
+                    names.join(", ")
+                )
+            }
+            SyncAction::Link {
+                issue_number,
+                org_item,
+                relink: true,
+                ..
+            } => format!(
+                "Relink #{}: {} (GH_ISSUE restored from heading id {})",
+                issue_number, org_item.title, org_item.id
+            ),
+            SyncAction::Link {
+                issue_number,
+                org_item,
+                ..
+            } => format!("Assign ID to #{}: {}", issue_number, org_item.title),
+            SyncAction::CloseArchived {
+                issue_number,
+                org_item,
+                ..
+            } => format!("Close #{}: {} (archived)", issue_number, org_item.title),
+            SyncAction::ArchiveHeading {
+                issue_number,
+                org_item,
+                confirmed,
+            } => {
+                let how = if *confirmed {
+                    "closed on GitHub"
+                } else {
+                    "closed on GitHub; set archive_closed = \"always\" to archive it"
+                };
+                format!("Archive #{}: {} ({})", issue_number, org_item.title, how)
+            }
+            SyncAction::Untrack {
+                issue_number,
+                title,
+                close,
+                moved_to,
+            } => match (moved_to, close) {
+                (Some(path), _) => format!(
+                    "Untrack #{}: {} (moved to {})",
+                    issue_number,
+                    title,
+                    path.display()
+                ),
+                (None, true) => format!(
+                    "Close #{}: {} (heading deleted, closing as not planned)",
+                    issue_number, title
+                ),
+                (None, false) => format!("Unlink #{}: {} (heading deleted)", issue_number, title),
+            },
+            SyncAction::DeletedHeading {
+                issue_number,
+                title,
+            } => format!(
+                "Warning: Issue #{} ({}) was in org but heading removed",
+                issue_number, title
+            ),
+            SyncAction::NoOp { issue_number } => format!("Skip #{}: no changes", issue_number),
+            SyncAction::Warn { message } => format!("Warning: {}", message),
+        }
+    }
+}
+
+/// What executing a plan did
+#[derive(Debug, Default)]
+pub struct SyncReport {
+    pub pushed: Vec<PushItem>,
+    pub pulled: Vec<PullItem>,
+    pub conflicts: Vec<Conflict>,
+    pub skipped: usize,
+    pub comments_added: usize,
+    pub notes_posted: usize,
+    /// Issues no longer tracked because their heading left the file
+    pub untracked: usize,
+    /// Of those, issues closed as not planned
+    pub closed: usize,
+    /// Headings archived because their issue was closed
+    pub archived: usize,
+    /// Headings whose lost `:GH_ISSUE:` was restored
+    pub relinked: usize,
+}
+
+/// Result of looking up the GitHub issue linked to an org item
+enum LinkedIssue {
+    Found(GhIssue),
+    /// Not in an incremental listing and nothing changed locally either
+    Unchanged,
+    NotFound,
 }
 
 pub struct SyncEngine {
     config: Config,
     client: GitHubClient,
     state: SyncState,
-    dry_run: bool,
-    force: bool,
+    keywords: TodoKeywords,
+    /// Plan and report, but change nothing on either side
+    pub dry_run: bool,
+    /// Resolve conflicts in this direction instead of reporting them
+    pub force: Option<Direction>,
+    /// Plan only for a push or a pull, skipping the fetches (comments,
+    /// import search, archived issues) only the other direction uses
+    pub direction: Option<Direction>,
+    since: Option<DateTime<Utc>>,
+    fetched_at: Option<DateTime<Utc>>,
+    /// Projects board holding the date fields, fetched by `plan`
+    board: Option<ProjectBoard>,
+    /// Every issue, for matching new headings by title: the listing of a
+    /// full `plan`, or fetched once by the first create
+    all_issues: Option<Vec<GhIssue>>,
+    /// Pull requests from the listing fetched by `plan`
+    pull_requests: Vec<GhPullRequest>,
+    /// Subtrees archived by `execute`, by archive file, for `save` to write
+    archived: Vec<(PathBuf, String)>,
 }
 
 impl SyncEngine {
-    pub async fn new(
-        config: Config,
-        org_file: &OrgFile,
-        dry_run: bool,
-        force: bool,
-    ) -> Result<Self> {
+    pub async fn new(config: Config, org_file: &OrgFile) -> Result<Self> {
         let repo = org_file.repo.as_ref().ok_or(Error::NoRepo)?;
 
         let token = config.github_token()?;
         let client = GitHubClient::new(&token, repo).await?;
         let state = SyncState::load(&org_file.path)?;
 
-        Ok(Self {
+        Ok(Self::with_client(config, client, state, org_file))
+    }
+
+    /// Build an engine around an existing client and sync state
+    pub fn with_client(
+        mut config: Config,
+        client: GitHubClient,
+        mut state: SyncState,
+        org_file: &OrgFile,
+    ) -> Self {
+        if state.repo.is_empty() {
+            state.repo = org_file.repo.clone().unwrap_or_default();
+        }
+
+        // File keywords refine the configured label handling and import filter
+        if org_file.label_prefix.is_some() {
+            config.sync.label_prefix = org_file.label_prefix.clone();
+        }
+        for label in &org_file.default_labels {
+            if !config.sync.default_labels.contains(label) {
+                config.sync.default_labels.push(label.clone());
+            }
+        }
+        if org_file.import.is_some() {
+            config.sync.import = org_file.import.clone();
+        }
+
+        Self {
             config,
             client,
             state,
-            dry_run,
-            force,
-        })
+            keywords: org_file.todo_keywords.clone(),
+            dry_run: false,
+            force: None,
+            direction: None,
+            since: None,
+            fetched_at: None,
+            board: None,
+            all_issues: None,
+            pull_requests: Vec::new(),
+            archived: Vec::new(),
+        }
+    }
+
+    pub fn config(&self) -> &Config {
+        &self.config
+    }
+
+    pub fn state(&self) -> &SyncState {
+        &self.state
     }
 
     /// Plan sync actions by comparing org, GitHub, and base state
-    pub async fn plan(&self, org_file: &OrgFile) -> Result<Vec<SyncAction>> {
-        let gh_issues = self.client.fetch_issues().await?;
-        let gh_map: HashMap<u64, &GhIssue> = gh_issues.iter().map(|i| (i.number, i)).collect();
+    ///
+    /// Only issues updated since the last sync are listed unless `full` is
+    /// set; linked issues missing from that listing are looked up on demand.
+    pub async fn plan(&mut self, org_file: &OrgFile, full: bool) -> Result<Vec<SyncAction>> {
+        self.fetched_at = Some(Utc::now());
+        self.since = if full { None } else { self.state.last_sync };
+        let listing = self.client.fetch_listing(self.since).await?;
+        let gh_issues = listing.issues;
+        self.pull_requests = listing.pull_requests;
+        self.all_issues = self.since.is_none().then(|| gh_issues.clone());
+        self.board = fetch_board(&self.client, self.config.sync.project.as_ref()).await?;
+
+        // Headings that lost their GH_ISSUE count as linked from here on
+        let relinked = relinked(org_file, &self.state);
+        let patched = (!relinked.is_empty()).then(|| relinked_file(org_file, &relinked));
+        let org_file = patched.as_ref().unwrap_or(org_file);
+        let mut comments = self.listed_comments(org_file, &gh_issues).await?;
 
         let mut actions = Vec::new();
+        for (index, item) in org_file.items.iter().enumerate() {
+            let relink = relinked.contains_key(&index);
+            let item_actions = self
+                .item_actions(org_file, item, relink, &gh_issues, &mut comments)
+                .await?;
+            actions.extend(item_actions);
+        }
 
-        // Process each org item
-        for item in &org_file.items {
-            let action = if let Some(issue_num) = item.gh_issue {
-                // Linked item - reconcile
-                if let Some(gh) = gh_map.get(&issue_num) {
-                    if let Some(base) = self.state.items.get(&issue_num) {
-                        self.reconcile(item, gh, base)
-                    } else {
-                        // No base state - treat as GitHub being source of truth
-                        SyncAction::UpdateOrg {
-                            issue_number: issue_num,
-                            gh_issue: (*gh).clone(),
-                        }
-                    }
-                } else {
-                    SyncAction::Warn {
-                        message: format!(
-                            "Issue #{} linked in org but not found in GitHub",
-                            issue_num
-                        ),
-                    }
-                }
-            } else {
-                // Unlinked item - try to match or create
-                if let Some(matched) = self.find_matching_issue(item, &gh_issues).await {
-                    // Found matching issue - this is initial link
-                    SyncAction::UpdateOrg {
-                        issue_number: matched.number,
-                        gh_issue: matched,
-                    }
-                } else {
-                    // No match - create new issue
-                    SyncAction::CreateIssue {
-                        org_item: item.clone(),
-                    }
-                }
-            };
-            actions.push(action);
+        // Issues matching the import filter that nothing links to yet
+        let import = self
+            .config
+            .sync
+            .import
+            .as_ref()
+            .filter(|_| self.plans(Direction::Pull));
+        if let Some(filter) = import {
+            actions.extend(import_actions(&self.client, filter, &self.state, org_file).await?);
         }
 
-        // Check for issues in state that are no longer in org
-        for (issue_num, synced) in &self.state.items {
-            let in_org = org_file
-                .items
-                .iter()
-                .any(|i| i.gh_issue == Some(*issue_num));
-            if !in_org {
+        actions.extend(self.removed_actions(org_file, &gh_issues).await?);
+
+        Ok(actions)
+    }
+
+    /// Actions for one org item: create its issue, or bring the heading and
+    /// the issue it links in line
+    ///
+    /// `relink` marks a heading whose lost `:GH_ISSUE:` is restored.
+    async fn item_actions(
+        &self,
+        org_file: &OrgFile,
+        item: &OrgItem,
+        relink: bool,
+        gh_issues: &[GhIssue],
+        comments: &mut HashMap<u64, Vec<GhComment>>,
+    ) -> Result<Vec<SyncAction>> {
+        let mut actions = Vec::new();
+        if item.archived {
+            if let Some(issue_num) = item.gh_issue {
+                actions.push(self.close_action(gh_issues, item, issue_num).await?);
+            }
+            return Ok(actions);
+        }
+
+        let Some(issue_num) = item.gh_issue else {
+            actions.push(SyncAction::CreateIssue {
+                org_item: item.clone(),
+            });
+            return Ok(actions);
+        };
+
+        actions.extend(link_action(
+            item,
+            issue_num,
+            relink,
+            self.config.org.assign_ids,
+        ));
+
+        let base = self.state.items.get(&issue_num);
+
+        let set_parent = parent_action(org_file, item, issue_num, base);
+
+        // Board dates change without touching the issue, so always compare them
+        let set_dates = date_actions(
+            item,
+            issue_num,
+            base,
+            self.board.as_ref(),
+            self.config.sync.project.as_ref(),
+            self.force.as_ref(),
+        );
+
+        let gh_issue = match self.linked_issue(gh_issues, item, issue_num, base).await? {
+            LinkedIssue::Found(issue) => issue,
+            LinkedIssue::Unchanged => {
+                actions.push(SyncAction::NoOp {
+                    issue_number: issue_num,
+                });
+                actions.extend(set_parent);
+                actions.extend(set_dates);
+                return Ok(actions);
+            }
+            LinkedIssue::NotFound => {
                 actions.push(SyncAction::Warn {
-                    message: format!(
-                        "Issue #{} ({}) was in org but heading removed",
-                        issue_num, synced.title
-                    ),
+                    message: format!("Issue #{} linked in org but not found in GitHub", issue_num),
+                });
+                return Ok(actions);
+            }
+        };
+
+        // New GitHub comments go to the LOGBOOK whatever else changed
+        let (entries, newest) = if self.plans(Direction::Pull) {
+            self.new_comments(&gh_issue, base, comments).await?
+        } else {
+            (Vec::new(), None)
+        };
+        if let Some(newest_comment) = newest {
+            actions.push(SyncAction::PullComments {
+                issue_number: issue_num,
+                org_item: item.clone(),
+                entries,
+                newest_comment,
+            });
+        }
+
+        let action = self.reconcile(item, gh_issue, base);
+        let archive = archive_closed_action(&action, self.config.sync.archive_closed);
+        actions.push(action);
+        actions.extend(archive);
+        actions.extend(set_parent);
+        actions.extend(set_dates);
+
+        // New org notes go to GitHub once the item has a base state
+        if let Some(base) = base {
+            let notes = base.unposted_notes(&item.notes);
+            if !notes.is_empty() {
+                actions.push(SyncAction::PostNotes {
+                    issue_number: issue_num,
+                    notes,
                 });
             }
         }
@@ -146,275 +675,980 @@ impl SyncEngine {
         Ok(actions)
     }
 
-    /// Reconcile an org item with a GitHub issue using three-way diff
-    fn reconcile(
+    /// Actions for issues in state whose heading is no longer in org:
+    /// archived and moved headings are looked after where they went,
+    /// deleted ones get the configured policy
+    async fn removed_actions(
         &self,
-        org: &OrgItem,
-        gh: &GhIssue,
-        base: &crate::sync::state::SyncedItem,
-    ) -> SyncAction {
-        let diff = three_way_diff(org, gh, base);
+        org_file: &OrgFile,
+        gh_issues: &[GhIssue],
+    ) -> Result<Vec<SyncAction>> {
+        let removed = removed_items(org_file, &self.state);
+        let numbers: Vec<u64> = removed.iter().map(|(num, _)| *num).collect();
+        let mut gone = if numbers.is_empty() {
+            HashMap::new()
+        } else {
+            locate(org_file, &self.state.repo, &numbers, &self.config.org)
+        };
 
-        if !diff.has_changes() {
-            return SyncAction::NoOp {
-                issue_number: gh.number,
-            };
+        let mut actions = Vec::new();
+        for (issue_number, title) in removed {
+            actions.push(match gone.remove(&issue_number) {
+                Some(Gone::Archived { item, .. }) => {
+                    self.close_action(gh_issues, &item, issue_number).await?
+                }
+                Some(Gone::Moved(path)) => SyncAction::Untrack {
+                    issue_number,
+                    title,
+                    close: false,
+                    moved_to: Some(path),
+                },
+                _ => deleted_action(issue_number, title, self.config.sync.deleted_heading),
+            });
         }
+        Ok(actions)
+    }
 
-        if diff.has_conflicts() && !self.force {
-            return SyncAction::Conflict {
-                issue_number: gh.number,
-                fields: diff.conflict_fields(),
-                org_item: org.clone(),
-                gh_issue: gh.clone(),
-            };
+    /// Close the issue of an archived heading, when pushing
+    async fn close_action(
+        &self,
+        gh_issues: &[GhIssue],
+        item: &OrgItem,
+        issue_num: u64,
+    ) -> Result<SyncAction> {
+        if !self.plans(Direction::Push) {
+            return Ok(SyncAction::NoOp {
+                issue_number: issue_num,
+            });
         }
+        let incremental = self.since.is_some();
+        archived_action(
+            &self.client,
+            &self.state,
+            gh_issues,
+            item,
+            issue_num,
+            incremental,
+        )
+        .await
+    }
 
-        // Build update requests based on diff and resolution strategy
-        let (gh_request, org_changes) = self.resolve_diff(org, gh, &diff);
+    /// Pull requests from the last `plan` listing that close an issue
+    /// linked in `org_file`, by issue number
+    ///
+    /// An incremental plan only lists pull requests updated since the last
+    /// sync.
+    pub fn linked_pull_requests(&self, org_file: &OrgFile) -> Vec<(u64, &GhPullRequest)> {
+        let mut linked: Vec<(u64, &GhPullRequest)> = org_file
+            .items
+            .iter()
+            .filter_map(|item| item.gh_issue)
+            .flat_map(|issue| {
+                self.pull_requests
+                    .iter()
+                    .filter(move |pr| pr.links_issue(issue))
+                    .map(move |pr| (issue, pr))
+            })
+            .collect();
+        linked.sort_by_key(|(issue, pr)| (*issue, pr.number));
+        linked.dedup_by_key(|(issue, pr)| (*issue, pr.number));
+        linked
+    }
 
-        match (gh_request, org_changes) {
-            (Some(gh_req), Some(org_ch)) => SyncAction::UpdateBoth {
-                issue_number: gh.number,
-                gh_request: gh_req,
-                org_changes: org_ch,
-            },
-            (Some(req), None) => SyncAction::UpdateGitHub {
-                issue_number: gh.number,
-                request: req,
-            },
-            (None, Some(_)) => SyncAction::UpdateOrg {
-                issue_number: gh.number,
-                gh_issue: gh.clone(),
-            },
-            (None, None) => SyncAction::NoOp {
-                issue_number: gh.number,
-            },
-        }
+    /// Whether the plan includes actions going `direction`
+    fn plans(&self, direction: Direction) -> bool {
+        self.direction.as_ref().is_none_or(|d| *d == direction)
     }
 
-    /// Resolve diff into concrete update requests
-    fn resolve_diff(
+    /// Look up the GitHub issue linked to an org item
+    ///
+    /// With an incremental listing, an issue missing from `gh_issues` has not
+    /// changed on GitHub since the last sync. It is only fetched individually
+    /// when the org side changed too, or when there is no base state yet.
+    async fn linked_issue(
         &self,
-        org: &OrgItem,
-        gh: &GhIssue,
-        diff: &DiffResult,
-    ) -> (Option<UpdateIssueRequest>, Option<OrgChanges>) {
-        let mut gh_req = UpdateIssueRequest::default();
-        let mut org_changes = OrgChanges {
-            state: None,
-            assignees: None,
-            labels: None,
-            comments: vec![],
-        };
-        let mut has_gh_changes = false;
-        let mut has_org_changes = false;
-
-        // Title: org wins (or force)
-        match diff.title {
-            FieldChange::OrgChanged | FieldChange::Conflict => {
-                gh_req.title = Some(org.title.clone());
-                has_gh_changes = true;
-            }
-            FieldChange::GitHubChanged => {
-                // Title from GH - but we don't update org titles from GH
-                // (org is authoring surface)
-            }
-            FieldChange::None => {}
+        gh_issues: &[GhIssue],
+        item: &OrgItem,
+        issue_num: u64,
+        base: Option<&SyncedItem>,
+    ) -> Result<LinkedIssue> {
+        if let Some(issue) = gh_issues.iter().find(|i| i.number == issue_num) {
+            return Ok(LinkedIssue::Found(issue.clone()));
         }
 
-        // Body: org wins
-        match diff.body {
-            FieldChange::OrgChanged | FieldChange::Conflict => {
-                gh_req.body = Some(org.body.clone());
-                has_gh_changes = true;
-            }
-            FieldChange::GitHubChanged => {
-                // Don't update org body from GH
-            }
-            FieldChange::None => {}
+        if self.since.is_none() {
+            return Ok(LinkedIssue::NotFound);
         }
 
-        // State: configurable
-        match diff.state {
-            FieldChange::OrgChanged => {
-                gh_req.state = Some(if org.state.is_open() {
-                    GhIssueState::Open
-                } else {
-                    GhIssueState::Closed
-                });
-                has_gh_changes = true;
-            }
-            FieldChange::GitHubChanged => {
-                org_changes.state = Some(if gh.state.is_open() {
-                    TodoState::Todo
-                } else {
-                    TodoState::Done
-                });
-                has_org_changes = true;
+        if base.is_some_and(|base| !org_has_changes(item, base, &self.config.sync)) {
+            return Ok(LinkedIssue::Unchanged);
+        }
+
+        // A number that belongs to a pull request is no issue to sync
+        let issue = self
+            .client
+            .find_issue(issue_num)
+            .await?
+            .filter(|issue| !issue.is_pull_request);
+        Ok(match issue {
+            Some(issue) => LinkedIssue::Found(issue),
+            None => LinkedIssue::NotFound,
+        })
+    }
+
+    /// Comments on the listed issues that `org_file` links, by issue number
+    ///
+    /// Issues without comments are skipped and the rest are fetched a few
+    /// at a time; nothing is fetched when the plan does not pull.
+    async fn listed_comments(
+        &self,
+        org_file: &OrgFile,
+        gh_issues: &[GhIssue],
+    ) -> Result<HashMap<u64, Vec<GhComment>>> {
+        if !self.plans(Direction::Pull) {
+            return Ok(HashMap::new());
+        }
+        let numbers = commented_issues(org_file, gh_issues);
+        self.client.fetch_comments_for(&numbers, self.since).await
+    }
+
+    /// GitHub comments not yet copied into the item's LOGBOOK, as entries
+    /// with the newest comment id seen (see [`comment_entries`])
+    ///
+    /// Comments come from `listed` (see [`Self::listed_comments`]) when the
+    /// issue was listed, and are fetched here when it was looked up on
+    /// demand.
+    async fn new_comments(
+        &self,
+        gh: &GhIssue,
+        base: Option<&SyncedItem>,
+        listed: &mut HashMap<u64, Vec<GhComment>>,
+    ) -> Result<(Vec<String>, Option<u64>)> {
+        let comments = match listed.remove(&gh.number) {
+            Some(comments) => comments,
+            None if gh.comments == 0 => Vec::new(),
+            None => {
+                self.client
+                    .fetch_comments_since(gh.number, self.since)
+                    .await?
             }
-            FieldChange::Conflict => {
-                // Force mode: org wins
-                if self.force || self.config.sync.state_conflict == ConflictResolution::OrgWins {
-                    gh_req.state = Some(if org.state.is_open() {
-                        GhIssueState::Open
-                    } else {
-                        GhIssueState::Closed
-                    });
-                    has_gh_changes = true;
+        };
+        Ok(comment_entries(comments, base))
+    }
+
+    /// Reconcile an org item with a GitHub issue using three-way diff
+    ///
+    /// Without a base state, fields that differ are resolved as conflicts.
+    fn reconcile(&self, org: &OrgItem, gh: GhIssue, base: Option<&SyncedItem>) -> SyncAction {
+        let config = &self.config.sync;
+        let mut fields = Vec::new();
+        let mut conflicts = Vec::new();
+
+        let diff = match base {
+            Some(base) => three_way_diff(org, &gh, base, config),
+            // Without a base state, fields that differ are settled as conflicts
+            None => two_way_diff(org, &gh, config),
+        };
+        for (field, change) in diff.changes() {
+            // Deleting the conflict drawer settles its fields with the org values
+            let settled = !org.has_conflict && base.is_some_and(|b| b.conflicts.contains(&field));
+            match change {
+                FieldChange::None => {}
+                _ if settled => fields.push((field, Direction::Push)),
+                FieldChange::OrgChanged => fields.push((field, Direction::Push)),
+                FieldChange::GitHubChanged => fields.push((field, Direction::Pull)),
+                FieldChange::Merged(merged) => {
+                    fields.push((field, Direction::Edit(merged.clone())))
+                }
+                FieldChange::Conflict if field == Field::Labels => {
+                    fields.push((field, Direction::Merge))
                 }
+                FieldChange::Conflict => match self.conflict_direction(field) {
+                    Some(direction) => fields.push((field, direction)),
+                    None => conflicts.push(field),
+                },
             }
-            FieldChange::None => {}
         }
 
-        // Assignees: GitHub wins
-        match diff.assignees {
-            FieldChange::OrgChanged => {
-                gh_req.assignees = Some(org.assignees.clone());
-                has_gh_changes = true;
-            }
-            FieldChange::GitHubChanged | FieldChange::Conflict => {
-                org_changes.assignees = Some(gh.assignees.clone());
-                has_org_changes = true;
-            }
-            FieldChange::None => {}
+        if !conflicts.is_empty() {
+            return SyncAction::Conflict {
+                issue_number: gh.number,
+                fields: conflicts,
+                resolved: fields,
+                org_item: org.clone(),
+                gh_issue: gh,
+            };
         }
 
-        // Labels: union merge
-        match diff.labels {
-            FieldChange::OrgChanged => {
-                gh_req.labels = Some(org.labels.clone());
-                has_gh_changes = true;
-            }
-            FieldChange::GitHubChanged => {
-                org_changes.labels = Some(gh.labels.clone());
-                has_org_changes = true;
-            }
-            FieldChange::Conflict => {
-                // Union merge
-                let merged = merge_labels(&org.labels, &gh.labels);
-                gh_req.labels = Some(merged.clone());
-                org_changes.labels = Some(merged);
-                has_gh_changes = true;
-                has_org_changes = true;
-            }
-            FieldChange::None => {}
+        // Without a base state there is nothing to record if both sides
+        // agree, unless a conflict marker is left to clear
+        let marked = org.has_conflict || base.is_some_and(|b| !b.conflicts.is_empty());
+        if fields.is_empty() && base.is_some() && !marked {
+            return SyncAction::NoOp {
+                issue_number: gh.number,
+            };
         }
 
-        (
-            if has_gh_changes { Some(gh_req) } else { None },
-            if has_org_changes {
-                Some(org_changes)
-            } else {
-                None
-            },
-        )
+        SyncAction::Update {
+            issue_number: gh.number,
+            org_item: org.clone(),
+            gh_issue: gh,
+            fields,
+        }
     }
 
-    /// Try to find an existing GitHub issue matching an org item by title
-    async fn find_matching_issue(&self, item: &OrgItem, issues: &[GhIssue]) -> Option<GhIssue> {
-        issues.iter().find(|i| i.title == item.title).cloned()
+    /// How a conflicting field resolves without asking: `--force` first,
+    /// then the field's configured strategy (`None` means prompt)
+    fn conflict_direction(&self, field: Field) -> Option<Direction> {
+        if let Some(direction) = &self.force {
+            return Some(direction.clone());
+        }
+
+        let sync = &self.config.sync;
+        let resolution = match field {
+            Field::Title => sync.title_conflict,
+            Field::Body => sync.body_conflict,
+            Field::State => sync.state_conflict,
+            Field::Assignees => sync.assignee_conflict,
+            Field::Labels => ConflictResolution::Prompt,
+        };
+        match resolution {
+            ConflictResolution::OrgWins => Some(Direction::Push),
+            ConflictResolution::GitHubWins => Some(Direction::Pull),
+            ConflictResolution::Prompt => None,
+        }
     }
 
     /// Execute planned actions
+    ///
+    /// GitHub is updated as actions run; org edits are applied to
+    /// `org_file.content` at the end, last heading first. Nothing is saved
+    /// until [`SyncEngine::save`].
     pub async fn execute(
         &mut self,
         actions: Vec<SyncAction>,
         org_file: &mut OrgFile,
-    ) -> Result<()> {
-        if self.dry_run {
-            for action in &actions {
-                println!("{:?}", action);
-            }
-            return Ok(());
-        }
+    ) -> Result<SyncReport> {
+        let mut report = SyncReport::default();
+        let mut pending_updates = Vec::new();
+        let mut comment_marks = Vec::new();
+        // Issues created for headings in this run, by heading span
+        let mut created: Vec<(std::ops::Range<usize>, u64)> = Vec::new();
+        let mut imports = Vec::new();
+        let mut to_archive = Vec::new();
+        // Heading ids that replace the recorded ones, by issue
+        let mut new_ids = Vec::new();
 
         for action in actions {
             match action {
                 SyncAction::CreateIssue { org_item } => {
-                    let issue = self
-                        .client
-                        .create_issue(CreateIssueRequest {
-                            title: org_item.title.clone(),
-                            body: Some(org_item.body.clone()),
-                            assignees: org_item.assignees.clone(),
-                            labels: org_item.labels.clone(),
-                        })
+                    let Some(number) = self
+                        .create_issue(&org_item, &mut pending_updates, &mut report)
+                        .await?
+                    else {
+                        continue;
+                    };
+
+                    // A child heading becomes a sub-issue right away
+                    let parent = parent_issue(org_file, &org_item).or_else(|| {
+                        let parent = org_file.items.get(org_item.parent?)?;
+                        created
+                            .iter()
+                            .find(|(span, _)| *span == parent.span)
+                            .map(|(_, n)| *n)
+                    });
+                    if parent.is_some() {
+                        set_parent(&self.client, &mut self.state, number, parent, None).await?;
+                    }
+                    created.push((org_item.span.clone(), number));
+
+                    // Dates go on the board right away too
+                    let project = self
+                        .config
+                        .sync
+                        .project
+                        .as_ref()
+                        .filter(|_| self.board.is_some());
+                    let dates = new_item_dates(&org_item, project);
+                    if !dates.is_empty() {
+                        self.set_dates(
+                            number,
+                            &org_item,
+                            &dates,
+                            &mut pending_updates,
+                            &mut report,
+                        )
                         .await?;
+                    }
+                }
 
-                    // Update org file with new issue number
-                    // TODO: Apply property updates to org_file.content
-
-                    // Record in state
-                    self.state.record_sync(
-                        issue.number,
-                        &org_item.id,
-                        &issue.title,
-                        issue.body.as_deref().unwrap_or(""),
-                        if issue.state.is_open() {
-                            "open"
-                        } else {
-                            "closed"
-                        },
-                        &issue.assignees,
-                        &issue.labels,
-                        issue.updated_at,
-                    );
-
-                    println!("Created issue #{}: {}", issue.number, issue.title);
+                SyncAction::SetParent {
+                    issue_number,
+                    parent,
+                    previous,
+                    ..
+                } => {
+                    if !self.dry_run {
+                        set_parent(
+                            &self.client,
+                            &mut self.state,
+                            issue_number,
+                            parent,
+                            previous,
+                        )
+                        .await?;
+                    }
+                }
+
+                SyncAction::ImportIssue { gh_issue } => {
+                    report.pulled.push(PullItem {
+                        issue_number: gh_issue.number,
+                        title: gh_issue.title.clone(),
+                        changes: vec!["imported".to_string()],
+                    });
+                    imports.push(gh_issue);
                 }
 
-                SyncAction::UpdateGitHub {
+                SyncAction::SetDates {
                     issue_number,
-                    request,
+                    org_item,
+                    changes,
                 } => {
-                    let _issue = self.client.update_issue(issue_number, request).await?;
-                    println!("Updated GitHub issue #{}", issue_number);
+                    if !self.dry_run {
+                        self.set_dates(
+                            issue_number,
+                            &org_item,
+                            &changes,
+                            &mut pending_updates,
+                            &mut report,
+                        )
+                        .await?;
+                    }
                 }
 
-                SyncAction::UpdateOrg {
+                SyncAction::Update {
                     issue_number,
-                    gh_issue: _,
+                    org_item,
+                    gh_issue,
+                    fields,
                 } => {
-                    // TODO: Apply changes to org_file.content
-                    println!("Updated org from GitHub issue #{}", issue_number);
+                    self.update(
+                        issue_number,
+                        &org_item,
+                        &gh_issue,
+                        &fields,
+                        &mut pending_updates,
+                        &mut report,
+                    )
+                    .await?;
                 }
 
-                SyncAction::UpdateBoth {
+                SyncAction::PullComments {
                     issue_number,
-                    gh_request,
-                    org_changes: _,
+                    org_item,
+                    entries,
+                    newest_comment,
                 } => {
-                    self.client.update_issue(issue_number, gh_request).await?;
-                    // TODO: Apply org_changes to org_file.content
-                    println!("Updated both sides for issue #{}", issue_number);
+                    report.comments_added += entries.len();
+                    if !entries.is_empty() {
+                        pending_updates.push(PendingUpdate {
+                            item: org_item,
+                            properties: Vec::new(),
+                            logbook: entries,
+                        });
+                    }
+                    comment_marks.push((issue_number, newest_comment));
+                }
+
+                SyncAction::PostNotes {
+                    issue_number,
+                    notes,
+                } => {
+                    if !self.dry_run {
+                        post_notes(&self.client, &mut self.state, issue_number, &notes).await?;
+                    }
+                    report.notes_posted += notes.len();
                 }
 
                 SyncAction::Conflict {
                     issue_number,
                     fields,
+                    org_item,
+                    gh_issue,
                     ..
                 } => {
-                    eprintln!(
-                        "Conflict on issue #{}: {} (use --force to override)",
-                        issue_number,
-                        fields.join(", ")
-                    );
+                    if self.config.sync.conflict_markers && !self.dry_run {
+                        if let Some(update) =
+                            self.mark_conflict(issue_number, &fields, &org_item, &gh_issue)
+                        {
+                            pending_updates.push(update);
+                        }
+                    }
+                    for field in fields {
+                        let (local, remote) = self.conflict_values(field, &org_item, &gh_issue);
+                        report.conflicts.push(Conflict {
+                            issue_number,
+                            field: field.name().to_string(),
+                            local,
+                            remote,
+                        });
+                    }
                 }
 
-                SyncAction::NoOp { issue_number: _ } => {
-                    // Nothing to do
+                SyncAction::Untrack {
+                    issue_number,
+                    close,
+                    ..
+                } => {
+                    if !self.dry_run {
+                        untrack(&self.client, &mut self.state, issue_number, close).await?;
+                    }
+                    report.untracked += 1;
+                    if close {
+                        report.closed += 1;
+                    }
+                }
+
+                SyncAction::Link {
+                    issue_number,
+                    org_item,
+                    relink,
+                    assign_id,
+                } => {
+                    if relink {
+                        report.relinked += 1;
+                    }
+                    let (update, id) = link_update(org_item, issue_number, relink, assign_id);
+                    new_ids.extend(id.map(|id| (issue_number, id)));
+                    pending_updates.push(update);
+                }
+
+                SyncAction::CloseArchived {
+                    issue_number,
+                    org_item,
+                    ..
+                } => {
+                    if !self.dry_run {
+                        let issue = close_archived(
+                            &self.client,
+                            &mut self.state,
+                            &self.config.sync,
+                            issue_number,
+                            &org_item,
+                        )
+                        .await?;
+                        report.pushed.push(PushItem {
+                            title: issue.title,
+                            issue_number,
+                            url: issue.html_url,
+                            action: "updated".to_string(),
+                        });
+                    }
+                }
+
+                SyncAction::ArchiveHeading {
+                    issue_number,
+                    confirmed,
+                    ..
+                } => {
+                    if confirmed {
+                        report.archived += 1;
+                        to_archive.push(issue_number);
+                    }
+                }
+
+                SyncAction::NoOp { .. } => {
+                    report.skipped += 1;
+                }
+
+                SyncAction::Warn { .. } | SyncAction::DeletedHeading { .. } => {
+                    // Shown when the plan is printed
+                }
+            }
+        }
+
+        if !self.dry_run {
+            org_file.content = apply_pending_updates(&org_file.content, pending_updates);
+            if !imports.is_empty() {
+                org_file.content = file_imports(
+                    &self.client,
+                    &mut self.state,
+                    &org_file.content,
+                    &imports,
+                    &self.config,
+                    &self.keywords,
+                )
+                .await?;
+            }
+            for (issue_num, comment_id) in comment_marks {
+                self.state.record_comments(issue_num, comment_id);
+            }
+            for (issue_num, id) in new_ids {
+                if let Some(synced) = self.state.items.get_mut(&issue_num) {
+                    synced.org_heading_id = id;
                 }
+            }
+            if !to_archive.is_empty() {
+                if let Some(archived) = archive_headings(org_file, &to_archive, &self.config.org)? {
+                    self.archived.push(archived);
+                }
+            }
+        }
+
+        Ok(report)
+    }
+
+    /// Write the org file and sync state
+    ///
+    /// `mark_synced` moves the incremental fetch cutoff to the time of the
+    /// plan; only commands that pulled every GitHub change should set it.
+    pub fn save(&mut self, org_file: &OrgFile, mark_synced: bool) -> Result<()> {
+        if self.dry_run {
+            return Ok(());
+        }
+
+        // Archived subtrees are safe in the archive before they leave the file
+        for (path, subtrees) in self.archived.drain(..) {
+            append_archive(&path, &org_file.path, &subtrees)?;
+        }
+        write_file(org_file)?;
+        if mark_synced {
+            if let Some(fetched_at) = self.fetched_at {
+                self.state.mark_synced(fetched_at);
+            }
+        }
+        self.state.save(&org_file.path)
+    }
 
-                SyncAction::Warn { message } => {
-                    eprintln!("Warning: {}", message);
+    /// Create an issue for an unlinked heading, or link one with the same title
+    ///
+    /// Returns the issue number, or `None` in a dry run.
+    async fn create_issue(
+        &mut self,
+        item: &OrgItem,
+        pending_updates: &mut Vec<PendingUpdate>,
+        report: &mut SyncReport,
+    ) -> Result<Option<u64>> {
+        if self.dry_run {
+            return Ok(None);
+        }
+        // Try to find existing issue by title first
+        let title = title_to_markdown(&item.title);
+        if self.all_issues.is_none() {
+            self.all_issues = Some(self.client.fetch_issues().await?);
+        }
+        let existing = self
+            .all_issues
+            .iter()
+            .flatten()
+            .find(|issue| issue.title == title)
+            .cloned();
+        let config = &self.config.sync;
+        let (issue, matched) = match existing {
+            Some(existing) => (existing, true),
+            None => {
+                let body = to_markdown(&item.body);
+                let req = CreateIssueRequest {
+                    title,
+                    body: if body.is_empty() { None } else { Some(body) },
+                    assignees: item.assignees.clone(),
+                    labels: issue_labels(
+                        &item_labels(item, config),
+                        &config.default_labels,
+                        &org_status(&item.state, config),
+                        config,
+                    ),
+                };
+                let issue = self.client.create_issue(req).await?;
+                if let Some(all) = &mut self.all_issues {
+                    all.push(issue.clone());
                 }
+                (issue, false)
             }
+        };
+
+        // Queue property updates (applied in reverse order later)
+        let mut properties = vec![
+            ("GH_ISSUE".to_string(), issue.number.to_string()),
+            ("GH_URL".to_string(), issue.html_url.clone()),
+        ];
+        let mut heading_id = item.id.clone();
+        if self.config.org.assign_ids && item.org_id.is_none() {
+            heading_id = new_id();
+            properties.push(("ID".to_string(), heading_id.clone()));
         }
+        pending_updates.push(PendingUpdate {
+            item: item.clone(),
+            properties,
+            logbook: Vec::new(),
+        });
+
+        self.state.record_sync(
+            issue.number,
+            &heading_id,
+            &issue.title,
+            &normalize_markdown(issue.body.as_deref().unwrap_or("")),
+            &gh_status(&issue, config),
+            &issue.assignees,
+            &org_labels(&issue.labels, config),
+            issue.updated_at,
+        );
+
+        // Notes written before the issue existed go along with it
+        let notes = self.state.items[&issue.number].unposted_notes(&item.notes);
+        post_notes(&self.client, &mut self.state, issue.number, &notes).await?;
+        report.notes_posted += notes.len();
+
+        report.pushed.push(PushItem {
+            title: issue.title,
+            issue_number: issue.number,
+            url: issue.html_url,
+            action: if matched { "matched" } else { "created" }.to_string(),
+        });
+        Ok(Some(issue.number))
+    }
 
-        // Save updated state
-        self.state.save(&org_file.path)?;
+    /// Apply date changes: pushed dates go on the board (adding the issue to
+    /// it when needed), pulled ones onto the planning line
+    async fn set_dates(
+        &mut self,
+        issue_number: u64,
+        item: &OrgItem,
+        changes: &[DateChange],
+        pending_updates: &mut Vec<PendingUpdate>,
+        report: &mut SyncReport,
+    ) -> Result<()> {
+        for change in changes {
+            if let (Direction::Push, Some(board), Some(project)) = (
+                &change.direction,
+                &mut self.board,
+                &self.config.sync.project,
+            ) {
+                push_date(
+                    &self.client,
+                    board,
+                    project,
+                    issue_number,
+                    change.date,
+                    change.value,
+                )
+                .await?;
+            }
+            if let Some(synced) = self.state.items.get_mut(&issue_number) {
+                change.date.set_base(synced, change.value);
+            }
+        }
 
+        if let Some(update) = pulled_dates(item, changes) {
+            let names = update.properties.iter().map(|(k, _)| k.to_lowercase());
+            match report
+                .pulled
+                .iter_mut()
+                .find(|p| p.issue_number == issue_number)
+            {
+                Some(pulled) => pulled.changes.extend(names),
+                None => report.pulled.push(PullItem {
+                    issue_number,
+                    title: item.title.clone(),
+                    changes: names.collect(),
+                }),
+            }
+            pending_updates.push(update);
+        }
         Ok(())
     }
+
+    /// Apply per-field changes to GitHub and org, then record the new base
+    async fn update(
+        &mut self,
+        issue_number: u64,
+        org: &OrgItem,
+        gh: &GhIssue,
+        fields: &[(Field, Direction)],
+        pending_updates: &mut Vec<PendingUpdate>,
+        report: &mut SyncReport,
+    ) -> Result<()> {
+        let config = &self.config.sync;
+        let direction = |field: Field| fields.iter().find(|f| f.0 == field).map(|f| &f.1);
+
+        let item_status = org_status(&org.state, config);
+        let issue_status = gh_status(gh, config);
+        let gh_labels = org_labels(&gh.labels, config);
+        let gh_body = normalize_markdown(gh.body.as_deref().unwrap_or(""));
+
+        // For each field, the value sent to GitHub and the value written to
+        // org, both as Markdown; org markup goes to GitHub as Markdown and
+        // comes back converted to org
+        let org_title = title_to_markdown(&org.title);
+        let org_body = to_markdown(&org.body);
+        let title = targets(direction(Field::Title), &org_title, &gh.title, |v| {
+            v.to_string()
+        });
+        let body = targets(direction(Field::Body), &org_body, &gh_body, |v| {
+            v.to_string()
+        });
+        let state = match direction(Field::State) {
+            Some(Direction::Push) => (Some(item_status.clone()), None),
+            Some(Direction::Pull) => {
+                let new_state = org_state_for_status(&issue_status, &self.keywords);
+                (None, Some(new_state))
+            }
+            Some(Direction::Edit(keyword)) => {
+                let new_state = self
+                    .keywords
+                    .state(keyword)
+                    .unwrap_or_else(|| org.state.clone());
+                (Some(org_status(&new_state, config)), Some(new_state))
+            }
+            Some(Direction::Merge) | None => (None, None),
+        };
+        let assignees = targets(
+            direction(Field::Assignees),
+            &org.assignees,
+            &gh.assignees,
+            split_list,
+        );
+        let org_item_labels = item_labels(org, config);
+        let labels = match direction(Field::Labels) {
+            Some(Direction::Merge) => {
+                let merged = merge_labels(&org_item_labels, &gh_labels);
+                (Some(merged.clone()), Some(merged))
+            }
+            other => targets(other, &org_item_labels, &gh_labels, split_list),
+        };
+
+        let mut gh_updates = UpdateIssueRequest {
+            title: title.0.clone(),
+            body: body.0.clone(),
+            assignees: assignees.0.clone(),
+            labels: labels.0.clone(),
+            ..Default::default()
+        };
+        if let Some(status) = &state.0 {
+            if let Some((state, reason)) = state_change(&issue_status, status) {
+                gh_updates.state = Some(state);
+                gh_updates.state_reason = Some(reason);
+            }
+        }
+
+        // Status labels (DOING, BLOCKED, ...) ride along with the label list
+        let final_status = state.0.clone().unwrap_or_else(|| issue_status.clone());
+        if state.0.is_some() || gh_updates.labels.is_some() {
+            let labels = gh_updates.labels.as_ref().unwrap_or(&gh_labels);
+            let labels = issue_labels(labels, &gh.labels, &final_status, config);
+            gh_updates.labels = if vec_eq(&labels, &gh.labels) {
+                None
+            } else {
+                Some(labels)
+            };
+        }
+
+        let mut org_changes: Vec<(String, String)> = Vec::new();
+        if let Some(title) = &title.1 {
+            org_changes.push(("title".to_string(), title_from_markdown(title)));
+        }
+        if let Some(body) = &body.1 {
+            org_changes.push(("body".to_string(), from_markdown(body)));
+        }
+        if let Some(new_state) = &state.1 {
+            org_changes.push(("state".to_string(), new_state.keyword().to_string()));
+        }
+        if let Some(assignees) = &assignees.1 {
+            org_changes.push(("ASSIGNEE".to_string(), assignees.join(", ")));
+        }
+        if let Some(labels) = &labels.1 {
+            org_changes.extend(label_changes(org, labels, &self.config));
+        }
+
+        let pulled: Vec<String> = fields
+            .iter()
+            .filter(|f| f.1 != Direction::Push)
+            .map(|f| f.0.name().to_string())
+            .collect();
+        if !pulled.is_empty() {
+            report.pulled.push(PullItem {
+                issue_number,
+                title: gh.title.clone(),
+                changes: pulled,
+            });
+        }
+
+        if self.dry_run {
+            return Ok(());
+        }
+
+        let has_gh_updates = gh_updates.title.is_some()
+            || gh_updates.body.is_some()
+            || gh_updates.state.is_some()
+            || gh_updates.assignees.is_some()
+            || gh_updates.labels.is_some();
+        let mut gh_updated_at = gh.updated_at;
+        if has_gh_updates {
+            let issue = self.client.update_issue(issue_number, gh_updates).await?;
+            gh_updated_at = issue.updated_at;
+            report.pushed.push(PushItem {
+                title: issue.title,
+                issue_number,
+                url: issue.html_url,
+                action: "updated".to_string(),
+            });
+        }
+
+        // Whatever the drawer recorded is settled by this update
+        if org.has_conflict {
+            org_changes.push(("conflict".to_string(), String::new()));
+        }
+
+        if !org_changes.is_empty() {
+            pending_updates.push(PendingUpdate {
+                item: org.clone(),
+                properties: org_changes,
+                logbook: Vec::new(),
+            });
+        }
+
+        // New base: the value each applied field now has on both sides;
+        // fields left alone keep their base (or, on first sync, GitHub) value
+        let mut synced = self
+            .state
+            .items
+            .get(&issue_number)
+            .cloned()
+            .unwrap_or_else(|| SyncedItem {
+                org_heading_id: org.id.clone(),
+                title: gh.title.clone(),
+                body_hash: hash_body(&gh_body),
+                body: Some(gh_body.clone()),
+                org_body_hash: None,
+                org_title_hash: None,
+                state: issue_status.clone(),
+                org_state: None,
+                assignees: gh.assignees.clone(),
+                labels: gh_labels.clone(),
+                org_labels: None,
+                gh_updated_at,
+                org_updated_at: None,
+                last_comment_id: None,
+                posted_notes: Vec::new(),
+                conflicts: Vec::new(),
+                parent: None,
+                scheduled: None,
+                deadline: None,
+            });
+
+        if let Some(text) = title.0.clone().or(title.1.clone()) {
+            // A title written to org may not convert back to the same Markdown
+            let org_text = match &title.1 {
+                Some(pulled) => title_to_markdown(&title_from_markdown(pulled)),
+                None => text.clone(),
+            };
+            synced.org_title_hash = (org_text != text).then(|| hash_body(&org_text));
+            synced.title = text;
+        }
+        if let Some(text) = body.0.clone().or(body.1.clone()) {
+            // A body written to org may not convert back to the same Markdown
+            let org_text = match &body.1 {
+                Some(pulled) => to_markdown(&from_markdown(pulled)),
+                None => text.clone(),
+            };
+            synced.body_hash = hash_body(&text);
+            synced.org_body_hash = (org_text != text).then(|| hash_body(&org_text));
+            synced.body = Some(text);
+        }
+        if direction(Field::State).is_some() {
+            // A pulled status without a keyword in the file maps back to
+            // another one
+            let written = match &state.1 {
+                Some(new_state) => org_status(new_state, config),
+                None => final_status.clone(),
+            };
+            synced.org_state = (written != final_status).then_some(written);
+            synced.state = final_status;
+        }
+        if let Some(assignees) = assignees.0.or(assignees.1) {
+            synced.assignees = assignees;
+        }
+        if let Some(labels) = labels.0.or(labels.1) {
+            let written = written_labels(org, &labels, &self.config);
+            synced.org_labels = (!vec_eq(&written, &labels)).then_some(written);
+            synced.labels = labels;
+        }
+        synced.org_heading_id = org.id.clone();
+        synced.conflicts.clear();
+        synced.gh_updated_at = gh_updated_at;
+        synced.org_updated_at = Some(Utc::now());
+        self.state.items.insert(issue_number, synced);
+
+        Ok(())
+    }
+
+    /// Queue a `:GH_CONFLICT:` drawer for conflicting fields and remember them
+    fn mark_conflict(
+        &mut self,
+        issue_number: u64,
+        fields: &[Field],
+        org: &OrgItem,
+        gh: &GhIssue,
+    ) -> Option<PendingUpdate> {
+        let base = self.state.items.get_mut(&issue_number)?;
+        let entries: Vec<String> = fields
+            .iter()
+            .map(|&field| {
+                let (local, remote) = field_values(field, org, gh, &self.config.sync);
+                conflict_entry(field.name(), &local, &remote, base.value(field).as_deref())
+            })
+            .collect();
+        base.conflicts = fields.to_vec();
+
+        Some(PendingUpdate {
+            item: org.clone(),
+            properties: vec![("conflict".to_string(), entries.join("\n"))],
+            logbook: Vec::new(),
+        })
+    }
+
+    /// Org and GitHub values of a conflicting field, for reporting
+    fn conflict_values(&self, field: Field, org: &OrgItem, gh: &GhIssue) -> (String, String) {
+        match field {
+            Field::Body => ("(changed)".to_string(), "(changed)".to_string()),
+            _ => field_values(field, org, gh, &self.config.sync),
+        }
+    }
+}
+
+/// Org and GitHub values of a field as text, for showing to the user
+pub fn field_values(
+    field: Field,
+    org: &OrgItem,
+    gh: &GhIssue,
+    config: &SyncConfig,
+) -> (String, String) {
+    match field {
+        Field::Title => (org.title.clone(), gh.title.clone()),
+        Field::Body => (org.body.clone(), gh.body.clone().unwrap_or_default()),
+        Field::State => (org.state.keyword().to_string(), gh_status(gh, config)),
+        Field::Assignees => (org.assignees.join(", "), gh.assignees.join(", ")),
+        Field::Labels => (
+            item_labels(org, config).join(", "),
+            org_labels(&gh.labels, config).join(", "),
+        ),
+    }
+}
+
+/// Value to send to GitHub and value to write into org for one field
+fn targets<T: Clone>(
+    direction: Option<&Direction>,
+    org: &T,
+    gh: &T,
+    parse: impl Fn(&str) -> T,
+) -> (Option<T>, Option<T>) {
+    match direction {
+        Some(Direction::Push) => (Some(org.clone()), None),
+        Some(Direction::Pull) => (None, Some(gh.clone())),
+        Some(Direction::Edit(value)) => {
+            let value = parse(value);
+            (Some(value.clone()), Some(value))
+        }
+        Some(Direction::Merge) | None => (None, None),
+    }
 }
@@ -0,0 +1,437 @@
+//! GitHub issues brought into org as new headings
+//!
+//! Issues matching the import filter that no heading links to are filed
+//! under the import heading, and `org-gh clone` writes a whole file of them.
+//! Each gets the keyword, priority cookie, labels, body and LOGBOOK comments
+//! a pull would give it, and is recorded as synced, so the next sync finds
+//! nothing to do.
+
+use crate::config::{Config, SyncConfig};
+use crate::error::Result;
+use crate::github::model::{GhComment, GhIssue};
+use crate::github::GitHubClient;
+use crate::org::markdown::{
+    from_markdown, normalize_markdown, title_from_markdown, title_to_markdown, to_markdown,
+};
+use crate::org::model::{OrgFile, TodoKeywords};
+use crate::org::parser::{is_valid_tag, slugify};
+use crate::org::writer::{append_under_heading, comment_entry, new_id, NewHeading};
+use crate::sync::engine::SyncAction;
+use crate::sync::labels::{label_priority, org_labels, without_priority_labels};
+use crate::sync::state::{hash_body, SyncState};
+use crate::sync::status::{gh_status, org_state_for_status, org_status};
+use clap::ValueEnum;
+use std::collections::HashMap;
+
+/// How `org-gh clone` groups issues under top-level headings
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, ValueEnum)]
+pub enum GroupBy {
+    /// One heading per milestone
+    #[default]
+    Milestone,
+    /// One heading per label (an issue goes under its first label)
+    Label,
+    /// No grouping: every issue is a top-level heading
+    None,
+}
+
+/// Search filter for the issues to import: open issues unless the filter
+/// asks for a state itself
+pub fn import_query(filter: &str) -> String {
+    let has_state = filter.split_whitespace().any(|term| {
+        term.starts_with("is:open") || term.starts_with("is:closed") || term.starts_with("state:")
+    });
+    if has_state {
+        filter.trim().to_string()
+    } else {
+        format!("is:open {}", filter.trim()).trim_end().to_string()
+    }
+}
+
+/// The org heading for an issue (level 1; set the level when placing it),
+/// with its comments in the LOGBOOK
+pub fn issue_heading(
+    issue: &GhIssue,
+    comments: &[GhComment],
+    config: &Config,
+    keywords: &TodoKeywords,
+) -> NewHeading {
+    let status = gh_status(issue, &config.sync);
+    let labels = org_labels(&issue.labels, &config.sync);
+    let priority = label_priority(&labels, &config.sync);
+    let labels = without_priority_labels(&labels, &config.sync);
+
+    let (tags, labels): (Vec<String>, Vec<String>) = if config.org.tags_as_labels {
+        labels.into_iter().partition(|label| is_valid_tag(label))
+    } else {
+        (Vec::new(), labels)
+    };
+
+    let mut properties = vec![
+        ("GH_ISSUE".to_string(), issue.number.to_string()),
+        ("GH_URL".to_string(), issue.html_url.clone()),
+    ];
+    if !issue.assignees.is_empty() {
+        properties.push(("ASSIGNEE".to_string(), issue.assignees.join(", ")));
+    }
+    if !labels.is_empty() {
+        properties.push(("LABELS".to_string(), labels.join(", ")));
+    }
+    if config.org.assign_ids {
+        properties.push(("ID".to_string(), new_id()));
+    }
+
+    NewHeading {
+        level: 1,
+        keyword: org_state_for_status(&status, keywords)
+            .keyword()
+            .to_string(),
+        priority,
+        title: title_from_markdown(&issue.title),
+        tags,
+        properties,
+        logbook: comments
+            .iter()
+            .map(|c| comment_entry(&c.author, c.created_at, &c.body))
+            .collect(),
+        body: from_markdown(issue.body.as_deref().unwrap_or("")),
+    }
+}
+
+/// Record an issue as synced with the heading written for it, and its
+/// comments as already in the LOGBOOK
+pub fn record_issue(
+    state: &mut SyncState,
+    issue: &GhIssue,
+    heading: &NewHeading,
+    comments: &[GhComment],
+    config: &SyncConfig,
+    keywords: &TodoKeywords,
+) {
+    let heading_id = heading
+        .properties
+        .iter()
+        .find(|(key, _)| key == "ID")
+        .map_or_else(|| slugify(&heading.title), |(_, id)| id.clone());
+    let body = normalize_markdown(issue.body.as_deref().unwrap_or(""));
+    let status = gh_status(issue, config);
+    state.record_sync(
+        issue.number,
+        &heading_id,
+        &issue.title,
+        &body,
+        &status,
+        &issue.assignees,
+        &org_labels(&issue.labels, config),
+        issue.updated_at,
+    );
+
+    // The title and body written to org may not convert back to the same
+    // Markdown, nor the keyword to the same status
+    let org_title = title_to_markdown(&heading.title);
+    let org_body = to_markdown(&heading.body);
+    let org_state = keywords
+        .state(&heading.keyword)
+        .map_or_else(|| status.clone(), |state| org_status(&state, config));
+    if let Some(synced) = state.items.get_mut(&issue.number) {
+        synced.org_title_hash = (org_title != issue.title).then(|| hash_body(&org_title));
+        synced.org_body_hash = (org_body != body).then(|| hash_body(&org_body));
+        synced.org_state = (org_state != status).then_some(org_state);
+    }
+    if let Some(newest) = comments.iter().map(|c| c.id).max() {
+        state.record_comments(issue.number, newest);
+    }
+}
+
+/// Import actions for the issues matching `filter` that no heading or
+/// state entry links to yet
+pub async fn import_actions(
+    client: &GitHubClient,
+    filter: &str,
+    state: &SyncState,
+    org_file: &OrgFile,
+) -> Result<Vec<SyncAction>> {
+    let linked = |number: u64| {
+        state.items.contains_key(&number)
+            || org_file.items.iter().any(|i| i.gh_issue == Some(number))
+    };
+    let mut issues = client.search_issues(&import_query(filter)).await?;
+    issues.sort_by_key(|issue| issue.number);
+    Ok(issues
+        .into_iter()
+        .filter(|issue| !linked(issue.number))
+        .map(|gh_issue| SyncAction::ImportIssue { gh_issue })
+        .collect())
+}
+
+/// File imported issues under the import heading of `content`, with their
+/// comments in the LOGBOOK, and record them as synced
+pub async fn file_imports(
+    client: &GitHubClient,
+    state: &mut SyncState,
+    content: &str,
+    issues: &[GhIssue],
+    config: &Config,
+    keywords: &TodoKeywords,
+) -> Result<String> {
+    let numbers: Vec<u64> = issues.iter().map(|issue| issue.number).collect();
+    let comments = client.fetch_comments_for(&numbers, None).await?;
+    let comments_of = |issue: &GhIssue| comments.get(&issue.number).map_or(&[][..], Vec::as_slice);
+    let headings: Vec<NewHeading> = issues
+        .iter()
+        .map(|issue| issue_heading(issue, comments_of(issue), config, keywords))
+        .collect();
+    let content = append_under_heading(content, &config.sync.import_heading, &headings);
+    for (issue, heading) in issues.iter().zip(&headings) {
+        record_issue(
+            state,
+            issue,
+            heading,
+            comments_of(issue),
+            &config.sync,
+            keywords,
+        );
+    }
+    Ok(content)
+}
+
+/// A new org file holding `issues`, and the sync state that links them
+///
+/// Issues are listed by number within each group; groups are sorted by
+/// name, with issues that have no milestone or label in a last group.
+/// `comments` (by issue number) go into each heading's LOGBOOK.
+pub fn clone_file(
+    repo: &str,
+    issues: &[GhIssue],
+    comments: &HashMap<u64, Vec<GhComment>>,
+    group_by: GroupBy,
+    config: &Config,
+) -> (String, SyncState) {
+    let keywords = TodoKeywords::from_config(&config.org);
+    let mut state = SyncState::new(repo);
+    let mut content = format!("#+TITLE: {}\n#+GH_REPO: {}\n", repo, repo);
+
+    let mut issues: Vec<&GhIssue> = issues.iter().collect();
+    issues.sort_by_key(|issue| issue.number);
+
+    let group_of = |issue: &GhIssue| match group_by {
+        GroupBy::Milestone => issue.milestone.clone(),
+        GroupBy::Label => {
+            let labels = org_labels(&issue.labels, &config.sync);
+            without_priority_labels(&labels, &config.sync)
+                .into_iter()
+                .next()
+        }
+        GroupBy::None => None,
+    };
+    let mut groups: Vec<(Option<String>, Vec<&GhIssue>)> = Vec::new();
+    for issue in issues {
+        let group = group_of(issue);
+        match groups.iter_mut().find(|(name, _)| *name == group) {
+            Some((_, members)) => members.push(issue),
+            None => groups.push((group, vec![issue])),
+        }
+    }
+    groups.sort_by(|(a, _), (b, _)| (a.is_none(), a).cmp(&(b.is_none(), b)));
+
+    let ungrouped = match group_by {
+        GroupBy::Label => "Unlabeled",
+        _ => "No milestone",
+    };
+    for (group, members) in groups {
+        content.push('\n');
+        let level = if group_by == GroupBy::None {
+            1
+        } else {
+            let title = group.as_deref().unwrap_or(ungrouped);
+            content.push_str(&format!("* {}\n", title));
+            2
+        };
+        for issue in members {
+            let comments = comments.get(&issue.number).map_or(&[][..], Vec::as_slice);
+            let heading = NewHeading {
+                level,
+                ..issue_heading(issue, comments, config, &keywords)
+            };
+            content.push_str(&heading.to_org());
+            record_issue(
+                &mut state,
+                issue,
+                &heading,
+                comments,
+                &config.sync,
+                &keywords,
+            );
+        }
+    }
+
+    (content, state)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::github::model::GhIssueState;
+    use chrono::Utc;
+
+    #[test]
+    fn test_import_query() {
+        assert_eq!(import_query(""), "is:open");
+        assert_eq!(import_query("label:bug"), "is:open label:bug");
+        assert_eq!(
+            import_query("is:closed milestone:v1"),
+            "is:closed milestone:v1"
+        );
+    }
+
+    #[test]
+    fn test_issue_heading() {
+        let issue = GhIssue {
+            number: 12,
+            title: "Fix `parser`".to_string(),
+            body: Some("Steps:\n\n- **crash**".to_string()),
+            state: GhIssueState::Open,
+            state_reason: None,
+            assignees: vec!["tftio".to_string()],
+            labels: vec![
+                "bug".to_string(),
+                "good first issue".to_string(),
+                "priority-high".to_string(),
+                "blocked".to_string(),
+            ],
+            milestone: None,
+            created_at: Utc::now(),
+            updated_at: Utc::now(),
+            closed_at: None,
+            html_url: "https://github.com/o/r/issues/12".to_string(),
+            comments: 0,
+            is_pull_request: false,
+        };
+        let mut config = Config::default();
+        config.org.tags_as_labels = true;
+        let keywords = TodoKeywords::from_config(&config.org);
+
+        assert_eq!(
+            issue_heading(&issue, &[], &config, &keywords).to_org(),
+            "* BLOCKED [#A] Fix =parser= :bug:
+:PROPERTIES:
+:GH_ISSUE: 12
+:GH_URL: https://github.com/o/r/issues/12
+:ASSIGNEE: tftio
+:LABELS: good first issue
+:END:
+Steps:
+
+- *crash*
+"
+        );
+    }
+
+    fn issue(number: u64, milestone: Option<&str>, labels: &[&str]) -> GhIssue {
+        GhIssue {
+            number,
+            title: format!("Issue {}", number),
+            body: None,
+            state: GhIssueState::Open,
+            state_reason: None,
+            assignees: vec![],
+            labels: labels.iter().map(|l| l.to_string()).collect(),
+            milestone: milestone.map(String::from),
+            created_at: Utc::now(),
+            updated_at: Utc::now(),
+            closed_at: None,
+            html_url: format!("https://github.com/o/r/issues/{}", number),
+            comments: 0,
+            is_pull_request: false,
+        }
+    }
+
+    #[test]
+    fn test_clone_file() {
+        let issues = vec![
+            issue(3, Some("v2"), &["bug"]),
+            issue(1, None, &[]),
+            issue(2, Some("v1"), &["priority-high", "docs"]),
+            issue(4, Some("v2"), &[]),
+        ];
+        let config = Config::default();
+
+        let comments = HashMap::from([(
+            4,
+            vec![GhComment {
+                id: 40,
+                author: "tftio".to_string(),
+                body: "Looks good".to_string(),
+                created_at: "2026-01-09T17:10:26Z".parse().unwrap(),
+                updated_at: Utc::now(),
+            }],
+        )]);
+
+        let (content, state) = clone_file("o/r", &issues, &comments, GroupBy::Milestone, &config);
+        let headings: Vec<&str> = content.lines().filter(|l| l.starts_with('*')).collect();
+        assert!(content.starts_with("#+TITLE: o/r\n#+GH_REPO: o/r\n\n* v1\n"));
+        assert_eq!(
+            headings,
+            vec![
+                "* v1",
+                "** TODO [#A] Issue 2",
+                "* v2",
+                "** TODO Issue 3",
+                "** TODO Issue 4",
+                "* No milestone",
+                "** TODO Issue 1",
+            ]
+        );
+        assert_eq!(state.repo, "o/r");
+        assert_eq!(state.items.len(), 4);
+        assert_eq!(state.items[&2].org_heading_id, "issue-2");
+        assert_eq!(state.items[&2].labels, vec!["priority-high", "docs"]);
+        assert!(content.contains(
+            ":LOGBOOK:\n- Comment by @tftio [2026-01-09T17:10:26Z]:\n  Looks good\n:END:\n"
+        ));
+        assert_eq!(state.items[&4].last_comment_id, Some(40));
+
+        // Priority labels don't make a group
+        let (content, _) = clone_file("o/r", &issues, &HashMap::new(), GroupBy::Label, &config);
+        let groups: Vec<&str> = content.lines().filter(|l| l.starts_with("* ")).collect();
+        assert_eq!(groups, vec!["* bug", "* docs", "* Unlabeled"]);
+
+        let (content, _) = clone_file("o/r", &issues, &HashMap::new(), GroupBy::None, &config);
+        assert!(content.contains("\n\n* TODO Issue 1\n:PROPERTIES:\n"));
+    }
+
+    #[test]
+    fn test_cloned_markup_is_in_sync() {
+        use crate::org::parser::parse_content;
+        use crate::sync::diff::{org_has_changes, three_way_diff, FieldChange};
+
+        // Text that org would read as markup, or that does not come back
+        // as the same Markdown
+        let issues = vec![
+            GhIssue {
+                title: "Support /usr/local/ paths".to_string(),
+                body: Some("=x= and ~x~".to_string()),
+                ..issue(1, None, &[])
+            },
+            GhIssue {
+                title: "Handle a*b*c".to_string(),
+                body: Some("\\*x\\* and 2*3*4".to_string()),
+                ..issue(2, None, &[])
+            },
+        ];
+        let config = Config::default();
+        let (content, state) = clone_file("o/r", &issues, &HashMap::new(), GroupBy::None, &config);
+        let org_file = parse_content(std::path::Path::new("todo.org"), &content).unwrap();
+
+        for (item, issue) in org_file.items.iter().zip(&issues) {
+            let base = &state.items[&issue.number];
+            assert!(!org_has_changes(item, base, &config.sync), "{:?}", item);
+            let diff = three_way_diff(item, issue, base, &config.sync);
+            assert!(
+                diff.changes().iter().all(|(_, c)| **c == FieldChange::None),
+                "{:?}",
+                diff
+            );
+        }
+    }
+}
@@ -0,0 +1,264 @@
+//! Labels as org sees them and as GitHub holds them
+//!
+//! Org manages an issue's labels except for status labels, default labels
+//! and, when a label prefix is set, labels without the prefix. Those stay
+//! on GitHub untouched and never show up in org.
+//!
+//! Priority labels stand for the headline's priority cookie. They count as
+//! org labels during a sync and are never prefixed.
+
+use crate::config::{Config, SyncConfig};
+use crate::org::model::OrgItem;
+use crate::org::parser::{is_valid_tag, ARCHIVE_TAG};
+use crate::sync::status::status_label;
+
+/// Whether org manages a GitHub label
+fn is_managed(label: &str, config: &SyncConfig) -> bool {
+    is_priority_label(label, config)
+        || (!config.status_labels().iter().any(|(_, l)| *l == label)
+            && !config.default_labels.iter().any(|l| l == label)
+            && config
+                .label_prefix
+                .as_ref()
+                .is_none_or(|prefix| label.starts_with(prefix.as_str())))
+}
+
+/// Whether a label is the label for some priority
+fn is_priority_label(label: &str, config: &SyncConfig) -> bool {
+    config.priority_labels.values().any(|l| l == label)
+}
+
+/// Label for a priority cookie (`A` → `priority-high`), if one is mapped
+pub fn priority_label<'a>(priority: &str, config: &'a SyncConfig) -> Option<&'a str> {
+    config.priority_labels.get(priority).map(String::as_str)
+}
+
+/// Priority given by a set of labels: the highest one with a label present
+pub fn label_priority(labels: &[String], config: &SyncConfig) -> Option<String> {
+    config
+        .priority_labels
+        .iter()
+        .find(|(_, label)| labels.contains(label))
+        .map(|(priority, _)| priority.clone())
+}
+
+/// An item's labels with the label for its priority cookie added
+pub fn item_labels(item: &OrgItem, config: &SyncConfig) -> Vec<String> {
+    let mut labels = item.labels.clone();
+    let priority = item.priority.as_deref();
+    if let Some(label) = priority.and_then(|p| priority_label(p, config)) {
+        if !labels.iter().any(|l| l == label) {
+            labels.push(label.to_string());
+        }
+    }
+    labels
+}
+
+/// Labels without the priority labels
+pub fn without_priority_labels(labels: &[String], config: &SyncConfig) -> Vec<String> {
+    labels
+        .iter()
+        .filter(|label| !is_priority_label(label, config))
+        .cloned()
+        .collect()
+}
+
+/// GitHub labels as org sees them: managed labels, prefix removed
+pub fn org_labels(gh_labels: &[String], config: &SyncConfig) -> Vec<String> {
+    let prefix = config.label_prefix.as_deref().unwrap_or("");
+    gh_labels
+        .iter()
+        .filter(|label| is_managed(label, config))
+        .map(|label| {
+            if is_priority_label(label, config) {
+                label.clone()
+            } else {
+                label[prefix.len()..].to_string()
+            }
+        })
+        .collect()
+}
+
+/// Labels to give an issue: the org labels with the prefix added, the
+/// unmanaged labels it already has, and the label for `status`
+pub fn issue_labels(
+    labels: &[String],
+    current: &[String],
+    status: &str,
+    config: &SyncConfig,
+) -> Vec<String> {
+    let prefix = config.label_prefix.as_deref().unwrap_or("");
+    let mut out: Vec<String> = labels
+        .iter()
+        .map(|label| {
+            if is_priority_label(label, config) {
+                label.clone()
+            } else {
+                format!("{}{}", prefix, label)
+            }
+        })
+        .collect();
+    for label in current {
+        let is_status = config.status_labels().iter().any(|(_, l)| l == label);
+        if !is_status && !is_managed(label, config) && !out.contains(label) {
+            out.push(label.clone());
+        }
+    }
+    if let Some(label) = status_label(status, config) {
+        out.push(label.to_string());
+    }
+    out
+}
+
+/// Org edits that give an item these labels
+///
+/// A priority label sets the priority cookie. With `tags_as_labels`, labels
+/// become headline tags; inherited labels are left where they come from, and
+/// labels that are not valid tags stay in `:LABELS:`.
+pub fn label_changes(item: &OrgItem, labels: &[String], config: &Config) -> Vec<(String, String)> {
+    let mut changes = Vec::new();
+    let priority = label_priority(labels, &config.sync);
+    let has_label = |p: &String| priority_label(p, &config.sync).is_some();
+    if priority != item.priority && (priority.is_some() || item.priority.iter().any(has_label)) {
+        changes.push(("priority".to_string(), priority.unwrap_or_default()));
+    }
+    let labels = without_priority_labels(labels, &config.sync);
+
+    if !config.org.tags_as_labels {
+        changes.push(("LABELS".to_string(), labels.join(", ")));
+        return changes;
+    }
+
+    let (tags, others): (Vec<&String>, Vec<&String>) = labels
+        .iter()
+        .filter(|label| !item.inherited_tags.contains(label))
+        .partition(|label| is_valid_tag(label));
+    let had_property = item
+        .labels
+        .iter()
+        .any(|label| !item.tags.contains(label) && !item.inherited_tags.contains(label));
+
+    changes.push((
+        "tags".to_string(),
+        tags.iter()
+            .map(|t| t.as_str())
+            .collect::<Vec<_>>()
+            .join(":"),
+    ));
+    if had_property || !others.is_empty() {
+        let others: Vec<&str> = others.iter().map(|l| l.as_str()).collect();
+        changes.push(("LABELS".to_string(), others.join(", ")));
+    }
+    changes
+}
+
+/// Labels the org item has once `labels` are written to it: inherited
+/// tags stay on the heading whatever the labels are
+pub fn written_labels(item: &OrgItem, labels: &[String], config: &Config) -> Vec<String> {
+    let mut written = labels.to_vec();
+    if config.org.tags_as_labels {
+        for tag in &item.inherited_tags {
+            if tag != ARCHIVE_TAG && !written.contains(tag) {
+                written.push(tag.clone());
+            }
+        }
+    }
+    written
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::org::parser::parse_content;
+    use std::path::Path;
+
+    fn labels(names: &[&str]) -> Vec<String> {
+        names.iter().map(|l| l.to_string()).collect()
+    }
+
+    #[test]
+    fn test_org_labels() {
+        let mut config = SyncConfig {
+            default_labels: labels(&["org-gh-managed"]),
+            ..Default::default()
+        };
+        let gh = labels(&["bug", "blocked", "org-gh-managed", "roadmap-tier1"]);
+        assert_eq!(org_labels(&gh, &config), vec!["bug", "roadmap-tier1"]);
+
+        config.label_prefix = Some("roadmap-".to_string());
+        assert_eq!(org_labels(&gh, &config), vec!["tier1"]);
+
+        // Priority labels are never prefixed
+        let gh = labels(&["priority-high", "roadmap-tier1"]);
+        assert_eq!(org_labels(&gh, &config), vec!["priority-high", "tier1"]);
+        assert_eq!(
+            issue_labels(&org_labels(&gh, &config), &gh, "open", &config),
+            gh
+        );
+    }
+
+    #[test]
+    fn test_priority_labels() {
+        let config = SyncConfig::default();
+        assert_eq!(priority_label("A", &config), Some("priority-high"));
+        assert_eq!(priority_label("D", &config), None);
+
+        let gh = labels(&["bug", "priority-low", "priority-high"]);
+        assert_eq!(label_priority(&gh, &config), Some("A".to_string()));
+        assert_eq!(label_priority(&labels(&["bug"]), &config), None);
+        assert_eq!(without_priority_labels(&gh, &config), vec!["bug"]);
+    }
+
+    #[test]
+    fn test_issue_labels() {
+        let config = SyncConfig {
+            label_prefix: Some("roadmap-".to_string()),
+            default_labels: labels(&["org-gh-managed"]),
+            ..Default::default()
+        };
+        let current = labels(&["bug", "in-progress", "roadmap-old"]);
+        assert_eq!(
+            issue_labels(&labels(&["tier1"]), &current, "BLOCKED", &config),
+            vec!["roadmap-tier1", "bug", "blocked"]
+        );
+        // New issues get the default labels
+        assert_eq!(
+            issue_labels(&labels(&["tier1"]), &config.default_labels, "open", &config),
+            vec!["roadmap-tier1", "org-gh-managed"]
+        );
+    }
+
+    #[test]
+    fn test_label_changes() {
+        let content =
+            "#+FILETAGS: :bug:\n* TODO [#A] Task :ui:\n:PROPERTIES:\n:GH_ISSUE: 2\n:END:\n";
+        let org_file = parse_content(Path::new("todo.org"), content).unwrap();
+        let item = &org_file.items[0];
+        let mut config = Config::default();
+        config.org.tags_as_labels = true;
+
+        let gh = labels(&["bug", "needs review", "priority-low", "ux"]);
+        assert_eq!(
+            label_changes(item, &gh, &config),
+            vec![
+                ("priority".to_string(), "C".to_string()),
+                ("tags".to_string(), "ux".to_string()),
+                ("LABELS".to_string(), "needs review".to_string()),
+            ]
+        );
+        // The inherited tag stays on the heading
+        assert_eq!(
+            written_labels(item, &labels(&["ux"]), &config),
+            vec!["ux", "bug"]
+        );
+
+        config.org.tags_as_labels = false;
+        assert_eq!(
+            label_changes(item, &labels(&["ux"]), &config),
+            vec![
+                ("priority".to_string(), String::new()),
+                ("LABELS".to_string(), "ux".to_string()),
+            ]
+        );
+    }
+}
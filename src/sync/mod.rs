@@ -1,6 +1,15 @@
+pub mod dates;
 pub mod diff;
 pub mod engine;
+pub mod import;
+pub mod labels;
+pub mod notes;
+pub mod parents;
+pub mod pending;
+pub mod relink;
+pub mod removed;
 pub mod state;
+pub mod status;
 
 pub use engine::SyncEngine;
 pub use state::SyncState;
@@ -0,0 +1,112 @@
+//! GitHub comments and org notes, kept in step through the LOGBOOK
+//!
+//! New comments on an issue are copied into its heading's LOGBOOK, and
+//! notes added to the LOGBOOK in org are posted as comments. The sync state
+//! keeps the newest comment copied and the comment posted for each note, so
+//! neither side gets its own entries back.
+
+use crate::error::Result;
+use crate::github::model::{GhComment, GhIssue};
+use crate::github::GitHubClient;
+use crate::org::model::OrgFile;
+use crate::org::writer::comment_entry;
+use crate::sync::state::{PendingNote, SyncState, SyncedItem};
+
+/// Listed issues that `org_file` links and that have comments to look at
+pub fn commented_issues(org_file: &OrgFile, gh_issues: &[GhIssue]) -> Vec<u64> {
+    let linked = |number: u64| {
+        org_file
+            .items
+            .iter()
+            .any(|item| !item.archived && item.gh_issue == Some(number))
+    };
+    gh_issues
+        .iter()
+        .filter(|issue| issue.comments > 0 && linked(issue.number))
+        .map(|issue| issue.number)
+        .collect()
+}
+
+/// LOGBOOK entries for the comments not yet copied, and the newest comment
+/// id seen
+///
+/// The newest id includes comments we posted ourselves from org notes,
+/// which are in the LOGBOOK already and get no entry.
+pub fn comment_entries(
+    comments: Vec<GhComment>,
+    base: Option<&SyncedItem>,
+) -> (Vec<String>, Option<u64>) {
+    let last_seen = base.and_then(|base| base.last_comment_id);
+    let comments: Vec<GhComment> = comments
+        .into_iter()
+        .filter(|c| last_seen.is_none_or(|id| c.id > id))
+        .collect();
+
+    let posted = |id: u64| base.is_some_and(|b| b.posted_notes.iter().any(|p| p.comment_id == id));
+
+    let newest = comments.iter().map(|c| c.id).max();
+    let entries = comments
+        .iter()
+        .filter(|c| !posted(c.id))
+        .map(|c| comment_entry(&c.author, c.created_at, &c.body))
+        .collect();
+    (entries, newest)
+}
+
+/// Post org notes as comments, or update the comments of edited notes, and
+/// record them
+pub async fn post_notes(
+    client: &GitHubClient,
+    state: &mut SyncState,
+    issue_number: u64,
+    notes: &[PendingNote],
+) -> Result<()> {
+    for note in notes {
+        let comment = match note.comment_id {
+            Some(id) => client.update_comment(id, &note.body).await?,
+            None => client.create_comment(issue_number, &note.body).await?,
+        };
+        state.record_note(issue_number, &note.key, comment.id);
+    }
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use chrono::Utc;
+
+    fn comment(id: u64, body: &str) -> GhComment {
+        GhComment {
+            id,
+            author: "octocat".to_string(),
+            body: body.to_string(),
+            created_at: Utc::now(),
+            updated_at: Utc::now(),
+        }
+    }
+
+    #[test]
+    fn test_comment_entries() {
+        let comments = || vec![comment(1, "Old"), comment(2, "Posted"), comment(3, "New")];
+
+        // Without a base every comment is new
+        let (entries, newest) = comment_entries(comments(), None);
+        assert_eq!(entries.len(), 3);
+        assert_eq!(newest, Some(3));
+
+        // Seen and self-posted comments get no entry, but count as seen
+        let mut state = SyncState::new("o/r");
+        state.record_sync(7, "id", "Title", "Body", "open", &[], &[], Utc::now());
+        state.record_comments(7, 1);
+        state.record_note(7, "note", 2);
+        let (entries, newest) = comment_entries(comments(), state.items.get(&7));
+        assert_eq!(entries.len(), 1);
+        assert!(entries[0].contains("New"));
+        assert_eq!(newest, Some(3));
+
+        let (entries, newest) = comment_entries(vec![comment(1, "Old")], state.items.get(&7));
+        assert!(entries.is_empty());
+        assert_eq!(newest, None);
+    }
+}
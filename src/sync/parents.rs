@@ -0,0 +1,107 @@
+//! Child headings as GitHub sub-issues
+//!
+//! A linked heading under another linked heading makes its issue a
+//! sub-issue of the parent heading's issue. Moving the heading in org moves
+//! the issue; sub-issue changes made on GitHub are left alone.
+
+use crate::error::Result;
+use crate::github::GitHubClient;
+use crate::org::model::{OrgFile, OrgItem};
+use crate::sync::engine::SyncAction;
+use crate::sync::state::{SyncState, SyncedItem};
+
+/// Issue linked to the heading enclosing `item`, if any
+pub fn parent_issue(org_file: &OrgFile, item: &OrgItem) -> Option<u64> {
+    org_file.items.get(item.parent?)?.gh_issue
+}
+
+/// Action moving the issue of `item` under the issue of its parent heading,
+/// if that changed since the last sync
+pub fn parent_action(
+    org_file: &OrgFile,
+    item: &OrgItem,
+    issue_number: u64,
+    base: Option<&SyncedItem>,
+) -> Option<SyncAction> {
+    let parent = parent_issue(org_file, item);
+    let previous = base.and_then(|base| base.parent);
+    (parent != previous).then(|| SyncAction::SetParent {
+        issue_number,
+        title: item.title.clone(),
+        parent,
+        previous,
+    })
+}
+
+/// Attach an issue to `parent` (detaching it from `previous` when it moves
+/// to the top level) and remember the new parent
+pub async fn set_parent(
+    client: &GitHubClient,
+    state: &mut SyncState,
+    issue_number: u64,
+    parent: Option<u64>,
+    previous: Option<u64>,
+) -> Result<()> {
+    match (parent, previous) {
+        (Some(parent), _) => {
+            // Adding an issue to the parent it already has is an error
+            let siblings = client.fetch_sub_issues(parent).await?;
+            if !siblings.contains(&issue_number) {
+                client.add_sub_issue(parent, issue_number).await?;
+            }
+        }
+        (None, Some(previous)) => {
+            client.remove_sub_issue(previous, issue_number).await?;
+        }
+        (None, None) => {}
+    }
+
+    if let Some(synced) = state.items.get_mut(&issue_number) {
+        synced.parent = parent;
+    }
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::org::parser::parse_content;
+    use chrono::Utc;
+    use std::path::Path;
+
+    #[test]
+    fn test_parent_action() {
+        let content = "* TODO Epic\n:PROPERTIES:\n:GH_ISSUE: 1\n:END:\n** TODO Task\n:PROPERTIES:\n:GH_ISSUE: 2\n:END:\n";
+        let org_file = parse_content(Path::new("todo.org"), content).unwrap();
+        let task = &org_file.items[1];
+        assert_eq!(parent_issue(&org_file, task), Some(1));
+
+        // New under the epic
+        assert!(matches!(
+            parent_action(&org_file, task, 2, None),
+            Some(SyncAction::SetParent {
+                parent: Some(1),
+                previous: None,
+                ..
+            })
+        ));
+
+        // Already there
+        let mut state = SyncState::new("o/r");
+        state.record_sync(2, "task", "Task", "", "open", &[], &[], Utc::now());
+        state.items.get_mut(&2).unwrap().parent = Some(1);
+        assert!(parent_action(&org_file, task, 2, state.items.get(&2)).is_none());
+
+        // Moved to the top level
+        let epic = &org_file.items[0];
+        state.items.get_mut(&2).unwrap().parent = Some(3);
+        assert!(matches!(
+            parent_action(&org_file, epic, 2, state.items.get(&2)),
+            Some(SyncAction::SetParent {
+                parent: None,
+                previous: Some(3),
+                ..
+            })
+        ));
+    }
+}
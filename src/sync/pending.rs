@@ -0,0 +1,128 @@
+//! Org edits queued while a plan executes
+//!
+//! Actions queue their edits to a heading instead of rewriting the file as
+//! they go. The edits are applied together at the end, one heading at a
+//! time from the last to the first, so the spans of headings not yet
+//! edited still hold.
+
+use crate::org::model::OrgItem;
+use crate::org::parser::split_tags;
+use crate::org::writer::{
+    append_logbook_entries, set_body, set_drawer, set_planning, set_priority, set_properties,
+    set_tags, set_title, set_todo_state, update_statistics_cookies,
+};
+use crate::sync::dates::PlanningDate;
+use chrono::NaiveDate;
+
+/// Pending property updates to apply to org file (grouped by item)
+pub(crate) struct PendingUpdate {
+    pub(crate) item: OrgItem,
+    pub(crate) properties: Vec<(String, String)>, // (property_name, value)
+    pub(crate) logbook: Vec<String>,
+}
+
+/// Apply queued org edits, last item first so earlier spans stay valid
+pub(crate) fn apply_pending_updates(
+    content: &str,
+    mut pending_updates: Vec<PendingUpdate>,
+) -> String {
+    let mut content = content.to_string();
+
+    // Sort by span start position descending
+    pending_updates.sort_by_key(|u| std::cmp::Reverse(u.item.span.start));
+
+    // One update per item, so later edits never see a stale span
+    let mut merged: Vec<PendingUpdate> = Vec::new();
+    for update in pending_updates {
+        match merged.last_mut() {
+            Some(last) if last.item.span.start == update.item.span.start => {
+                last.properties.extend(update.properties);
+                last.logbook.extend(update.logbook);
+            }
+            _ => merged.push(update),
+        }
+    }
+
+    for update in merged {
+        let change = |key: &str| {
+            update
+                .properties
+                .iter()
+                .find(|(k, _)| k == key)
+                .map(|(_, v)| v.as_str())
+        };
+
+        // Within an item, edit back to front: section body and LOGBOOK,
+        // then the property drawer, then the headline line itself
+        if let Some(new_body) = change("body") {
+            content = set_body(&content, &update.item, new_body);
+        }
+
+        if !update.logbook.is_empty() {
+            content = append_logbook_entries(&content, &update.item, &update.logbook);
+        }
+
+        // An empty value removes the drawer
+        if let Some(conflict) = change("conflict") {
+            let entries = if conflict.is_empty() {
+                Vec::new()
+            } else {
+                vec![conflict.to_string()]
+            };
+            content = set_drawer(&content, &update.item, "GH_CONFLICT", &entries);
+        }
+
+        let props: Vec<(&str, &str)> = update
+            .properties
+            .iter()
+            .filter(|(k, _)| {
+                !matches!(
+                    k.as_str(),
+                    "state"
+                        | "title"
+                        | "body"
+                        | "conflict"
+                        | "tags"
+                        | "priority"
+                        | "SCHEDULED"
+                        | "DEADLINE"
+                )
+            })
+            .map(|(k, v)| (k.as_str(), v.as_str()))
+            .collect();
+
+        if !props.is_empty() {
+            content = set_properties(&content, &update.item, &props);
+        }
+
+        for date in PlanningDate::ALL {
+            if let Some(value) = change(date.keyword()) {
+                let value = NaiveDate::parse_from_str(value, "%Y-%m-%d").ok();
+                content = set_planning(&content, &update.item, date.keyword(), value);
+            }
+        }
+
+        if let Some(new_body) = change("body") {
+            content = update_statistics_cookies(&content, &update.item, new_body);
+        }
+
+        if let Some(tags) = change("tags") {
+            content = set_tags(&content, &update.item, &split_tags(tags));
+        }
+
+        if let Some(new_title) = change("title") {
+            content = set_title(&content, &update.item, new_title);
+        }
+
+        if let Some(priority) = change("priority") {
+            let priority = Some(priority).filter(|p| !p.is_empty());
+            content = set_priority(&content, &update.item, priority);
+        }
+
+        if let Some(new_state) = change("state") {
+            content = set_todo_state(&content, &update.item, new_state);
+        }
+    }
+
+    content
+}
@@ -0,0 +1,118 @@
+//! Heading identity across renames
+//!
+//! A linked heading is recorded in the sync state under its heading id
+//! (`:ID:`, `:CUSTOM_ID:` or the slugified title). A heading that lost its
+//! `:GH_ISSUE:` property is re-attached to the issue synced under its id,
+//! and under `assign_ids` every linked heading gets an org-id `:ID:`.
+
+use crate::org::model::{OrgFile, OrgItem};
+use crate::org::writer::new_id;
+use crate::sync::engine::SyncAction;
+use crate::sync::pending::PendingUpdate;
+use crate::sync::state::SyncState;
+use std::collections::HashMap;
+
+/// Unlinked headings to re-attach to the issue synced with their
+/// heading id, by item index
+///
+/// Catches a `:GH_ISSUE:` property deleted by accident; issues another
+/// heading still links are left alone.
+pub fn relinked(org_file: &OrgFile, state: &SyncState) -> HashMap<usize, u64> {
+    let linked: Vec<u64> = org_file.items.iter().filter_map(|i| i.gh_issue).collect();
+    let mut relinked = HashMap::new();
+    for (index, item) in org_file.items.iter().enumerate() {
+        if item.gh_issue.is_some() || item.archived {
+            continue;
+        }
+        let issue = state.items.iter().find(|(num, synced)| {
+            synced.org_heading_id == item.id
+                && !linked.contains(num)
+                && !relinked.values().any(|n| n == *num)
+        });
+        if let Some((&num, _)) = issue {
+            relinked.insert(index, num);
+        }
+    }
+    relinked
+}
+
+/// `org_file` with the `relinked` headings linked again
+pub fn relinked_file(org_file: &OrgFile, relinked: &HashMap<usize, u64>) -> OrgFile {
+    let mut file = org_file.clone();
+    for (&index, &issue_num) in relinked {
+        file.items[index].gh_issue = Some(issue_num);
+    }
+    file
+}
+
+/// The identity properties a linked heading needs written, if any
+pub fn link_action(
+    item: &OrgItem,
+    issue_number: u64,
+    relink: bool,
+    assign_ids: bool,
+) -> Option<SyncAction> {
+    let assign_id = assign_ids && item.org_id.is_none();
+    (relink || assign_id).then(|| SyncAction::Link {
+        issue_number,
+        org_item: item.clone(),
+        relink,
+        assign_id,
+    })
+}
+
+/// The property edits for a `Link` action, and the `:ID:` it assigns
+pub(crate) fn link_update(
+    item: OrgItem,
+    issue_number: u64,
+    relink: bool,
+    assign_id: bool,
+) -> (PendingUpdate, Option<String>) {
+    let mut properties = Vec::new();
+    if relink {
+        properties.push(("GH_ISSUE".to_string(), issue_number.to_string()));
+    }
+    let id = assign_id.then(new_id);
+    if let Some(id) = &id {
+        properties.push(("ID".to_string(), id.clone()));
+    }
+    let update = PendingUpdate {
+        item,
+        properties,
+        logbook: Vec::new(),
+    };
+    (update, id)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::org::parser::parse_content;
+    use chrono::Utc;
+    use std::path::Path;
+
+    #[test]
+    fn test_relinked() {
+        let content = "* TODO Lost\n:PROPERTIES:\n:ID: lost\n:END:\n* TODO Kept\n:PROPERTIES:\n:ID: kept\n:GH_ISSUE: 2\n:END:\n";
+        let org_file = parse_content(Path::new("todo.org"), content).unwrap();
+        let mut state = SyncState::new("o/r");
+        state.record_sync(1, "lost", "Lost", "", "open", &[], &[], Utc::now());
+        state.record_sync(2, "kept", "Kept", "", "open", &[], &[], Utc::now());
+
+        let relinked = relinked(&org_file, &state);
+        assert_eq!(relinked, HashMap::from([(0, 1)]));
+        assert_eq!(
+            relinked_file(&org_file, &relinked).items[0].gh_issue,
+            Some(1)
+        );
+
+        let lost = &org_file.items[0];
+        assert!(link_action(lost, 1, false, false).is_none());
+        let (update, id) = link_update(lost.clone(), 1, true, true);
+        assert_eq!(
+            update.properties[0],
+            ("GH_ISSUE".to_string(), "1".to_string())
+        );
+        assert_eq!(update.properties[1].1, id.unwrap());
+    }
+}
@@ -0,0 +1,545 @@
+//! Linked headings that are no longer in the file
+//!
+//! A heading leaves a file by being deleted, archived (org moves the subtree
+//! to the archive file) or moved by hand to another org file for the same
+//! repository. Only deleted headings get the `deleted_heading` policy; the
+//! other two are recognized by the issue's `:GH_ISSUE:` property turning up
+//! in the archive file or a sibling org file.
+//!
+//! Archiving also goes the other way: headings whose issues were closed on
+//! GitHub are archived here as org would, and their issues stay tracked.
+
+use crate::config::{ArchiveClosed, DeletedHeading, OrgConfig, SyncConfig};
+use crate::error::Result;
+use crate::github::model::{GhIssue, GhIssueState, GhStateReason, UpdateIssueRequest};
+use crate::github::GitHubClient;
+use crate::org::model::{OrgFile, OrgItem};
+use crate::org::parser::{parse_content_with, ARCHIVE_TAG};
+use crate::org::writer::{archive_subtree, set_tags};
+use crate::sync::diff::Field;
+use crate::sync::engine::{Direction, SyncAction};
+use crate::sync::state::SyncState;
+use crate::sync::status::{gh_status, status_is_open};
+use chrono::Local;
+use std::collections::HashMap;
+use std::io::Write;
+use std::path::{Path, PathBuf};
+
+/// Where a linked heading went
+#[derive(Debug, Clone)]
+pub enum Gone {
+    Deleted,
+    /// Into the file's archive, as `item`
+    Archived {
+        path: PathBuf,
+        item: Box<OrgItem>,
+    },
+    /// In another org file linked to the same repository
+    Moved(PathBuf),
+}
+
+/// The file archived subtrees go to
+///
+/// Follows `#+ARCHIVE:`, or else the configured `archive_location`, where
+/// `%s` stands for the file's name and a leading `~/` for the home
+/// directory. A location without a file part archives into the file itself.
+pub fn archive_path(org_file: &OrgFile, config: &OrgConfig) -> PathBuf {
+    let location = org_file
+        .archive
+        .as_deref()
+        .unwrap_or(&config.archive_location);
+    let file = location.split("::").next().unwrap_or("").trim();
+    if file.is_empty() {
+        return org_file.path.clone();
+    }
+
+    let name = org_file
+        .path
+        .file_name()
+        .map(|n| n.to_string_lossy().to_string())
+        .unwrap_or_default();
+    let file = file.replace("%s", &name);
+    home_path(&file).unwrap_or_else(|| org_dir(&org_file.path).join(file))
+}
+
+/// A path starting with `~/`, under the home directory
+fn home_path(path: &str) -> Option<PathBuf> {
+    let rest = path.strip_prefix("~/")?;
+    Some(directories::BaseDirs::new()?.home_dir().join(rest))
+}
+
+/// Find where each of `issues` went, looking in the archive file (when
+/// `scan_archive` is set) and in the other org files next to `org_file`
+/// that sync with `repo`
+///
+/// Archived headings whose `:ARCHIVE_FILE:` names another file don't count.
+pub fn locate(
+    org_file: &OrgFile,
+    repo: &str,
+    issues: &[u64],
+    config: &OrgConfig,
+) -> HashMap<u64, Gone> {
+    let mut gone: HashMap<u64, Gone> = issues.iter().map(|&n| (n, Gone::Deleted)).collect();
+
+    let archive = archive_path(org_file, config);
+    let archived = match std::fs::read_to_string(&archive) {
+        Ok(content) if config.scan_archive && archive != org_file.path => {
+            parse_content_with(&archive, &content, config)
+                .map(|file| (content, file.items))
+                .ok()
+        }
+        _ => None,
+    };
+    if let Some((content, items)) = archived {
+        for item in items {
+            let from_here = archived_from(&content, &item)
+                .is_none_or(|from| Path::new(from).file_name() == org_file.path.file_name());
+            let Some(number) = item.gh_issue.filter(|_| from_here) else {
+                continue;
+            };
+            if let Some(place) = gone.get_mut(&number) {
+                *place = Gone::Archived {
+                    path: archive.clone(),
+                    item: Box::new(item),
+                };
+            }
+        }
+    }
+
+    let Ok(entries) = std::fs::read_dir(org_dir(&org_file.path)) else {
+        return gone;
+    };
+    let mut others: Vec<PathBuf> = entries
+        .flatten()
+        .map(|entry| entry.path())
+        .filter(|path| {
+            path.extension().is_some_and(|ext| ext == "org")
+                && path.file_name() != org_file.path.file_name()
+                && path.file_name() != archive.file_name()
+        })
+        .collect();
+    others.sort();
+
+    for path in others {
+        let Ok(content) = std::fs::read_to_string(&path) else {
+            continue;
+        };
+        let (file_repo, numbers) = linked_issues(&content);
+        if file_repo.as_deref() != Some(repo) {
+            continue;
+        }
+        for number in numbers {
+            if let Some(place @ Gone::Deleted) = gone.get_mut(&number) {
+                *place = Gone::Moved(path.clone());
+            }
+        }
+    }
+
+    gone
+}
+
+/// Issues in state that no heading in `org_file` links, with their
+/// synced titles, by issue number
+pub fn removed_items(org_file: &OrgFile, state: &SyncState) -> Vec<(u64, String)> {
+    let mut removed: Vec<(u64, String)> = state
+        .items
+        .iter()
+        .filter(|(num, _)| !org_file.items.iter().any(|i| i.gh_issue == Some(**num)))
+        .map(|(num, synced)| (*num, synced.title.clone()))
+        .collect();
+    removed.sort_by_key(|(num, _)| *num);
+    removed
+}
+
+/// The action `policy` gives a deleted heading
+pub fn deleted_action(issue_number: u64, title: String, policy: DeletedHeading) -> SyncAction {
+    match policy {
+        DeletedHeading::Warn => SyncAction::Warn {
+            message: format!(
+                "Issue #{} ({}) was in org but heading removed",
+                issue_number, title
+            ),
+        },
+        DeletedHeading::Unlink | DeletedHeading::Close => SyncAction::Untrack {
+            issue_number,
+            title,
+            close: policy == DeletedHeading::Close,
+            moved_to: None,
+        },
+        DeletedHeading::Prompt => SyncAction::DeletedHeading {
+            issue_number,
+            title,
+        },
+    }
+}
+
+/// Stop tracking an issue, closing it as not planned first if `close`
+pub async fn untrack(
+    client: &GitHubClient,
+    state: &mut SyncState,
+    issue_number: u64,
+    close: bool,
+) -> Result<()> {
+    if close {
+        client
+            .update_issue(
+                issue_number,
+                UpdateIssueRequest {
+                    state: Some(GhIssueState::Closed),
+                    state_reason: Some(GhStateReason::NotPlanned),
+                    ..Default::default()
+                },
+            )
+            .await?;
+    }
+    state.remove(issue_number);
+    Ok(())
+}
+
+/// Close the issue of an archived heading if it is open
+///
+/// With an `incremental` listing, an issue missing from `gh_issues` is
+/// taken to be closed if it was at the last sync.
+pub async fn archived_action(
+    client: &GitHubClient,
+    state: &SyncState,
+    gh_issues: &[GhIssue],
+    item: &OrgItem,
+    issue_num: u64,
+    incremental: bool,
+) -> Result<SyncAction> {
+    let closed_before = state
+        .items
+        .get(&issue_num)
+        .is_some_and(|base| !status_is_open(&base.state));
+    let gh_issue = match gh_issues.iter().find(|i| i.number == issue_num) {
+        Some(issue) => Some(issue.clone()),
+        None if incremental && closed_before => None,
+        None => client.find_issue(issue_num).await?,
+    };
+
+    Ok(match gh_issue {
+        Some(gh_issue) if gh_issue.state.is_open() => SyncAction::CloseArchived {
+            issue_number: issue_num,
+            org_item: item.clone(),
+            gh_issue,
+        },
+        _ => SyncAction::NoOp {
+            issue_number: issue_num,
+        },
+    })
+}
+
+/// Offer to archive a heading whose issue the update pulls a close for
+pub fn archive_closed_action(action: &SyncAction, policy: ArchiveClosed) -> Option<SyncAction> {
+    let SyncAction::Update {
+        issue_number,
+        org_item,
+        gh_issue,
+        fields,
+    } = action
+    else {
+        return None;
+    };
+    let closed = !gh_issue.state.is_open() && fields.contains(&(Field::State, Direction::Pull));
+    let confirmed = match policy {
+        ArchiveClosed::Never => return None,
+        ArchiveClosed::Prompt => false,
+        ArchiveClosed::Always => true,
+    };
+    closed.then(|| SyncAction::ArchiveHeading {
+        issue_number: *issue_number,
+        org_item: org_item.clone(),
+        confirmed,
+    })
+}
+
+/// Close the issue of an archived heading, as not planned if the heading
+/// was cancelled; it stays tracked, now as closed
+pub async fn close_archived(
+    client: &GitHubClient,
+    state: &mut SyncState,
+    config: &SyncConfig,
+    issue_number: u64,
+    item: &OrgItem,
+) -> Result<GhIssue> {
+    let reason = if item.state.is_cancelled() {
+        GhStateReason::NotPlanned
+    } else {
+        GhStateReason::Completed
+    };
+    let issue = client
+        .update_issue(
+            issue_number,
+            UpdateIssueRequest {
+                state: Some(GhIssueState::Closed),
+                state_reason: Some(reason),
+                ..Default::default()
+            },
+        )
+        .await?;
+
+    if let Some(synced) = state.items.get_mut(&issue_number) {
+        synced.state = gh_status(&issue, config);
+        synced.org_state = None;
+        synced.gh_updated_at = issue.updated_at;
+    }
+    Ok(issue)
+}
+
+/// Archive the headings linked to `issues`, as org's archive command
+/// would: with the ARCHIVE tag when the file archives into itself, or else
+/// cut from the file and returned with the archive file they go to
+pub fn archive_headings(
+    org_file: &mut OrgFile,
+    issues: &[u64],
+    config: &OrgConfig,
+) -> Result<Option<(PathBuf, String)>> {
+    let parsed = parse_content_with(&org_file.path, &org_file.content, config)?;
+    let mut items: Vec<&OrgItem> = parsed
+        .items
+        .iter()
+        .filter(|item| item.gh_issue.is_some_and(|n| issues.contains(&n)))
+        .collect();
+    // A heading inside another archived one goes along with it
+    let spans: Vec<_> = items.iter().map(|item| item.span.clone()).collect();
+    items.retain(|item| {
+        !spans.iter().any(|span| {
+            *span != item.span && span.start <= item.span.start && item.span.end <= span.end
+        })
+    });
+    // Last heading first, so earlier spans stay valid
+    items.sort_by_key(|item| std::cmp::Reverse(item.span.start));
+
+    let path = archive_path(org_file, config);
+    let mut content = org_file.content.clone();
+    let mut archived = None;
+    if path == org_file.path {
+        for item in items {
+            let mut tags = item.tags.clone();
+            tags.push(ARCHIVE_TAG.to_string());
+            content = set_tags(&content, item, &tags);
+        }
+    } else {
+        let time = Local::now().format("%Y-%m-%d %a %H:%M").to_string();
+        let file = org_file.path.display().to_string();
+        let category = org_file
+            .path
+            .file_stem()
+            .map(|s| s.to_string_lossy().to_string())
+            .unwrap_or_default();
+        let mut subtrees = Vec::new();
+        for item in items {
+            let mut props = vec![
+                ("ARCHIVE_TIME", time.as_str()),
+                ("ARCHIVE_FILE", file.as_str()),
+                ("ARCHIVE_CATEGORY", category.as_str()),
+            ];
+            if !item.state.keyword().is_empty() {
+                props.push(("ARCHIVE_TODO", item.state.keyword()));
+            }
+            let (rest, subtree) = archive_subtree(&content, item, &props);
+            content = rest;
+            subtrees.push(subtree);
+        }
+        subtrees.reverse();
+        archived = Some((path, subtrees.concat()));
+    }
+
+    org_file.content = content;
+    Ok(archived)
+}
+
+/// Append archived subtrees to an archive file, starting it with org's
+/// header if it is new
+pub fn append_archive(path: &Path, from: &Path, subtrees: &str) -> Result<()> {
+    if let Some(dir) = path.parent() {
+        std::fs::create_dir_all(dir)?;
+    }
+    let mut file = std::fs::OpenOptions::new()
+        .create(true)
+        .append(true)
+        .open(path)?;
+    if file.metadata()?.len() == 0 {
+        write!(
+            file,
+            "#    -*- mode: org -*-\n\n\nArchived entries from file {}\n\n",
+            from.display()
+        )?;
+    }
+    file.write_all(subtrees.as_bytes())?;
+    Ok(())
+}
+
+/// The file an archived heading came from (its `:ARCHIVE_FILE:`)
+fn archived_from<'a>(content: &'a str, item: &OrgItem) -> Option<&'a str> {
+    let drawer = &content[item.properties_span.clone()?];
+    drawer
+        .lines()
+        .find_map(|line| line.trim().strip_prefix(":ARCHIVE_FILE:"))
+        .map(str::trim)
+}
+
+/// Directory holding an org file
+fn org_dir(path: &Path) -> &Path {
+    match path.parent() {
+        Some(dir) if !dir.as_os_str().is_empty() => dir,
+        _ => Path::new("."),
+    }
+}
+
+/// The `#+GH_REPO:` of org text and the issues its `:GH_ISSUE:` properties link
+fn linked_issues(content: &str) -> (Option<String>, Vec<u64>) {
+    let mut repo = None;
+    let mut numbers = Vec::new();
+    for line in content.lines() {
+        let line = line.trim();
+        let Some((key, value)) = line.split_once(": ").or_else(|| line.split_once(":\t")) else {
+            continue;
+        };
+        let key = key.to_uppercase();
+        if key == "#+GH_REPO" {
+            repo = Some(value.trim().to_string());
+        } else if key == ":GH_ISSUE" {
+            numbers.extend(value.trim().parse::<u64>());
+        }
+    }
+    (repo, numbers)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::org::parser::parse_content;
+    use tempfile::TempDir;
+
+    #[test]
+    fn test_archive_path() {
+        let mut config = OrgConfig::default();
+        let mut org_file = parse_content(Path::new("/notes/todo.org"), "").unwrap();
+        assert_eq!(
+            archive_path(&org_file, &config),
+            PathBuf::from("/notes/todo.org_archive")
+        );
+
+        config.archive_location = "/archive/%s::".to_string();
+        assert_eq!(
+            archive_path(&org_file, &config),
+            PathBuf::from("/archive/todo.org")
+        );
+
+        org_file.archive = Some("archive/%s::* From %s".to_string());
+        assert_eq!(
+            archive_path(&org_file, &config),
+            PathBuf::from("/notes/archive/todo.org")
+        );
+
+        org_file.archive = Some("~/org/archive.org::".to_string());
+        let home = directories::BaseDirs::new()
+            .unwrap()
+            .home_dir()
+            .to_path_buf();
+        assert_eq!(
+            archive_path(&org_file, &config),
+            home.join("org/archive.org")
+        );
+
+        org_file.archive = Some("::* Archived".to_string());
+        assert_eq!(
+            archive_path(&org_file, &config),
+            PathBuf::from("/notes/todo.org")
+        );
+    }
+
+    #[test]
+    fn test_locate() {
+        let dir = TempDir::new().unwrap();
+        let path = dir.path().join("todo.org");
+        let property = |n: u64| format!("* DONE Item\n:PROPERTIES:\n:GH_ISSUE: {}\n:END:\n", n);
+        std::fs::write(
+            dir.path().join("todo.org_archive"),
+            format!(
+                "{}* DONE Other\n:PROPERTIES:\n:GH_ISSUE: 5\n:ARCHIVE_FILE: ~/work/other.org\n:END:\n",
+                property(2)
+            ),
+        )
+        .unwrap();
+        std::fs::write(
+            dir.path().join("other.org"),
+            format!("#+GH_REPO: o/r\n{}{}", property(2), property(3)),
+        )
+        .unwrap();
+        std::fs::write(
+            dir.path().join("elsewhere.org"),
+            format!("#+GH_REPO: o/other\n{}", property(4)),
+        )
+        .unwrap();
+        let org_file = parse_content(&path, "#+GH_REPO: o/r\n").unwrap();
+
+        let mut config = OrgConfig::default();
+        let gone = locate(&org_file, "o/r", &[1, 2, 3, 4, 5], &config);
+        assert!(matches!(gone[&1], Gone::Deleted));
+        assert!(matches!(
+            &gone[&2],
+            Gone::Archived { path, item }
+                if *path == dir.path().join("todo.org_archive") && item.title == "Item"
+        ));
+        assert!(matches!(&gone[&3], Gone::Moved(path) if *path == dir.path().join("other.org")));
+        // Same issue number, different repository
+        assert!(matches!(gone[&4], Gone::Deleted));
+        // Archived from another file
+        assert!(matches!(gone[&5], Gone::Deleted));
+
+        // Without the archive, only the copy in other.org is found
+        config.scan_archive = false;
+        let gone = locate(&org_file, "o/r", &[2], &config);
+        assert!(matches!(gone[&2], Gone::Moved(_)));
+    }
+
+    #[test]
+    fn test_deleted_action() {
+        let org_file = parse_content(
+            Path::new("todo.org"),
+            "* TODO Kept\n:PROPERTIES:\n:GH_ISSUE: 2\n:END:\n",
+        )
+        .unwrap();
+        let mut state = SyncState::new("o/r");
+        for (number, title) in [(3, "Gone"), (2, "Kept"), (1, "Old")] {
+            state.record_sync(
+                number,
+                "id",
+                title,
+                "",
+                "open",
+                &[],
+                &[],
+                chrono::Utc::now(),
+            );
+        }
+        let removed = removed_items(&org_file, &state);
+        assert_eq!(
+            removed,
+            vec![(1, "Old".to_string()), (3, "Gone".to_string())]
+        );
+
+        assert!(matches!(
+            deleted_action(1, "Old".to_string(), DeletedHeading::Warn),
+            SyncAction::Warn { .. }
+        ));
+        assert!(matches!(
+            deleted_action(1, "Old".to_string(), DeletedHeading::Close),
+            SyncAction::Untrack {
+                close: true,
+                moved_to: None,
+                ..
+            }
+        ));
+        assert!(matches!(
+            deleted_action(1, "Old".to_string(), DeletedHeading::Prompt),
+            SyncAction::DeletedHeading {
+                issue_number: 1,
+                ..
+            }
+        ));
+    }
+}
@@ -1,5 +1,7 @@
 use crate::error::Result;
-use chrono::{DateTime, Utc};
+use crate::org::model::OrgNote;
+use crate::sync::diff::Field;
+use chrono::{DateTime, NaiveDate, Utc};
 use serde::{Deserialize, Serialize};
 use sha2::{Digest, Sha256};
 use std::collections::HashMap;
@@ -22,11 +24,68 @@ pub struct SyncedItem {
     pub org_heading_id: String,
     pub title: String,
     pub body_hash: String,
+    /// Last synced body text, the base for merging body edits
+    #[serde(default)]
+    pub body: Option<String>,
+    /// Hash of the org body as Markdown, when converting the pulled body
+    /// to org and back does not give the GitHub text again
+    #[serde(default)]
+    pub org_body_hash: Option<String>,
+    /// Hash of the org title as Markdown, when converting the pulled title
+    /// to org and back does not give the GitHub title again
+    #[serde(default)]
+    pub org_title_hash: Option<String>,
     pub state: String,
+    /// Status of the org heading, when the pulled status has no keyword in
+    /// the org file and the heading got a different one
+    #[serde(default)]
+    pub org_state: Option<String>,
     pub assignees: Vec<String>,
     pub labels: Vec<String>,
+    /// Labels of the org heading, when inherited tags keep labels on it
+    /// that GitHub does not have
+    #[serde(default)]
+    pub org_labels: Option<Vec<String>>,
     pub gh_updated_at: DateTime<Utc>,
     pub org_updated_at: Option<DateTime<Utc>>,
+    /// Newest GitHub comment already copied into the LOGBOOK
+    #[serde(default)]
+    pub last_comment_id: Option<u64>,
+    /// Org notes already posted as GitHub comments
+    #[serde(default)]
+    pub posted_notes: Vec<PostedNote>,
+    /// Fields written into a `:GH_CONFLICT:` drawer; deleting the drawer
+    /// settles them with the org values
+    #[serde(default)]
+    pub conflicts: Vec<Field>,
+    /// Parent issue last set from the org hierarchy
+    #[serde(default)]
+    pub parent: Option<u64>,
+    /// Last synced SCHEDULED date (Projects board date field)
+    #[serde(default)]
+    pub scheduled: Option<NaiveDate>,
+    /// Last synced DEADLINE date (Projects board date field)
+    #[serde(default)]
+    pub deadline: Option<NaiveDate>,
+}
+
+/// An org LOGBOOK note that has been posted as a GitHub comment
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct PostedNote {
+    /// Note timestamp and body hash (see [`note_key`])
+    pub note: String,
+    pub comment_id: u64,
+}
+
+/// An org note to post as a GitHub comment
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct PendingNote {
+    /// State key of the note as it is now (see [`note_key`])
+    pub key: String,
+    pub body: String,
+    /// Comment posted for an earlier version of the note, to update
+    /// instead of posting a new one
+    pub comment_id: Option<u64>,
 }
 
 /// An org heading pending creation in GitHub
@@ -36,6 +95,87 @@ pub struct PendingCreate {
     pub title: String,
 }
 
+impl SyncedItem {
+    /// Last synced value of a field as text, as shown in conflict markers
+    ///
+    /// The body is unknown in state files written before it was stored.
+    pub fn value(&self, field: Field) -> Option<String> {
+        match field {
+            Field::Title => Some(self.title.clone()),
+            Field::Body => self.body.clone(),
+            Field::State => Some(self.state.clone()),
+            Field::Assignees => Some(self.assignees.join(", ")),
+            Field::Labels => Some(self.labels.join(", ")),
+        }
+    }
+
+    /// Hash the org title's Markdown had when last synced
+    pub fn org_title_hash(&self) -> String {
+        self.org_title_hash
+            .clone()
+            .unwrap_or_else(|| hash_body(&self.title))
+    }
+
+    /// Status the org heading had when last synced
+    pub fn org_state(&self) -> &str {
+        self.org_state.as_deref().unwrap_or(&self.state)
+    }
+
+    /// Labels the org heading had when last synced
+    pub fn org_labels(&self) -> &[String] {
+        self.org_labels.as_deref().unwrap_or(&self.labels)
+    }
+
+    /// Hash the org body's Markdown had when last synced
+    pub fn org_body_hash(&self) -> &str {
+        self.org_body_hash.as_deref().unwrap_or(&self.body_hash)
+    }
+
+    /// Non-empty org notes not yet posted as GitHub comments
+    ///
+    /// A posted note whose key no longer matches any note was edited: the
+    /// first unposted note with its timestamp takes over its comment.
+    pub fn unposted_notes(&self, notes: &[OrgNote]) -> Vec<PendingNote> {
+        let notes: Vec<(String, &OrgNote)> = notes
+            .iter()
+            .filter(|note| !note.body.is_empty())
+            .map(|note| (note_key(note), note))
+            .collect();
+        let mut edited: Vec<&PostedNote> = self
+            .posted_notes
+            .iter()
+            .filter(|p| !notes.iter().any(|(key, _)| *key == p.note))
+            .collect();
+
+        notes
+            .iter()
+            .filter(|(key, _)| !self.posted_notes.iter().any(|p| p.note == *key))
+            .map(|(key, note)| {
+                let earlier = edited.iter().position(|p| {
+                    p.note
+                        .strip_prefix(note.timestamp.as_str())
+                        .is_some_and(|rest| rest.starts_with('#'))
+                });
+                PendingNote {
+                    key: key.clone(),
+                    body: note.body.clone(),
+                    comment_id: earlier.map(|i| edited.remove(i).comment_id),
+                }
+            })
+            .collect()
+    }
+}
+
+/// Key a note by its timestamp and a hash of its body, as `[ts]#<hash>`
+///
+/// Notes taken in the same minute stay apart whatever order org adds them
+/// in; editing a note changes its key (see [`SyncedItem::unposted_notes`]).
+pub fn note_key(note: &OrgNote) -> String {
+    let hash = hash_body(note.body.trim());
+    let hex = hash.strip_prefix("sha256:").unwrap_or(&hash);
+    format!("{}#{}", note.timestamp, &hex[..12])
+}
+
 impl SyncState {
     pub fn new(repo: &str) -> Self {
         Self {
@@ -95,20 +235,72 @@ impl SyncState {
         gh_updated_at: DateTime<Utc>,
     ) {
         let body_hash = hash_body(body);
+        let existing = self.items.get(&issue_number);
+        let (last_comment_id, posted_notes, parent) = existing
+            .map(|item| (item.last_comment_id, item.posted_notes.clone(), item.parent))
+            .unwrap_or_default();
+        let (scheduled, deadline) = existing
+            .map(|item| (item.scheduled, item.deadline))
+            .unwrap_or_default();
         self.items.insert(
             issue_number,
             SyncedItem {
                 org_heading_id: org_heading_id.to_string(),
                 title: title.to_string(),
                 body_hash,
+                body: Some(body.to_string()),
+                org_body_hash: None,
+                org_title_hash: None,
                 state: state.to_string(),
+                org_state: None,
                 assignees: assignees.to_vec(),
                 labels: labels.to_vec(),
+                org_labels: None,
                 gh_updated_at,
                 org_updated_at: Some(Utc::now()),
+                last_comment_id,
+                posted_notes,
+                conflicts: Vec::new(),
+                parent,
+                scheduled,
+                deadline,
             },
         );
-        self.last_sync = Some(Utc::now());
+    }
+
+    /// Record the newest comment copied into the LOGBOOK for a synced item
+    pub fn record_comments(&mut self, issue_number: u64, comment_id: u64) {
+        if let Some(item) = self.items.get_mut(&issue_number) {
+            item.last_comment_id = Some(item.last_comment_id.unwrap_or(0).max(comment_id));
+        }
+    }
+
+    /// Record an org note posted as a GitHub comment for a synced item
+    ///
+    /// A comment already recorded, for an earlier version of the note, takes
+    /// the new key.
+    pub fn record_note(&mut self, issue_number: u64, note: &str, comment_id: u64) {
+        if let Some(item) = self.items.get_mut(&issue_number) {
+            match item
+                .posted_notes
+                .iter_mut()
+                .find(|p| p.comment_id == comment_id)
+            {
+                Some(posted) => posted.note = note.to_string(),
+                None => item.posted_notes.push(PostedNote {
+                    note: note.to_string(),
+                    comment_id,
+                }),
+            }
+        }
+    }
+
+    /// Record that all GitHub changes up to `fetched_at` have been processed
+    ///
+    /// This is the cutoff for the next incremental fetch, so it should be the
+    /// time the issue listing was requested, not when the sync finished.
+    pub fn mark_synced(&mut self, fetched_at: DateTime<Utc>) {
+        self.last_sync = Some(fetched_at);
     }
 
     /// Remove an item from sync state
@@ -152,6 +344,122 @@ mod tests {
         );
     }
 
+    #[test]
+    fn test_record_sync_leaves_last_sync() {
+        let mut state = SyncState::new("owner/repo");
+        state.record_sync(1, "id", "Title", "Body", "open", &[], &[], Utc::now());
+        assert!(state.last_sync.is_none());
+
+        let fetched_at = Utc::now();
+        state.mark_synced(fetched_at);
+        assert_eq!(state.last_sync, Some(fetched_at));
+    }
+
+    #[test]
+    fn test_record_sync_keeps_last_comment_id() {
+        let mut state = SyncState::new("owner/repo");
+        state.record_sync(1, "id", "Title", "Body", "open", &[], &[], Utc::now());
+        state.record_comments(1, 42);
+        state.record_comments(1, 7);
+        assert_eq!(state.items[&1].last_comment_id, Some(42));
+
+        state.record_sync(1, "id", "New title", "Body", "open", &[], &[], Utc::now());
+        assert_eq!(state.items[&1].last_comment_id, Some(42));
+    }
+
+    #[test]
+    fn test_unposted_notes() {
+        let note = |timestamp: &str, body: &str| OrgNote {
+            timestamp: timestamp.to_string(),
+            body: body.to_string(),
+        };
+        let notes = vec![
+            note("[2026-01-10 Sat 09:15]", "First"),
+            note("[2026-01-10 Sat 09:15]", "Same minute"),
+            note("[2026-01-11 Sun 10:00]", "Later"),
+        ];
+
+        let mut state = SyncState::new("owner/repo");
+        state.record_sync(1, "id", "Title", "Body", "open", &[], &[], Utc::now());
+        state.record_note(1, &note_key(&notes[0]), 99);
+
+        let unposted = state.items[&1].unposted_notes(&notes);
+        let bodies: Vec<&str> = unposted.iter().map(|n| n.body.as_str()).collect();
+        assert_eq!(bodies, vec!["Same minute", "Later"]);
+        assert_eq!(unposted[0].key, note_key(&notes[1]));
+        assert!(unposted[0].key.starts_with("[2026-01-10 Sat 09:15]#"));
+        assert!(unposted.iter().all(|n| n.comment_id.is_none()));
+
+        // A newer note in the same minute, added first as org does, leaves
+        // the posted one posted
+        let mut newer = vec![note("[2026-01-10 Sat 09:15]", "Newest")];
+        newer.extend(notes.clone());
+        let unposted = state.items[&1].unposted_notes(&newer);
+        let bodies: Vec<&str> = unposted.iter().map(|n| n.body.as_str()).collect();
+        assert_eq!(bodies, vec!["Newest", "Same minute", "Later"]);
+    }
+
+    #[test]
+    fn test_edited_note_updates_its_comment() {
+        let note = |timestamp: &str, body: &str| OrgNote {
+            timestamp: timestamp.to_string(),
+            body: body.to_string(),
+        };
+        let posted = [
+            note("[2026-01-10 Sat 09:15]", "First"),
+            note("[2026-01-10 Sat 09:15]", "Second"),
+        ];
+        let mut state = SyncState::new("owner/repo");
+        state.record_sync(1, "id", "Title", "Body", "open", &[], &[], Utc::now());
+        state.record_note(1, &note_key(&posted[0]), 98);
+        state.record_note(1, &note_key(&posted[1]), 99);
+
+        let edited = vec![
+            note("[2026-01-10 Sat 09:15]", "First"),
+            note("[2026-01-10 Sat 09:15]", "Second, reworded"),
+            note("[2026-01-11 Sun 10:00]", "Later"),
+        ];
+        let unposted = state.items[&1].unposted_notes(&edited);
+        assert_eq!(
+            unposted,
+            vec![
+                PendingNote {
+                    key: note_key(&edited[1]),
+                    body: "Second, reworded".to_string(),
+                    comment_id: Some(99),
+                },
+                PendingNote {
+                    key: note_key(&edited[2]),
+                    body: "Later".to_string(),
+                    comment_id: None,
+                },
+            ]
+        );
+
+        // Updating the comment moves it to the new key
+        state.record_note(1, &unposted[0].key, 99);
+        assert_eq!(state.items[&1].posted_notes.len(), 2);
+        assert_eq!(state.items[&1].unposted_notes(&edited).len(), 1);
+    }
+
+    #[test]
+    fn test_record_sync_keeps_posted_notes() {
+        let mut state = SyncState::new("owner/repo");
+        state.record_note(1, "[2026-01-10 Sat 09:15]", 99);
+        assert!(state.items.is_empty());
+
+        state.record_sync(1, "id", "Title", "Body", "open", &[], &[], Utc::now());
+        state.record_note(1, "[2026-01-10 Sat 09:15]", 99);
+        state.record_sync(1, "id", "New title", "Body", "open", &[], &[], Utc::now());
+        assert_eq!(
+            state.items[&1].posted_notes,
+            vec![PostedNote {
+                note: "[2026-01-10 Sat 09:15]".to_string(),
+                comment_id: 99,
+            }]
+        );
+    }
+
     #[test]
     fn test_hash_body() {
         let hash1 = hash_body("Hello world");
@@ -0,0 +1,220 @@
+use crate::config::SyncConfig;
+use crate::github::model::{GhIssue, GhIssueState, GhStateReason};
+use crate::org::model::{TodoKeywords, TodoState};
+
+/// Workflow status of an org item, as recorded in sync state
+///
+/// `"closed"` for done keywords and `"not_planned"` for cancelled ones, the
+/// keyword itself for open keywords that have a status label (DOING,
+/// BLOCKED, WAITING), and `"open"` otherwise.
+pub fn org_status(state: &TodoState, config: &SyncConfig) -> String {
+    if state.is_cancelled() {
+        return "not_planned".to_string();
+    }
+    if state.is_closed() {
+        return "closed".to_string();
+    }
+    config
+        .status_labels()
+        .iter()
+        .find(|(keyword, _)| *keyword == state.keyword())
+        .map(|(keyword, _)| keyword.to_string())
+        .unwrap_or_else(|| "open".to_string())
+}
+
+/// Workflow status of a GitHub issue, derived from its state, state reason
+/// and labels
+pub fn gh_status(issue: &GhIssue, config: &SyncConfig) -> String {
+    if !issue.state.is_open() {
+        return if issue.state_reason == Some(GhStateReason::NotPlanned) {
+            "not_planned".to_string()
+        } else {
+            "closed".to_string()
+        };
+    }
+    config
+        .status_labels()
+        .iter()
+        .find(|(_, label)| issue.labels.iter().any(|l| l == label))
+        .map(|(keyword, _)| keyword.to_string())
+        .unwrap_or_else(|| "open".to_string())
+}
+
+/// Whether a status is open on GitHub
+pub fn status_is_open(status: &str) -> bool {
+    !matches!(status, "closed" | "not_planned")
+}
+
+/// The state and state reason to send when a status moves from `from` to `to`
+///
+/// `None` when the issue stays open, since open statuses differ only in
+/// their labels.
+pub fn state_change(from: &str, to: &str) -> Option<(GhIssueState, GhStateReason)> {
+    match (status_is_open(from), status_is_open(to)) {
+        (true, true) => None,
+        (false, true) => Some((GhIssueState::Open, GhStateReason::Reopened)),
+        _ if from == to => None,
+        _ if to == "not_planned" => Some((GhIssueState::Closed, GhStateReason::NotPlanned)),
+        _ => Some((GhIssueState::Closed, GhStateReason::Completed)),
+    }
+}
+
+/// The label that marks a status on GitHub, if any
+pub fn status_label<'a>(status: &str, config: &'a SyncConfig) -> Option<&'a str> {
+    config
+        .status_labels()
+        .into_iter()
+        .find(|(keyword, _)| *keyword == status)
+        .map(|(_, label)| label)
+}
+
+/// Labels without the ones that only encode workflow status
+pub fn without_status_labels(labels: &[String], config: &SyncConfig) -> Vec<String> {
+    let status_labels = config.status_labels();
+    labels
+        .iter()
+        .filter(|l| !status_labels.iter().any(|(_, label)| label == l))
+        .cloned()
+        .collect()
+}
+
+/// The org TODO state for a status pulled from GitHub
+///
+/// Falls back to the file's default keywords when the status keyword is
+/// not in use in this file.
+pub fn org_state_for_status(status: &str, keywords: &TodoKeywords) -> TodoState {
+    match status {
+        "closed" => return keywords.default_closed(),
+        "not_planned" => return keywords.default_cancelled(),
+        _ => {}
+    }
+    keywords
+        .state(status)
+        .filter(TodoState::is_open)
+        .unwrap_or_else(|| keywords.default_open())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::github::model::GhIssueState;
+    use chrono::Utc;
+
+    fn make_issue(state: GhIssueState, labels: &[&str]) -> GhIssue {
+        GhIssue {
+            number: 1,
+            title: "Issue".to_string(),
+            body: None,
+            state,
+            state_reason: None,
+            assignees: vec![],
+            labels: labels.iter().map(|l| l.to_string()).collect(),
+            milestone: None,
+            created_at: Utc::now(),
+            updated_at: Utc::now(),
+            closed_at: None,
+            html_url: String::new(),
+            comments: 0,
+            is_pull_request: false,
+        }
+    }
+
+    #[test]
+    fn test_org_status() {
+        let config = SyncConfig::default();
+        assert_eq!(org_status(&TodoState::open("TODO"), &config), "open");
+        assert_eq!(org_status(&TodoState::open("DOING"), &config), "DOING");
+        assert_eq!(org_status(&TodoState::open("BLOCKED"), &config), "BLOCKED");
+        assert_eq!(org_status(&TodoState::closed("DONE"), &config), "closed");
+        assert_eq!(
+            org_status(&TodoState::cancelled("CANCELLED"), &config),
+            "not_planned"
+        );
+    }
+
+    #[test]
+    fn test_gh_status() {
+        let config = SyncConfig::default();
+        assert_eq!(
+            gh_status(&make_issue(GhIssueState::Open, &["bug"]), &config),
+            "open"
+        );
+        assert_eq!(
+            gh_status(
+                &make_issue(GhIssueState::Open, &["bug", "in-progress"]),
+                &config
+            ),
+            "DOING"
+        );
+        assert_eq!(
+            gh_status(&make_issue(GhIssueState::Open, &["waiting"]), &config),
+            "WAITING"
+        );
+        // Status labels on a closed issue don't reopen it
+        assert_eq!(
+            gh_status(&make_issue(GhIssueState::Closed, &["blocked"]), &config),
+            "closed"
+        );
+
+        let mut issue = make_issue(GhIssueState::Closed, &[]);
+        issue.state_reason = Some(GhStateReason::NotPlanned);
+        assert_eq!(gh_status(&issue, &config), "not_planned");
+    }
+
+    #[test]
+    fn test_state_change() {
+        assert_eq!(state_change("open", "DOING"), None);
+        assert_eq!(
+            state_change("DOING", "closed"),
+            Some((GhIssueState::Closed, GhStateReason::Completed))
+        );
+        assert_eq!(
+            state_change("closed", "not_planned"),
+            Some((GhIssueState::Closed, GhStateReason::NotPlanned))
+        );
+        assert_eq!(
+            state_change("not_planned", "open"),
+            Some((GhIssueState::Open, GhStateReason::Reopened))
+        );
+        assert_eq!(state_change("closed", "closed"), None);
+    }
+
+    #[test]
+    fn test_without_status_labels() {
+        let config = SyncConfig::default();
+        let labels = vec!["bug".to_string(), "in-progress".to_string()];
+        assert_eq!(without_status_labels(&labels, &config), vec!["bug"]);
+    }
+
+    #[test]
+    fn test_org_state_for_status() {
+        let keywords = TodoKeywords::default();
+        assert_eq!(
+            org_state_for_status("DOING", &keywords),
+            TodoState::open("DOING")
+        );
+        assert_eq!(
+            org_state_for_status("open", &keywords),
+            TodoState::open("TODO")
+        );
+        assert_eq!(
+            org_state_for_status("closed", &keywords),
+            TodoState::closed("DONE")
+        );
+        assert_eq!(
+            org_state_for_status("not_planned", &keywords),
+            TodoState::cancelled("CANCELLED")
+        );
+
+        // A file whose keywords don't include BLOCKED gets its default open keyword
+        let keywords = TodoKeywords {
+            todo: vec!["NEXT".to_string()],
+            done: vec!["SHIPPED".to_string()],
+            cancelled: vec![],
+        };
+        assert_eq!(
+            org_state_for_status("BLOCKED", &keywords),
+            TodoState::open("NEXT")
+        );
+    }
+}
@@ -1,8 +1,11 @@
 //! Common test utilities and fixtures
 
+// Each test binary uses a different subset of these helpers
+#![allow(dead_code)]
+
 use std::fs;
 use std::path::Path;
-use wiremock::matchers::{method, path, query_param};
+use wiremock::matchers::{method, path, path_regex, query_param};
 use wiremock::{Mock, MockServer, ResponseTemplate};
 
 pub const TEST_REPO: &str = "tftio/org-gh-test-fixture";
@@ -88,6 +91,20 @@ pub async fn setup_mock_github() -> MockServer {
     server
 }
 
+/// Answer comment listings for issues without a fixture with an empty list
+///
+/// Mount after `setup_mock_github` so the issue 5 fixture still wins.
+pub async fn mount_empty_comments(server: &MockServer) {
+    Mock::given(method("GET"))
+        .and(path_regex(format!(
+            r"^/repos/{}/issues/\d+/comments$",
+            TEST_REPO
+        )))
+        .respond_with(ResponseTemplate::new(200).set_body_string("[]"))
+        .mount(server)
+        .await;
+}
+
 /// Create a test org file content
 pub fn sample_org_content() -> String {
     format!(
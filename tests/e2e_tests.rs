@@ -161,6 +161,7 @@ async fn e2e_update_issue_fields() {
                 title: Some(format!("{} (updated)", title)),
                 body: Some("Updated body content".to_string()),
                 state: None,
+                state_reason: None,
                 assignees: None,
                 labels: Some(vec!["e2e-test".to_string(), "updated".to_string()]),
             },
@@ -317,6 +318,7 @@ Second test item body.
         force: false,
         dry_run: false,
         verbose: true,
+        full: false,
     };
 
     org_gh::cli::sync::run(args, Format::Human)
@@ -417,6 +419,7 @@ Test body
         force: false,
         dry_run: false,
         verbose: true,
+        full: false,
     };
 
     org_gh::cli::sync::run(args, Format::Human)
@@ -510,6 +513,7 @@ Test body
         force: false,
         dry_run: false,
         verbose: true,
+        full: false,
     };
 
     org_gh::cli::sync::run(args, Format::Human)
@@ -609,6 +613,7 @@ Test body
         force: false,
         dry_run: false,
         verbose: true,
+        full: false,
     };
 
     org_gh::cli::sync::run(args, Format::Human)
@@ -683,6 +688,7 @@ Local description
         force: false,
         dry_run: false,
         verbose: true,
+        full: false,
     };
 
     org_gh::cli::sync::run(args, Format::Human)
@@ -754,6 +760,7 @@ Should not be created
         force: false,
         dry_run: true,
         verbose: true,
+        full: false,
     };
 
     org_gh::cli::sync::run(args, Format::Human)
@@ -46,6 +46,179 @@ async fn test_fetch_issues() {
     assert!(!issue_3.unwrap().state.is_open()); // closed
 }
 
+#[tokio::test]
+async fn test_fetch_issues_follows_pagination() {
+    use wiremock::matchers::{method, path, query_param};
+    use wiremock::{Mock, MockServer, ResponseTemplate};
+
+    let server = MockServer::start().await;
+    let issues_path = format!("/repos/{}/issues", TEST_REPO);
+
+    // Second page of open issues
+    Mock::given(method("GET"))
+        .and(path(issues_path.clone()))
+        .and(query_param("state", "open"))
+        .and(query_param("page", "2"))
+        .respond_with(
+            ResponseTemplate::new(200)
+                .set_body_string(format!("[{}]", load_fixture("issue_2.json"))),
+        )
+        .with_priority(1)
+        .mount(&server)
+        .await;
+
+    // First page links to the second
+    let next = format!(
+        "<{}{}?state=open&per_page=100&page=2>; rel=\"next\"",
+        server.uri(),
+        issues_path
+    );
+    Mock::given(method("GET"))
+        .and(path(issues_path.clone()))
+        .and(query_param("state", "open"))
+        .respond_with(
+            ResponseTemplate::new(200)
+                .insert_header("Link", next.as_str())
+                .set_body_string(format!("[{}]", load_fixture("issue_1.json"))),
+        )
+        .mount(&server)
+        .await;
+
+    Mock::given(method("GET"))
+        .and(path(issues_path))
+        .and(query_param("state", "closed"))
+        .respond_with(
+            ResponseTemplate::new(200)
+                .set_body_string(format!("[{}]", load_fixture("issue_3.json"))),
+        )
+        .mount(&server)
+        .await;
+
+    let client = GitHubClient::with_base_url("fake-token", TEST_REPO, Some(&server.uri()))
+        .await
+        .expect("Failed to create client");
+
+    let issues = client.fetch_issues().await.expect("Failed to fetch issues");
+
+    let mut numbers: Vec<u64> = issues.iter().map(|i| i.number).collect();
+    numbers.sort();
+    assert_eq!(numbers, vec![1, 2, 3]);
+}
+
+#[tokio::test]
+async fn test_fetch_issues_since_sends_since() {
+    use wiremock::matchers::{method, path, query_param_contains};
+    use wiremock::{Mock, MockServer, ResponseTemplate};
+
+    let server = MockServer::start().await;
+
+    Mock::given(method("GET"))
+        .and(path(format!("/repos/{}/issues", TEST_REPO)))
+        .and(query_param_contains("since", "2026-01-09"))
+        .respond_with(
+            ResponseTemplate::new(200)
+                .set_body_string(format!("[{}]", load_fixture("issue_1.json"))),
+        )
+        .expect(2) // open and closed listings
+        .mount(&server)
+        .await;
+
+    let client = GitHubClient::with_base_url("fake-token", TEST_REPO, Some(&server.uri()))
+        .await
+        .expect("Failed to create client");
+
+    let since = chrono::DateTime::parse_from_rfc3339("2026-01-09T12:00:00Z")
+        .unwrap()
+        .with_timezone(&chrono::Utc);
+    let issues = client
+        .fetch_issues_since(Some(since))
+        .await
+        .expect("Failed to fetch issues");
+
+    assert_eq!(issues.len(), 2);
+}
+
+#[tokio::test]
+async fn test_find_issue_not_found() {
+    use wiremock::matchers::{method, path};
+    use wiremock::{Mock, MockServer, ResponseTemplate};
+
+    let server = MockServer::start().await;
+
+    Mock::given(method("GET"))
+        .and(path(format!("/repos/{}/issues/999", TEST_REPO)))
+        .respond_with(ResponseTemplate::new(404).set_body_string(
+            r#"{"message":"Not Found","documentation_url":"https://docs.github.com"}"#,
+        ))
+        .mount(&server)
+        .await;
+
+    let client = GitHubClient::with_base_url("fake-token", TEST_REPO, Some(&server.uri()))
+        .await
+        .expect("Failed to create client");
+
+    let issue = client
+        .find_issue(999)
+        .await
+        .expect("Lookup should not error");
+    assert!(issue.is_none());
+}
+
+#[tokio::test]
+async fn test_fetch_listing_separates_pull_requests() {
+    use common::load_fixture_json;
+    use wiremock::matchers::{method, path, query_param};
+    use wiremock::{Mock, MockServer, ResponseTemplate};
+
+    let server = MockServer::start().await;
+
+    // A pull request sharing its title with issue #1 and closing it
+    let mut pr = load_fixture_json("issue_1.json");
+    pr["number"] = 7.into();
+    pr["body"] = "Fixes #1".into();
+    pr["html_url"] = format!("https://github.com/{}/pull/7", TEST_REPO).into();
+    pr["pull_request"] = serde_json::json!({
+        "url": format!("https://api.github.com/repos/{}/pulls/7", TEST_REPO),
+        "html_url": format!("https://github.com/{}/pull/7", TEST_REPO),
+        "diff_url": format!("https://github.com/{}/pull/7.diff", TEST_REPO),
+        "patch_url": format!("https://github.com/{}/pull/7.patch", TEST_REPO),
+    });
+    let open = serde_json::json!([pr, load_fixture_json("issue_1.json")]);
+
+    Mock::given(method("GET"))
+        .and(path(format!("/repos/{}/issues", TEST_REPO)))
+        .and(query_param("state", "open"))
+        .respond_with(ResponseTemplate::new(200).set_body_json(open))
+        .mount(&server)
+        .await;
+    Mock::given(method("GET"))
+        .and(path(format!("/repos/{}/issues", TEST_REPO)))
+        .and(query_param("state", "closed"))
+        .respond_with(ResponseTemplate::new(200).set_body_string("[]"))
+        .mount(&server)
+        .await;
+
+    let client = GitHubClient::with_base_url("fake-token", TEST_REPO, Some(&server.uri()))
+        .await
+        .expect("Failed to create client");
+
+    let listing = client.fetch_listing(None).await.expect("Failed to fetch");
+    assert_eq!(listing.issues.len(), 1);
+    assert_eq!(listing.issues[0].number, 1);
+    assert!(!listing.issues[0].is_pull_request);
+    assert_eq!(listing.pull_requests.len(), 1);
+    assert_eq!(listing.pull_requests[0].number, 7);
+    assert!(listing.pull_requests[0].links_issue(1));
+
+    // Title matching must never pick the pull request
+    let found = client
+        .find_by_title("Test issue open simple")
+        .await
+        .expect("Failed to search")
+        .expect("Issue should match");
+    assert_eq!(found.number, 1);
+}
+
 #[tokio::test]
 async fn test_get_single_issue() {
     let server = setup_mock_github().await;
@@ -79,6 +252,92 @@ async fn test_fetch_comments() {
     assert_eq!(comments[1].body, "Second comment for testing");
 }
 
+#[tokio::test]
+async fn test_fetch_comments_for() {
+    let server = setup_mock_github().await;
+    common::mount_empty_comments(&server).await;
+
+    let client = GitHubClient::with_base_url("fake-token", TEST_REPO, Some(&server.uri()))
+        .await
+        .expect("Failed to create client");
+
+    let comments = client
+        .fetch_comments_for(&[1, 5, 7], None)
+        .await
+        .expect("Failed to fetch comments");
+
+    assert_eq!(comments.len(), 3);
+    assert_eq!(comments[&5].len(), 2);
+    assert_eq!(comments[&5][0].body, "First comment on the issue");
+    assert!(comments[&1].is_empty());
+    assert!(comments[&7].is_empty());
+}
+
+#[tokio::test]
+async fn test_fetch_comments_since_sends_since() {
+    use wiremock::matchers::{method, path, query_param_contains};
+    use wiremock::{Mock, MockServer, ResponseTemplate};
+
+    let server = MockServer::start().await;
+
+    Mock::given(method("GET"))
+        .and(path(format!("/repos/{}/issues/5/comments", TEST_REPO)))
+        .and(query_param_contains("since", "2026-01-09"))
+        .respond_with(
+            ResponseTemplate::new(200).set_body_string(load_fixture("issue_5_comments.json")),
+        )
+        .expect(1)
+        .mount(&server)
+        .await;
+
+    let client = GitHubClient::with_base_url("fake-token", TEST_REPO, Some(&server.uri()))
+        .await
+        .expect("Failed to create client");
+
+    let since = chrono::DateTime::parse_from_rfc3339("2026-01-09T12:00:00Z")
+        .unwrap()
+        .with_timezone(&chrono::Utc);
+    let comments = client
+        .fetch_comments_since(5, Some(since))
+        .await
+        .expect("Failed to fetch comments");
+
+    assert_eq!(comments.len(), 2);
+    assert!(comments[0].id < comments[1].id);
+}
+
+#[tokio::test]
+async fn test_create_comment() {
+    use wiremock::matchers::{body_json, method, path};
+    use wiremock::{Mock, MockServer, ResponseTemplate};
+
+    let server = MockServer::start().await;
+
+    let comments: serde_json::Value =
+        serde_json::from_str(&load_fixture("issue_5_comments.json")).unwrap();
+    Mock::given(method("POST"))
+        .and(path(format!("/repos/{}/issues/5/comments", TEST_REPO)))
+        .and(body_json(
+            serde_json::json!({"body": "First comment on the issue"}),
+        ))
+        .respond_with(ResponseTemplate::new(201).set_body_json(&comments[0]))
+        .expect(1)
+        .mount(&server)
+        .await;
+
+    let client = GitHubClient::with_base_url("fake-token", TEST_REPO, Some(&server.uri()))
+        .await
+        .expect("Failed to create client");
+
+    let comment = client
+        .create_comment(5, "First comment on the issue")
+        .await
+        .expect("Failed to create comment");
+
+    assert_eq!(comment.id, 3729844078);
+    assert_eq!(comment.body, "First comment on the issue");
+}
+
 #[tokio::test]
 async fn test_find_by_title() {
     let server = setup_mock_github().await;
@@ -267,6 +526,7 @@ fn test_sync_state_remove() {
 
 #[test]
 fn test_three_way_diff_no_changes() {
+    use org_gh::config::SyncConfig;
     use org_gh::github::model::{GhIssue, GhIssueState};
     use org_gh::org::model::{OrgItem, TodoState};
     use org_gh::sync::diff::three_way_diff;
@@ -276,15 +536,25 @@ fn test_three_way_diff_no_changes() {
         id: "test".to_string(),
         title: "Title".to_string(),
         body: "Body".to_string(),
-        state: TodoState::Todo,
+        state: TodoState::open("TODO"),
+        priority: None,
         gh_issue: Some(1),
         gh_url: None,
         assignees: vec![],
         labels: vec![],
+        tags: vec![],
+        inherited_tags: vec![],
         created: None,
         updated: None,
+        scheduled: None,
+        deadline: None,
         span: 0..10,
         properties_span: None,
+        notes: vec![],
+        has_conflict: false,
+        archived: false,
+        org_id: None,
+        parent: None,
     };
 
     let gh = GhIssue {
@@ -292,26 +562,41 @@ fn test_three_way_diff_no_changes() {
         title: "Title".to_string(),
         body: Some("Body".to_string()),
         state: GhIssueState::Open,
+        state_reason: None,
         assignees: vec![],
         labels: vec![],
+        milestone: None,
         created_at: chrono::Utc::now(),
         updated_at: chrono::Utc::now(),
         closed_at: None,
         html_url: "https://github.com/test/repo/issues/1".to_string(),
+        comments: 0,
+        is_pull_request: false,
     };
 
     let base = SyncedItem {
         org_heading_id: "test".to_string(),
         title: "Title".to_string(),
         body_hash: hash_body("Body"),
+        body: Some("Body".to_string()),
+        org_body_hash: None,
+        org_title_hash: None,
         state: "open".to_string(),
+        org_state: None,
         assignees: vec![],
         labels: vec![],
+        org_labels: None,
         gh_updated_at: chrono::Utc::now(),
         org_updated_at: None,
+        last_comment_id: None,
+        posted_notes: vec![],
+        conflicts: vec![],
+        parent: None,
+        scheduled: None,
+        deadline: None,
     };
 
-    let diff = three_way_diff(&org, &gh, &base);
+    let diff = three_way_diff(&org, &gh, &base, &SyncConfig::default());
 
     assert!(!diff.has_changes());
     assert!(!diff.has_conflicts());
@@ -319,6 +604,7 @@ fn test_three_way_diff_no_changes() {
 
 #[test]
 fn test_three_way_diff_org_changed() {
+    use org_gh::config::SyncConfig;
     use org_gh::github::model::{GhIssue, GhIssueState};
     use org_gh::org::model::{OrgItem, TodoState};
     use org_gh::sync::diff::{three_way_diff, FieldChange};
@@ -328,15 +614,25 @@ fn test_three_way_diff_org_changed() {
         id: "test".to_string(),
         title: "New Title".to_string(), // Changed
         body: "Body".to_string(),
-        state: TodoState::Todo,
+        state: TodoState::open("TODO"),
+        priority: None,
         gh_issue: Some(1),
         gh_url: None,
         assignees: vec![],
         labels: vec![],
+        tags: vec![],
+        inherited_tags: vec![],
         created: None,
         updated: None,
+        scheduled: None,
+        deadline: None,
         span: 0..10,
         properties_span: None,
+        notes: vec![],
+        has_conflict: false,
+        archived: false,
+        org_id: None,
+        parent: None,
     };
 
     let gh = GhIssue {
@@ -344,26 +640,41 @@ fn test_three_way_diff_org_changed() {
         title: "Title".to_string(), // Unchanged
         body: Some("Body".to_string()),
         state: GhIssueState::Open,
+        state_reason: None,
         assignees: vec![],
         labels: vec![],
+        milestone: None,
         created_at: chrono::Utc::now(),
         updated_at: chrono::Utc::now(),
         closed_at: None,
         html_url: "https://github.com/test/repo/issues/1".to_string(),
+        comments: 0,
+        is_pull_request: false,
     };
 
     let base = SyncedItem {
         org_heading_id: "test".to_string(),
         title: "Title".to_string(),
         body_hash: hash_body("Body"),
+        body: Some("Body".to_string()),
+        org_body_hash: None,
+        org_title_hash: None,
         state: "open".to_string(),
+        org_state: None,
         assignees: vec![],
         labels: vec![],
+        org_labels: None,
         gh_updated_at: chrono::Utc::now(),
         org_updated_at: None,
+        last_comment_id: None,
+        posted_notes: vec![],
+        conflicts: vec![],
+        parent: None,
+        scheduled: None,
+        deadline: None,
     };
 
-    let diff = three_way_diff(&org, &gh, &base);
+    let diff = three_way_diff(&org, &gh, &base, &SyncConfig::default());
 
     assert!(diff.has_changes());
     assert!(!diff.has_conflicts());
@@ -372,6 +683,7 @@ fn test_three_way_diff_org_changed() {
 
 #[test]
 fn test_three_way_diff_conflict() {
+    use org_gh::config::SyncConfig;
     use org_gh::github::model::{GhIssue, GhIssueState};
     use org_gh::org::model::{OrgItem, TodoState};
     use org_gh::sync::diff::{three_way_diff, FieldChange};
@@ -381,15 +693,25 @@ fn test_three_way_diff_conflict() {
         id: "test".to_string(),
         title: "Org Title".to_string(), // Changed
         body: "Body".to_string(),
-        state: TodoState::Todo,
+        state: TodoState::open("TODO"),
+        priority: None,
         gh_issue: Some(1),
         gh_url: None,
         assignees: vec![],
         labels: vec![],
+        tags: vec![],
+        inherited_tags: vec![],
         created: None,
         updated: None,
+        scheduled: None,
+        deadline: None,
         span: 0..10,
         properties_span: None,
+        notes: vec![],
+        has_conflict: false,
+        archived: false,
+        org_id: None,
+        parent: None,
     };
 
     let gh = GhIssue {
@@ -397,32 +719,133 @@ fn test_three_way_diff_conflict() {
         title: "GH Title".to_string(), // Also changed, different value
         body: Some("Body".to_string()),
         state: GhIssueState::Open,
+        state_reason: None,
         assignees: vec![],
         labels: vec![],
+        milestone: None,
         created_at: chrono::Utc::now(),
         updated_at: chrono::Utc::now(),
         closed_at: None,
         html_url: "https://github.com/test/repo/issues/1".to_string(),
+        comments: 0,
+        is_pull_request: false,
     };
 
     let base = SyncedItem {
         org_heading_id: "test".to_string(),
         title: "Original Title".to_string(), // Base value
         body_hash: hash_body("Body"),
+        body: Some("Body".to_string()),
+        org_body_hash: None,
+        org_title_hash: None,
         state: "open".to_string(),
+        org_state: None,
         assignees: vec![],
         labels: vec![],
+        org_labels: None,
         gh_updated_at: chrono::Utc::now(),
         org_updated_at: None,
+        last_comment_id: None,
+        posted_notes: vec![],
+        conflicts: vec![],
+        parent: None,
+        scheduled: None,
+        deadline: None,
     };
 
-    let diff = three_way_diff(&org, &gh, &base);
+    let diff = three_way_diff(&org, &gh, &base, &SyncConfig::default());
 
     assert!(diff.has_changes());
     assert!(diff.has_conflicts());
     assert_eq!(diff.title, FieldChange::Conflict);
 }
 
+#[test]
+fn test_three_way_diff_status_labels() {
+    use org_gh::config::SyncConfig;
+    use org_gh::github::model::{GhIssue, GhIssueState};
+    use org_gh::org::model::{OrgItem, TodoState};
+    use org_gh::sync::diff::{three_way_diff, FieldChange};
+    use org_gh::sync::state::{hash_body, SyncedItem};
+
+    let mut org = OrgItem {
+        id: "test".to_string(),
+        title: "Title".to_string(),
+        body: "Body".to_string(),
+        state: TodoState::open("DOING"),
+        priority: None,
+        gh_issue: Some(1),
+        gh_url: None,
+        assignees: vec![],
+        labels: vec!["bug".to_string()],
+        tags: vec![],
+        inherited_tags: vec![],
+        created: None,
+        updated: None,
+        scheduled: None,
+        deadline: None,
+        span: 0..10,
+        properties_span: None,
+        notes: vec![],
+        has_conflict: false,
+        archived: false,
+        org_id: None,
+        parent: None,
+    };
+
+    let mut gh = GhIssue {
+        number: 1,
+        title: "Title".to_string(),
+        body: Some("Body".to_string()),
+        state: GhIssueState::Open,
+        state_reason: None,
+        assignees: vec![],
+        labels: vec!["bug".to_string()],
+        milestone: None,
+        created_at: chrono::Utc::now(),
+        updated_at: chrono::Utc::now(),
+        closed_at: None,
+        html_url: "https://github.com/test/repo/issues/1".to_string(),
+        comments: 0,
+        is_pull_request: false,
+    };
+
+    let base = SyncedItem {
+        org_heading_id: "test".to_string(),
+        title: "Title".to_string(),
+        body_hash: hash_body("Body"),
+        body: Some("Body".to_string()),
+        org_body_hash: None,
+        org_title_hash: None,
+        state: "open".to_string(),
+        org_state: None,
+        assignees: vec![],
+        labels: vec!["bug".to_string()],
+        org_labels: None,
+        gh_updated_at: chrono::Utc::now(),
+        org_updated_at: None,
+        last_comment_id: None,
+        posted_notes: vec![],
+        conflicts: vec![],
+        parent: None,
+        scheduled: None,
+        deadline: None,
+    };
+    let config = SyncConfig::default();
+
+    // TODO -> DOING in org is a state change, not a label change
+    let diff = three_way_diff(&org, &gh, &base, &config);
+    assert_eq!(diff.state, FieldChange::OrgChanged);
+    assert_eq!(diff.labels, FieldChange::None);
+
+    // The blocked label added on GitHub is a state change too
+    org.state = TodoState::open("TODO");
+    gh.labels.push("blocked".to_string());
+    let diff = three_way_diff(&org, &gh, &base, &config);
+    assert_eq!(diff.state, FieldChange::GitHubChanged);
+    assert_eq!(diff.labels, FieldChange::None);
+}
+
 // ============================================================================
 // Writer Tests
 // ============================================================================
@@ -438,15 +861,25 @@ fn test_set_property_new_drawer() {
         id: "test".to_string(),
         title: "Test item".to_string(),
         body: "Some body text".to_string(),
-        state: TodoState::Todo,
+        state: TodoState::open("TODO"),
+        priority: None,
         gh_issue: None,
         gh_url: None,
         assignees: vec![],
         labels: vec![],
+        tags: vec![],
+        inherited_tags: vec![],
         created: None,
         updated: None,
+        scheduled: None,
+        deadline: None,
         span: 0..content.len(),
         properties_span: None, // No existing drawer
+        notes: vec![],
+        has_conflict: false,
+        archived: false,
+        org_id: None,
+        parent: None,
     };
 
     let result = set_property(content, &item, "GH_ISSUE", "42");
@@ -467,15 +900,25 @@ fn test_set_todo_state() {
         id: "test".to_string(),
         title: "Test item".to_string(),
         body: "Body".to_string(),
-        state: TodoState::Todo,
+        state: TodoState::open("TODO"),
+        priority: None,
         gh_issue: None,
         gh_url: None,
         assignees: vec![],
         labels: vec![],
+        tags: vec![],
+        inherited_tags: vec![],
         created: None,
         updated: None,
+        scheduled: None,
+        deadline: None,
         span: 0..content.len(),
         properties_span: None,
+        notes: vec![],
+        has_conflict: false,
+        archived: false,
+        org_id: None,
+        parent: None,
     };
 
     let result = set_todo_state(content, &item, "DONE");
@@ -484,6 +927,76 @@ fn test_set_todo_state() {
     assert!(!result.contains("* TODO"));
 }
 
+#[test]
+fn test_set_title_preserves_headline_parts() {
+    use org_gh::org::parser::parse_content;
+    use org_gh::org::writer::set_title;
+
+    let content = "* TODO [#A] Old title   :infra:\n** TODO Child\n";
+    let file = parse_content(std::path::Path::new("test.org"), content).unwrap();
+
+    let result = set_title(content, &file.items[0], "New title");
+
+    assert_eq!(result, "* TODO [#A] New title   :infra:\n** TODO Child\n");
+}
+
+#[test]
+fn test_set_body_preserves_drawers_and_children() {
+    use org_gh::org::parser::parse_content;
+    use org_gh::org::writer::set_body;
+
+    let content = r#"* TODO Parent
+:PROPERTIES:
+:GH_ISSUE: 1
+:END:
+:LOGBOOK:
+- Previous entry
+:END:
+Old body
+spanning lines
+
+** TODO Child
+Child body
+"#;
+    let file = parse_content(std::path::Path::new("test.org"), content).unwrap();
+
+    let result = set_body(content, &file.items[0], "New body from GitHub");
+
+    assert_eq!(
+        result,
+        r#"* TODO Parent
+:PROPERTIES:
+:GH_ISSUE: 1
+:END:
+:LOGBOOK:
+- Previous entry
+:END:
+New body from GitHub
+
+** TODO Child
+Child body
+"#
+    );
+
+    let reparsed = parse_content(std::path::Path::new("test.org"), &result).unwrap();
+    assert_eq!(reparsed.items[0].body, "New body from GitHub");
+    assert_eq!(reparsed.items[0].gh_issue, Some(1));
+    assert_eq!(reparsed.items[1].body, "Child body");
+}
+
+#[test]
+fn test_set_body_on_empty_section() {
+    use org_gh::org::parser::parse_content;
+    use org_gh::org::writer::set_body;
+
+    let content = "* TODO First\n* TODO Second\n";
+    let file = parse_content(std::path::Path::new("test.org"), content).unwrap();
+
+    let result = set_body(content, &file.items[0], "Pulled body");
+
+    assert_eq!(result, "* TODO First\nPulled body\n* TODO Second\n");
+}
+
 // ============================================================================
 // Config Tests
 // ============================================================================
@@ -547,15 +1060,25 @@ Some body text
         id: "test".to_string(),
         title: "Test item".to_string(),
         body: "Some body text".to_string(),
-        state: TodoState::Todo,
+        state: TodoState::open("TODO"),
+        priority: None,
         gh_issue: None,
         gh_url: None,
         assignees: vec![],
         labels: vec![],
+        tags: vec![],
+        inherited_tags: vec![],
         created: None,
         updated: None,
+        scheduled: None,
+        deadline: None,
         span: 0..content.len(),
         properties_span: Some(props_start..props_end),
+        notes: vec![],
+        has_conflict: false,
+        archived: false,
+        org_id: None,
+        parent: None,
     };
 
     let result = set_property(content, &item, "GH_ISSUE", "42");
@@ -582,15 +1105,25 @@ fn test_update_existing_property() {
         id: "test".to_string(),
         title: "Test item".to_string(),
         body: String::new(),
-        state: TodoState::Todo,
+        state: TodoState::open("TODO"),
+        priority: None,
         gh_issue: Some(1),
         gh_url: None,
         assignees: vec![],
         labels: vec![],
+        tags: vec![],
+        inherited_tags: vec![],
         created: None,
         updated: None,
+        scheduled: None,
+        deadline: None,
         span: 0..content.len(),
         properties_span: Some(props_start..props_end),
+        notes: vec![],
+        has_conflict: false,
+        archived: false,
+        org_id: None,
+        parent: None,
     };
 
     let result = set_property(content, &item, "GH_ISSUE", "99");
@@ -649,37 +1182,59 @@ fn test_sync_state_pending_creates() {
 fn test_todo_state_keywords() {
     use org_gh::org::model::TodoState;
 
-    assert_eq!(TodoState::Todo.to_keyword(), "TODO");
-    assert_eq!(TodoState::Doing.to_keyword(), "DOING");
-    assert_eq!(TodoState::Blocked.to_keyword(), "BLOCKED");
-    assert_eq!(TodoState::Waiting.to_keyword(), "WAITING");
-    assert_eq!(TodoState::Done.to_keyword(), "DONE");
-    assert_eq!(TodoState::Cancelled.to_keyword(), "CANCELLED");
+    let state = TodoState::open("NEXT");
+    assert_eq!(state.keyword(), "NEXT");
+    assert!(state.is_open());
+    assert!(!state.is_closed());
+
+    let state = TodoState::closed("SHIPPED");
+    assert_eq!(state.keyword(), "SHIPPED");
+    assert!(state.is_closed());
 }
 
 #[test]
-fn test_todo_state_from_keyword() {
-    use org_gh::org::model::TodoState;
-
-    assert_eq!(TodoState::from_keyword("TODO"), Some(TodoState::Todo));
-    assert_eq!(TodoState::from_keyword("todo"), Some(TodoState::Todo)); // case insensitive
-    assert_eq!(TodoState::from_keyword("DOING"), Some(TodoState::Doing));
-    assert_eq!(TodoState::from_keyword("BLOCKED"), Some(TodoState::Blocked));
-    assert_eq!(TodoState::from_keyword("WAITING"), Some(TodoState::Waiting));
-    assert_eq!(TodoState::from_keyword("DONE"), Some(TodoState::Done));
+fn test_todo_keywords_from_config() {
+    use org_gh::config::OrgConfig;
+    use org_gh::org::model::{TodoKeywords, TodoState};
+
+    let keywords = TodoKeywords::from_config(&OrgConfig::default());
+    assert_eq!(keywords.state("TODO"), Some(TodoState::open("TODO")));
+    assert_eq!(keywords.state("DOING"), Some(TodoState::open("DOING")));
+    assert_eq!(keywords.state("BLOCKED"), Some(TodoState::open("BLOCKED")));
+    assert_eq!(keywords.state("WAITING"), Some(TodoState::open("WAITING")));
+    assert_eq!(keywords.state("DONE"), Some(TodoState::closed("DONE")));
     assert_eq!(
-        TodoState::from_keyword("CANCELLED"),
-        Some(TodoState::Cancelled)
+        keywords.state("CANCELLED"),
+        Some(TodoState::cancelled("CANCELLED"))
     );
     assert_eq!(
-        TodoState::from_keyword("CANCELED"),
-        Some(TodoState::Cancelled)
-    ); // alt spelling
-    assert_eq!(
-        TodoState::from_keyword("WONTFIX"),
-        Some(TodoState::Cancelled)
+        keywords.state("WONTFIX"),
+        Some(TodoState::cancelled("WONTFIX"))
     );
-    assert_eq!(TodoState::from_keyword("INVALID"), None);
+    assert_eq!(keywords.state("todo"), Some(TodoState::open("TODO")));
+    assert_eq!(keywords.state("Done"), Some(TodoState::closed("DONE")));
+    assert_eq!(keywords.state("INVALID"), None);
+    assert_eq!(keywords.default_open(), TodoState::open("TODO"));
+    assert_eq!(keywords.default_closed(), TodoState::closed("DONE"));
+}
+
+#[test]
+fn test_set_todo_state_round_trips_custom_keywords() {
+    use org_gh::org::parser::parse_content;
+    use org_gh::org::writer::set_todo_state;
+
+    let content = "#+TODO: NEXT REVIEW | SHIPPED\n\n* REVIEW Ship the thing :release:\nBody\n";
+    let file = parse_content(std::path::Path::new("test.org"), content).unwrap();
+    let item = &file.items[0];
+    assert_eq!(item.state.keyword(), "REVIEW");
+
+    let closed = file.todo_keywords.default_closed();
+    let result = set_todo_state(content, item, closed.keyword());
+    assert!(result.contains("* SHIPPED Ship the thing :release:"));
+
+    let reparsed = parse_content(std::path::Path::new("test.org"), &result).unwrap();
+    assert_eq!(reparsed.items[0].state, closed);
+    assert_eq!(reparsed.items[0].title, "Ship the thing");
 }
 
 #[test]
@@ -805,6 +1360,47 @@ async fn test_update_issue_mock() {
     assert!(!issue.state.is_open());
 }
 
+#[tokio::test]
+async fn test_update_issue_sends_state_reason() {
+    use common::load_fixture_json;
+    use org_gh::github::model::{GhIssueState, GhStateReason, UpdateIssueRequest};
+    use wiremock::matchers::{body_partial_json, method, path};
+    use wiremock::{Mock, MockServer, ResponseTemplate};
+
+    let server = MockServer::start().await;
+
+    let mut fixture = load_fixture_json("issue_3.json");
+    fixture["state_reason"] = serde_json::json!("not_planned");
+
+    Mock::given(method("PATCH"))
+        .and(path(format!("/repos/{}/issues/3", TEST_REPO)))
+        .and(body_partial_json(serde_json::json!({
+            "state": "closed",
+            "state_reason": "not_planned"
+        })))
+        .respond_with(ResponseTemplate::new(200).set_body_json(fixture))
+        .expect(1)
+        .mount(&server)
+        .await;
+
+    let client = GitHubClient::with_base_url("fake-token", TEST_REPO, Some(&server.uri()))
+        .await
+        .expect("Failed to create client");
+
+    let req = UpdateIssueRequest {
+        state: Some(GhIssueState::Closed),
+        state_reason: Some(GhStateReason::NotPlanned),
+        ..Default::default()
+    };
+
+    let issue = client
+        .update_issue(3, req)
+        .await
+        .expect("Failed to update issue");
+
+    assert_eq!(issue.state_reason, Some(GhStateReason::NotPlanned));
+}
+
 #[tokio::test]
 async fn test_close_issue_mock() {
     use wiremock::matchers::{method, path};
@@ -873,6 +1469,11 @@ fn test_write_file() {
     let org_file = OrgFile {
         path: file_path.clone(),
         repo: Some("owner/repo".to_string()),
+        label_prefix: None,
+        default_labels: vec![],
+        import: None,
+        archive: None,
+        todo_keywords: Default::default(),
         content: "#+TITLE: Test\n* TODO Item\n".to_string(),
         items: vec![],
     };
@@ -902,15 +1503,25 @@ Some body text
         id: "test".to_string(),
         title: "Test item".to_string(),
         body: "Some body text".to_string(),
-        state: TodoState::Todo,
+        state: TodoState::open("TODO"),
+        priority: None,
         gh_issue: Some(1),
         gh_url: None,
         assignees: vec![],
         labels: vec![],
+        tags: vec![],
+        inherited_tags: vec![],
         created: None,
         updated: None,
+        scheduled: None,
+        deadline: None,
         span: 0..content.len(),
         properties_span: Some(props_start..props_end),
+        notes: vec![],
+        has_conflict: false,
+        archived: false,
+        org_id: None,
+        parent: None,
     };
 
     let result = append_to_logbook(content, &item, "- State changed to DONE [2026-01-09]");
@@ -942,15 +1553,25 @@ Some body text
         id: "test".to_string(),
         title: "Test item".to_string(),
         body: "Some body text".to_string(),
-        state: TodoState::Todo,
+        state: TodoState::open("TODO"),
+        priority: None,
         gh_issue: Some(1),
         gh_url: None,
         assignees: vec![],
         labels: vec![],
+        tags: vec![],
+        inherited_tags: vec![],
         created: None,
         updated: None,
+        scheduled: None,
+        deadline: None,
         span: 0..content.len(),
         properties_span: Some(props_start..props_end),
+        notes: vec![],
+        has_conflict: false,
+        archived: false,
+        org_id: None,
+        parent: None,
     };
 
     let result = append_to_logbook(content, &item, "- New entry");
@@ -1002,30 +1623,2360 @@ fn test_sync_action_debug() {
 }
 
 #[test]
-fn test_org_changes_default() {
-    use org_gh::sync::engine::OrgChanges;
-
-    let changes = OrgChanges {
-        state: None,
-        assignees: None,
-        labels: None,
-        comments: vec![],
-    };
-
-    assert!(changes.state.is_none());
-    assert!(changes.assignees.is_none());
-    assert!(changes.labels.is_none());
-    assert!(changes.comments.is_empty());
-}
-
-// ============================================================================
-// CLI Init Command Tests
-// ============================================================================
+fn test_sync_action_only() {
+    use org_gh::github::model::{GhIssue, GhIssueState};
+    use org_gh::org::model::{OrgItem, TodoState};
+    use org_gh::sync::diff::Field;
+    use org_gh::sync::engine::{Direction, SyncAction};
 
-#[tokio::test]
-async fn test_init_validates_repo_format() {
-    use org_gh::cli::init::Args;
-    use std::path::PathBuf;
+    let org_item = OrgItem {
+        id: "test".to_string(),
+        title: "Title".to_string(),
+        body: String::new(),
+        state: TodoState::open("TODO"),
+        priority: None,
+        gh_issue: Some(1),
+        gh_url: None,
+        assignees: vec![],
+        labels: vec![],
+        tags: vec![],
+        inherited_tags: vec![],
+        created: None,
+        updated: None,
+        scheduled: None,
+        deadline: None,
+        span: 0..10,
+        properties_span: None,
+        notes: vec![],
+        has_conflict: false,
+        archived: false,
+        org_id: None,
+        parent: None,
+    };
+    let gh_issue = GhIssue {
+        number: 1,
+        title: "Title".to_string(),
+        body: None,
+        state: GhIssueState::Open,
+        state_reason: None,
+        assignees: vec![],
+        labels: vec![],
+        milestone: None,
+        created_at: chrono::Utc::now(),
+        updated_at: chrono::Utc::now(),
+        closed_at: None,
+        html_url: "https://github.com/test/repo/issues/1".to_string(),
+        comments: 0,
+        is_pull_request: false,
+    };
+    let update = |fields: Vec<(Field, Direction)>| SyncAction::Update {
+        issue_number: 1,
+        org_item: org_item.clone(),
+        gh_issue: gh_issue.clone(),
+        fields,
+    };
+
+    let pushed = update(vec![
+        (Field::Title, Direction::Push),
+        (Field::Assignees, Direction::Pull),
+        (Field::Labels, Direction::Merge),
+    ])
+    .only(Direction::Push);
+    match pushed {
+        Some(SyncAction::Update { fields, .. }) => {
+            assert_eq!(fields, vec![(Field::Title, Direction::Push)]);
+        }
+        other => panic!("expected update, got {:?}", other),
+    }
+
+    let pulled = update(vec![(Field::Title, Direction::Push)]).only(Direction::Pull);
+    assert!(matches!(pulled, Some(SyncAction::NoOp { issue_number: 1 })));
+
+    let comments = SyncAction::PullComments {
+        issue_number: 1,
+        org_item: org_item.clone(),
+        entries: vec![],
+        newest_comment: 7,
+    };
+    assert!(comments.only(Direction::Push).is_none());
+
+    let create = SyncAction::CreateIssue { org_item };
+    assert!(create.only(Direction::Pull).is_none());
+}
+
+/// Parse `content` from a temp file and build an engine against `server`
+async fn engine_for(
+    server: &wiremock::MockServer,
+    content: &str,
+    state: SyncState,
+    config: org_gh::config::Config,
+) -> (TempDir, org_gh::org::OrgFile, org_gh::sync::SyncEngine) {
+    let dir = TempDir::new().unwrap();
+    let file_path = dir.path().join("test.org");
+    fs::write(&file_path, content).unwrap();
+    let org_file = parse_file(&file_path).unwrap();
+
+    let client = GitHubClient::with_base_url("fake-token", TEST_REPO, Some(&server.uri()))
+        .await
+        .expect("Failed to create client");
+    let engine = org_gh::sync::SyncEngine::with_client(config, client, state, &org_file);
+    (dir, org_file, engine)
+}
+
+#[tokio::test]
+async fn test_engine_plan_without_base_state() {
+    use org_gh::sync::engine::SyncAction;
+
+    let server = setup_mock_github().await;
+    common::mount_empty_comments(&server).await;
+
+    let content = sample_org_content();
+    let (_dir, org_file, mut engine) = engine_for(
+        &server,
+        &content,
+        SyncState::new(TEST_REPO),
+        org_gh::config::Config::default(),
+    )
+    .await;
+
+    let actions = engine.plan(&org_file, true).await.unwrap();
+
+    // Linked items agree with GitHub, so only the base state is recorded
+    for num in 1..=3 {
+        assert!(
+            actions.iter().any(|a| matches!(
+                a,
+                SyncAction::Update { issue_number, fields, .. }
+                    if *issue_number == num && fields.is_empty()
+            )),
+            "Expected baseline update for #{}",
+            num
+        );
+    }
+    assert!(actions.iter().any(|a| matches!(
+        a,
+        SyncAction::CreateIssue { org_item } if org_item.title == "New item without issue"
+    )));
+}
+
+#[tokio::test]
+async fn test_engine_first_sync_settles_differences_as_conflicts() {
+    use org_gh::sync::diff::Field;
+    use org_gh::sync::engine::{Direction, SyncAction};
+
+    let server = setup_mock_github().await;
+    common::mount_empty_comments(&server).await;
+
+    // Renamed in org before the issue was ever synced
+    let content = format!(
+        "#+GH_REPO: {}\n\n* TODO Renamed in org\n:PROPERTIES:\n:GH_ISSUE: 1\n:END:\nSimple body text\n",
+        TEST_REPO
+    );
+    let (_dir, org_file, mut engine) = engine_for(
+        &server,
+        &content,
+        SyncState::new(TEST_REPO),
+        org_gh::config::Config::default(),
+    )
+    .await;
+
+    // The title conflict goes the configured way (org wins by default)
+    let actions = engine.plan(&org_file, true).await.unwrap();
+    assert!(actions.iter().any(|a| matches!(
+        a,
+        SyncAction::Update { issue_number: 1, fields, .. }
+            if fields == &vec![(Field::Title, Direction::Push)]
+    )));
+
+    // Or is left to the user
+    let mut config = org_gh::config::Config::default();
+    config.sync.title_conflict = org_gh::config::ConflictResolution::Prompt;
+    let (_dir, org_file, mut engine) =
+        engine_for(&server, &content, SyncState::new(TEST_REPO), config).await;
+    let actions = engine.plan(&org_file, true).await.unwrap();
+    assert!(actions.iter().any(|a| matches!(
+        a,
+        SyncAction::Conflict { issue_number: 1, fields, .. } if fields == &vec![Field::Title]
+    )));
+}
+
+#[tokio::test]
+async fn test_engine_plan_three_way() {
+    use org_gh::sync::diff::Field;
+    use org_gh::sync::engine::{Direction, SyncAction};
+
+    let server = setup_mock_github().await;
+    common::mount_empty_comments(&server).await;
+
+    let now = chrono::Utc::now();
+    let mut state = SyncState::new(TEST_REPO);
+    // #1: org renamed the heading since the last sync
+    state.record_sync(
+        1,
+        "a",
+        "Test issue open simple",
+        "Simple body text",
+        "open",
+        &[],
+        &[],
+        now,
+    );
+    // #2: GitHub gained the assignee since the last sync
+    state.record_sync(
+        2,
+        "b",
+        "Test issue with labels",
+        "Has labels and assignee",
+        "open",
+        &[],
+        &["bug".to_string(), "enhancement".to_string()],
+        now,
+    );
+    // #3: both sides renamed it
+    state.record_sync(
+        3,
+        "c",
+        "Old title",
+        "This one is done",
+        "closed",
+        &[],
+        &["documentation".to_string()],
+        now,
+    );
+
+    let content = format!(
+        r#"#+GH_REPO: {}
+
+* TODO Renamed in org
+:PROPERTIES:
+:GH_ISSUE: 1
+:END:
+Simple body text
+
+* TODO Test issue with labels
+:PROPERTIES:
+:GH_ISSUE: 2
+:LABELS: bug,enhancement
+:END:
+Has labels and assignee
+
+* DONE Org title
+:PROPERTIES:
+:GH_ISSUE: 3
+:LABELS: documentation
+:END:
+This one is done
+"#,
+        TEST_REPO
+    );
+    // Leave title conflicts to the user instead of the org-wins default
+    let mut config = org_gh::config::Config::default();
+    config.sync.title_conflict = org_gh::config::ConflictResolution::Prompt;
+    let (_dir, org_file, mut engine) = engine_for(&server, &content, state, config).await;
+
+    let actions = engine.plan(&org_file, true).await.unwrap();
+    let fields_for = |num: u64| {
+        actions.iter().find_map(|a| match a {
+            SyncAction::Update {
+                issue_number,
+                fields,
+                ..
+            } if *issue_number == num => Some(fields.clone()),
+            _ => None,
+        })
+    };
+
+    assert_eq!(fields_for(1), Some(vec![(Field::Title, Direction::Push)]));
+    assert_eq!(
+        fields_for(2),
+        Some(vec![(Field::Assignees, Direction::Pull)])
+    );
+    assert!(actions.iter().any(|a| matches!(
+        a,
+        SyncAction::Conflict { issue_number: 3, fields, .. } if fields == &vec![Field::Title]
+    )));
+
+    // With force, the conflict resolves in the forced direction
+    engine.force = Some(Direction::Push);
+    let actions = engine.plan(&org_file, true).await.unwrap();
+    assert!(actions.iter().any(|a| matches!(
+        a,
+        SyncAction::Update { issue_number: 3, fields, .. }
+            if fields == &vec![(Field::Title, Direction::Push)]
+    )));
+}
+
+#[tokio::test]
+async fn test_engine_conflict_strategies() {
+    use org_gh::config::{Config, ConflictResolution};
+    use org_gh::sync::diff::Field;
+    use org_gh::sync::engine::{Direction, SyncAction};
+
+    let server = setup_mock_github().await;
+    common::mount_empty_comments(&server).await;
+
+    let base = || {
+        let mut state = SyncState::new(TEST_REPO);
+        state.record_sync(
+            3,
+            "c",
+            "Old title",
+            "This one is done",
+            "open",
+            &[],
+            &["documentation".to_string()],
+            chrono::Utc::now(),
+        );
+        state
+    };
+
+    // Both sides renamed and closed #3, org as not planned
+    let content = format!(
+        r#"#+GH_REPO: {}
+
+* CANCELLED Org title
+:PROPERTIES:
+:GH_ISSUE: 3
+:LABELS: documentation
+:END:
+This one is done
+"#,
+        TEST_REPO
+    );
+
+    // Defaults: title conflicts go to org, state conflicts need a prompt
+    let (_dir, org_file, mut engine) =
+        engine_for(&server, &content, base(), Config::default()).await;
+    let mut actions = engine.plan(&org_file, true).await.unwrap();
+    let index = actions
+        .iter()
+        .position(|a| matches!(a, SyncAction::Conflict { .. }))
+        .expect("Expected a conflict");
+    match &actions[index] {
+        SyncAction::Conflict {
+            fields, resolved, ..
+        } => {
+            assert_eq!(fields, &vec![Field::State]);
+            assert_eq!(resolved, &vec![(Field::Title, Direction::Push)]);
+        }
+        _ => unreachable!(),
+    }
+
+    // Answering the prompt turns the conflict into an update
+    let conflict = actions.remove(index);
+    match conflict.resolve(vec![(Field::State, Direction::Edit("DONE".to_string()))]) {
+        SyncAction::Update { fields, .. } => assert_eq!(
+            fields,
+            vec![
+                (Field::Title, Direction::Push),
+                (Field::State, Direction::Edit("DONE".to_string())),
+            ]
+        ),
+        other => panic!("expected update, got {:?}", other),
+    }
+
+    // A configured strategy resolves without asking
+    let mut config = Config::default();
+    config.sync.state_conflict = ConflictResolution::GitHubWins;
+    let (_dir, org_file, mut engine) = engine_for(&server, &content, base(), config).await;
+    let actions = engine.plan(&org_file, true).await.unwrap();
+    assert!(actions.iter().any(|a| matches!(
+        a,
+        SyncAction::Update { issue_number: 3, fields, .. }
+            if fields == &vec![(Field::Title, Direction::Push), (Field::State, Direction::Pull)]
+    )));
+}
+
+#[tokio::test]
+async fn test_engine_conflict_markers() {
+    use org_gh::config::{Config, ConflictResolution};
+    use org_gh::org::parser::parse_content;
+    use org_gh::sync::diff::Field;
+    use org_gh::sync::engine::{Direction, SyncAction};
+
+    let server = setup_mock_github().await;
+    common::mount_empty_comments(&server).await;
+
+    let mut state = SyncState::new(TEST_REPO);
+    state.record_sync(
+        1,
+        "a",
+        "Old title",
+        "Simple body text",
+        "open",
+        &[],
+        &[],
+        chrono::Utc::now(),
+    );
+    let content = format!(
+        r#"#+GH_REPO: {}
+
+* TODO Renamed in org
+:PROPERTIES:
+:GH_ISSUE: 1
+:END:
+Simple body text
+"#,
+        TEST_REPO
+    );
+
+    let mut config = Config::default();
+    config.sync.title_conflict = ConflictResolution::Prompt;
+    config.sync.conflict_markers = true;
+    let (_dir, mut org_file, mut engine) =
+        engine_for(&server, &content, state, config.clone()).await;
+
+    // The unresolved conflict is written under the heading
+    let actions = engine.plan(&org_file, true).await.unwrap();
+    let report = engine.execute(actions, &mut org_file).await.unwrap();
+    assert_eq!(report.conflicts.len(), 1);
+    assert!(org_file.content.contains(
+        ":END:\n:GH_CONFLICT:\n- title\n  - org: Renamed in org\n  - github: Test issue open simple\n  - base: Old title\n:END:\nSimple body text\n"
+    ));
+    assert_eq!(engine.state().items[&1].conflicts, vec![Field::Title]);
+
+    // While the drawer is there the conflict stays
+    let marked = parse_content(&org_file.path, &org_file.content).unwrap();
+    assert!(marked.items[0].has_conflict);
+    let (_dir, org_file, mut engine) = engine_for(
+        &server,
+        &marked.content,
+        engine.state().clone(),
+        config.clone(),
+    )
+    .await;
+    let actions = engine.plan(&org_file, true).await.unwrap();
+    assert!(actions.iter().any(|a| matches!(
+        a,
+        SyncAction::Conflict {
+            issue_number: 1,
+            ..
+        }
+    )));
+
+    // Deleting it keeps the org value
+    let (_dir, org_file, mut engine) =
+        engine_for(&server, &content, engine.state().clone(), config).await;
+    let actions = engine.plan(&org_file, true).await.unwrap();
+    assert!(actions.iter().any(|a| matches!(
+        a,
+        SyncAction::Update { issue_number: 1, fields, .. }
+            if fields == &vec![(Field::Title, Direction::Push)]
+    )));
+}
+
+#[tokio::test]
+async fn test_engine_execute_pull_writes_assignee_property() {
+    use org_gh::sync::engine::Direction;
+
+    let server = setup_mock_github().await;
+    common::mount_empty_comments(&server).await;
+
+    let mut state = SyncState::new(TEST_REPO);
+    state.record_sync(
+        2,
+        "b",
+        "Test issue with labels",
+        "Has labels and assignee",
+        "open",
+        &[],
+        &["bug".to_string(), "enhancement".to_string()],
+        chrono::Utc::now(),
+    );
+
+    let content = format!(
+        r#"#+GH_REPO: {}
+
+* TODO Test issue with labels
+:PROPERTIES:
+:GH_ISSUE: 2
+:LABELS: bug,enhancement
+:END:
+Has labels and assignee
+"#,
+        TEST_REPO
+    );
+    let (_dir, mut org_file, mut engine) =
+        engine_for(&server, &content, state, org_gh::config::Config::default()).await;
+
+    let actions: Vec<_> = engine
+        .plan(&org_file, true)
+        .await
+        .unwrap()
+        .into_iter()
+        .filter_map(|a| a.only(Direction::Pull))
+        .collect();
+    let report = engine.execute(actions, &mut org_file).await.unwrap();
+    engine.save(&org_file, true).unwrap();
+
+    assert_eq!(report.pulled.len(), 1);
+    assert_eq!(report.pulled[0].changes, vec!["assignees"]);
+    assert!(org_file.content.contains(":ASSIGNEE: tftio"));
+
+    let reparsed = parse_file(&org_file.path).unwrap();
+    assert_eq!(reparsed.items[0].assignees, vec!["tftio"]);
+    assert_eq!(engine.state().items[&2].assignees, vec!["tftio"]);
+    assert!(engine.state().last_sync.is_some());
+}
+
+#[tokio::test]
+async fn test_engine_execute_push_updates_issue() {
+    use common::load_fixture_json;
+    use org_gh::sync::engine::Direction;
+    use wiremock::matchers::{body_partial_json, method, path};
+    use wiremock::{Mock, ResponseTemplate};
+
+    let server = setup_mock_github().await;
+    common::mount_empty_comments(&server).await;
+
+    let mut fixture = load_fixture_json("issue_1.json");
+    fixture["title"] = serde_json::json!("Renamed in org");
+    Mock::given(method("PATCH"))
+        .and(path(format!("/repos/{}/issues/1", TEST_REPO)))
+        .and(body_partial_json(
+            serde_json::json!({"title": "Renamed in org"}),
+        ))
+        .respond_with(ResponseTemplate::new(200).set_body_json(fixture))
+        .expect(1)
+        .mount(&server)
+        .await;
+
+    let mut state = SyncState::new(TEST_REPO);
+    state.record_sync(
+        1,
+        "a",
+        "Test issue open simple",
+        "Simple body text",
+        "open",
+        &[],
+        &[],
+        chrono::Utc::now(),
+    );
+
+    let content = format!(
+        r#"#+GH_REPO: {}
+
+* TODO Renamed in org
+:PROPERTIES:
+:GH_ISSUE: 1
+:END:
+Simple body text
+"#,
+        TEST_REPO
+    );
+    let (_dir, mut org_file, mut engine) =
+        engine_for(&server, &content, state, org_gh::config::Config::default()).await;
+
+    let actions: Vec<_> = engine
+        .plan(&org_file, true)
+        .await
+        .unwrap()
+        .into_iter()
+        .filter_map(|a| a.only(Direction::Push))
+        .collect();
+    let report = engine.execute(actions, &mut org_file).await.unwrap();
+
+    assert_eq!(report.pushed.len(), 1);
+    assert_eq!(report.pushed[0].action, "updated");
+    assert_eq!(engine.state().items[&1].title, "Renamed in org");
+}
+
+#[tokio::test]
+async fn test_engine_posts_notes_and_pulls_comments_once() {
+    use common::load_fixture_json;
+    use wiremock::matchers::{body_partial_json, method, path};
+    use wiremock::{Mock, ResponseTemplate};
+
+    let server = setup_mock_github().await;
+    common::mount_empty_comments(&server).await;
+
+    // Each note is posted exactly once across both syncs
+    for (id, body) in [
+        (3729900001u64, "Talked to ops."),
+        (3729900002, "Same minute."),
+        (3729900003, "Newest, added on top."),
+    ] {
+        let mut comment = load_fixture_json("issue_5_comments.json")[0].clone();
+        comment["id"] = serde_json::json!(id);
+        comment["body"] = serde_json::json!(body);
+        Mock::given(method("POST"))
+            .and(path(format!("/repos/{}/issues/5/comments", TEST_REPO)))
+            .and(body_partial_json(serde_json::json!({ "body": body })))
+            .respond_with(ResponseTemplate::new(201).set_body_json(&comment))
+            .expect(1)
+            .mount(&server)
+            .await;
+    }
+
+    let mut state = SyncState::new(TEST_REPO);
+    state.record_sync(
+        5,
+        "test-issue-with-comments",
+        "Test issue with comments",
+        "Main body",
+        "open",
+        &[],
+        &[],
+        chrono::Utc::now(),
+    );
+    let content = format!(
+        r#"#+GH_REPO: {}
+
+* TODO Test issue with comments
+:PROPERTIES:
+:GH_ISSUE: 5
+:END:
+:LOGBOOK:
+- Note taken on [2026-01-10 Sat 09:15] \\
+  Same minute.
+- Note taken on [2026-01-10 Sat 09:15] \\
+  Talked to ops.
+:END:
+Main body
+"#,
+        TEST_REPO
+    );
+    let (dir, mut org_file, mut engine) =
+        engine_for(&server, &content, state, org_gh::config::Config::default()).await;
+
+    let actions = engine.plan(&org_file, true).await.unwrap();
+    let report = engine.execute(actions, &mut org_file).await.unwrap();
+    engine.save(&org_file, true).unwrap();
+    assert_eq!((report.notes_posted, report.comments_added), (2, 2));
+
+    let written = fs::read_to_string(dir.path().join("test.org")).unwrap();
+    assert!(
+        written.contains("  First comment on the issue\n"),
+        "{}",
+        written
+    );
+    assert!(
+        written.contains("  Second comment for testing\n"),
+        "{}",
+        written
+    );
+
+    // org-add-note puts a newer note from the same minute first
+    let written = written.replacen(
+        ":LOGBOOK:\n",
+        ":LOGBOOK:\n- Note taken on [2026-01-10 Sat 09:15] \\\\\n  Newest, added on top.\n",
+        1,
+    );
+    fs::write(dir.path().join("test.org"), &written).unwrap();
+    let mut org_file = parse_file(&dir.path().join("test.org")).unwrap();
+
+    let actions = engine.plan(&org_file, true).await.unwrap();
+    let report = engine.execute(actions, &mut org_file).await.unwrap();
+    engine.save(&org_file, true).unwrap();
+    assert_eq!((report.notes_posted, report.comments_added), (1, 0));
+
+    // Editing a posted note updates its comment instead of posting again
+    let mut comment = load_fixture_json("issue_5_comments.json")[0].clone();
+    comment["id"] = serde_json::json!(3729900001u64);
+    comment["body"] = serde_json::json!("Talked to ops twice.");
+    Mock::given(method("PATCH"))
+        .and(path(format!(
+            "/repos/{}/issues/comments/3729900001",
+            TEST_REPO
+        )))
+        .and(body_partial_json(
+            serde_json::json!({ "body": "Talked to ops twice." }),
+        ))
+        .respond_with(ResponseTemplate::new(200).set_body_json(&comment))
+        .expect(1)
+        .mount(&server)
+        .await;
+    let written = fs::read_to_string(dir.path().join("test.org"))
+        .unwrap()
+        .replace("Talked to ops.", "Talked to ops twice.");
+    fs::write(dir.path().join("test.org"), &written).unwrap();
+    let mut org_file = parse_file(&dir.path().join("test.org")).unwrap();
+
+    let actions = engine.plan(&org_file, true).await.unwrap();
+    let report = engine.execute(actions, &mut org_file).await.unwrap();
+    assert_eq!((report.notes_posted, report.comments_added), (1, 0));
+    let posted = &engine.state().items[&5].posted_notes;
+    assert_eq!(posted.len(), 3);
+    assert!(!engine.state().items[&5]
+        .unposted_notes(&org_file.items[0].notes)
+        .iter()
+        .any(|n| n.body.contains("ops")));
+}
+
+#[tokio::test]
+async fn test_engine_matches_new_headings_by_title() {
+    use common::load_fixture_json;
+    use wiremock::matchers::{method, path};
+    use wiremock::{Mock, ResponseTemplate};
+
+    let server = setup_mock_github().await;
+    common::mount_empty_comments(&server).await;
+
+    let mut created = load_fixture_json("issue_3.json");
+    created["number"] = serde_json::json!(9);
+    created["title"] = serde_json::json!("Brand new");
+    Mock::given(method("POST"))
+        .and(path(format!("/repos/{}/issues", TEST_REPO)))
+        .respond_with(ResponseTemplate::new(201).set_body_json(&created))
+        .expect(2)
+        .mount(&server)
+        .await;
+
+    let mut state = SyncState::new(TEST_REPO);
+    state.mark_synced(chrono::Utc::now());
+    let content = format!(
+        "#+GH_REPO: {}\n\n* TODO Brand new\n* TODO Test issue open simple\n* TODO Another\n* TODO Brand new\n",
+        TEST_REPO
+    );
+    let (_dir, mut org_file, mut engine) =
+        engine_for(&server, &content, state, org_gh::config::Config::default()).await;
+
+    let actions = engine.plan(&org_file, false).await.unwrap();
+    let report = engine.execute(actions, &mut org_file).await.unwrap();
+    let actions: Vec<(u64, &str)> = report
+        .pushed
+        .iter()
+        .map(|p| (p.issue_number, p.action.as_str()))
+        .collect();
+    // Issues created in this run match later headings too
+    assert_eq!(
+        actions,
+        vec![
+            (9, "created"),
+            (1, "matched"),
+            (9, "created"),
+            (9, "matched")
+        ]
+    );
+
+    // One full listing for all four headings
+    let full_listings = server
+        .received_requests()
+        .await
+        .unwrap()
+        .iter()
+        .filter(|r| {
+            r.method.as_str() == "GET"
+                && r.url.path() == format!("/repos/{}/issues", TEST_REPO)
+                && r.url
+                    .query_pairs()
+                    .any(|(k, v)| k == "state" && v == "open")
+                && !r.url.query_pairs().any(|(k, _)| k == "since")
+        })
+        .count();
+    assert_eq!(full_listings, 1);
+}
+
+#[tokio::test]
+async fn test_engine_push_plan_skips_pull_fetches() {
+    use org_gh::sync::engine::{Direction, SyncAction};
+
+    let server = setup_mock_github().await;
+    common::mount_empty_comments(&server).await;
+
+    let content = format!(
+        "#+GH_REPO: {}\n#+GH_IMPORT: label:bug\n\n* TODO Test issue with comments\n:PROPERTIES:\n:GH_ISSUE: 5\n:END:\nMain body\n",
+        TEST_REPO
+    );
+    let (_dir, org_file, mut engine) = engine_for(
+        &server,
+        &content,
+        SyncState::new(TEST_REPO),
+        org_gh::config::Config::default(),
+    )
+    .await;
+    engine.direction = Some(Direction::Push);
+
+    let actions = engine.plan(&org_file, true).await.unwrap();
+    assert!(!actions.iter().any(|a| matches!(
+        a,
+        SyncAction::PullComments { .. } | SyncAction::ImportIssue { .. }
+    )));
+
+    // Neither the comments nor the import search were fetched
+    let requests = server.received_requests().await.unwrap();
+    assert!(
+        !requests.iter().any(|r| {
+            let path = r.url.path();
+            path.ends_with("/comments") || path.starts_with("/search")
+        }),
+        "{:?}",
+        requests
+            .iter()
+            .map(|r| r.url.to_string())
+            .collect::<Vec<_>>()
+    );
+}
+
+#[tokio::test]
+async fn test_engine_lists_linked_pull_requests() {
+    use common::load_fixture_json;
+    use wiremock::matchers::{method, path, query_param};
+    use wiremock::{Mock, MockServer, ResponseTemplate};
+
+    let server = MockServer::start().await;
+    common::mount_empty_comments(&server).await;
+
+    let pull_request = |number: u64, body: &str| {
+        let mut pr = load_fixture_json("issue_1.json");
+        pr["number"] = number.into();
+        pr["title"] = format!("PR {}", number).into();
+        pr["body"] = body.into();
+        pr["pull_request"] = serde_json::json!({
+            "url": format!("https://api.github.com/repos/{}/pulls/{}", TEST_REPO, number),
+            "html_url": format!("https://github.com/{}/pull/{}", TEST_REPO, number),
+            "diff_url": format!("https://github.com/{}/pull/{}.diff", TEST_REPO, number),
+            "patch_url": format!("https://github.com/{}/pull/{}.patch", TEST_REPO, number),
+        });
+        pr
+    };
+    // #8 closes an issue the org file does not link
+    let open = serde_json::json!([
+        pull_request(7, "Fixes #1"),
+        pull_request(8, "Closes #2"),
+        load_fixture_json("issue_1.json")
+    ]);
+    Mock::given(method("GET"))
+        .and(path(format!("/repos/{}/issues", TEST_REPO)))
+        .and(query_param("state", "open"))
+        .respond_with(ResponseTemplate::new(200).set_body_json(open))
+        .mount(&server)
+        .await;
+    Mock::given(method("GET"))
+        .and(path(format!("/repos/{}/issues", TEST_REPO)))
+        .and(query_param("state", "closed"))
+        .respond_with(ResponseTemplate::new(200).set_body_string("[]"))
+        .mount(&server)
+        .await;
+
+    let content = format!(
+        "#+GH_REPO: {}\n\n* TODO Test issue open simple\n:PROPERTIES:\n:GH_ISSUE: 1\n:END:\n",
+        TEST_REPO
+    );
+    let (_dir, org_file, mut engine) = engine_for(
+        &server,
+        &content,
+        SyncState::new(TEST_REPO),
+        org_gh::config::Config::default(),
+    )
+    .await;
+
+    engine.plan(&org_file, true).await.unwrap();
+    let linked: Vec<(u64, u64)> = engine
+        .linked_pull_requests(&org_file)
+        .into_iter()
+        .map(|(issue, pr)| (issue, pr.number))
+        .collect();
+    assert_eq!(linked, vec![(1, 7)]);
+}
+
+#[tokio::test]
+async fn test_engine_pulled_status_without_keyword_stays_in_sync() {
+    use common::load_fixture_json;
+    use org_gh::sync::engine::SyncAction;
+    use wiremock::matchers::{method, path, query_param};
+    use wiremock::{Mock, MockServer, ResponseTemplate};
+
+    // The file has no DOING keyword, so the status lands on TODO
+    let mut label = load_fixture_json("issue_2.json")["labels"][0].clone();
+    label["name"] = serde_json::json!("in-progress");
+    let mut issue = load_fixture_json("issue_1.json");
+    issue["labels"] = serde_json::json!([label]);
+    let server = MockServer::start().await;
+    for (state, issues) in [
+        ("open", serde_json::json!([issue])),
+        ("closed", serde_json::json!([])),
+    ] {
+        Mock::given(method("GET"))
+            .and(path(format!("/repos/{}/issues", TEST_REPO)))
+            .and(query_param("state", state))
+            .respond_with(ResponseTemplate::new(200).set_body_json(issues))
+            .mount(&server)
+            .await;
+    }
+    common::mount_empty_comments(&server).await;
+
+    let mut state = SyncState::new(TEST_REPO);
+    state.record_sync(
+        1,
+        "test-issue-open-simple",
+        "Test issue open simple",
+        "Simple body text",
+        "open",
+        &[],
+        &[],
+        chrono::Utc::now(),
+    );
+    let content = format!(
+        "#+GH_REPO: {}\n#+TODO: TODO | DONE\n\n* TODO Test issue open simple\n:PROPERTIES:\n:GH_ISSUE: 1\n:END:\nSimple body text\n",
+        TEST_REPO
+    );
+    let (_dir, mut org_file, mut engine) =
+        engine_for(&server, &content, state, Default::default()).await;
+
+    let actions = engine.plan(&org_file, true).await.unwrap();
+    engine.execute(actions, &mut org_file).await.unwrap();
+    assert!(
+        org_file.content.contains("* TODO Test issue open simple\n"),
+        "{}",
+        org_file.content
+    );
+
+    // The status is not pushed back as open
+    let org_file = org_gh::org::parser::parse_content(&org_file.path, &org_file.content).unwrap();
+    let actions = engine.plan(&org_file, true).await.unwrap();
+    assert!(
+        actions.iter().all(|a| matches!(a, SyncAction::NoOp { .. })),
+        "{:?}",
+        actions
+    );
+}
+
+#[tokio::test]
+async fn test_engine_fetches_comments_only_for_commented_issues() {
+    use org_gh::sync::engine::SyncAction;
+
+    let server = setup_mock_github().await;
+    common::mount_empty_comments(&server).await;
+
+    // #5 has comments, #1 and #2 have none
+    let content = format!(
+        "#+GH_REPO: {}\n\n* TODO Test issue open simple\n:PROPERTIES:\n:GH_ISSUE: 1\n:END:\n\n* TODO Test issue with labels\n:PROPERTIES:\n:GH_ISSUE: 2\n:END:\n\n* TODO Test issue with comments\n:PROPERTIES:\n:GH_ISSUE: 5\n:END:\n",
+        TEST_REPO
+    );
+    let (_dir, org_file, mut engine) = engine_for(
+        &server,
+        &content,
+        SyncState::new(TEST_REPO),
+        org_gh::config::Config::default(),
+    )
+    .await;
+
+    let actions = engine.plan(&org_file, true).await.unwrap();
+    assert!(actions.iter().any(|a| matches!(
+        a,
+        SyncAction::PullComments { issue_number: 5, entries, .. } if entries.len() == 2
+    )));
+
+    let requests = server.received_requests().await.unwrap();
+    let fetched: Vec<&str> = requests
+        .iter()
+        .map(|r| r.url.path())
+        .filter(|path| path.ends_with("/comments"))
+        .collect();
+    assert_eq!(
+        fetched,
+        vec![format!("/repos/{}/issues/5/comments", TEST_REPO)]
+    );
+}
+
+#[tokio::test]
+async fn test_engine_pushes_body_as_markdown() {
+    use common::load_fixture_json;
+    use org_gh::sync::diff::org_has_changes;
+    use wiremock::matchers::{body_partial_json, method, path};
+    use wiremock::{Mock, ResponseTemplate};
+
+    let server = setup_mock_github().await;
+    common::mount_empty_comments(&server).await;
+
+    let markdown = "Simple **body** text\n\n```sh\nls -la\n```";
+    let mut fixture = load_fixture_json("issue_1.json");
+    fixture["body"] = serde_json::json!(markdown);
+    Mock::given(method("PATCH"))
+        .and(path(format!("/repos/{}/issues/1", TEST_REPO)))
+        .and(body_partial_json(serde_json::json!({"body": markdown})))
+        .respond_with(ResponseTemplate::new(200).set_body_json(fixture))
+        .expect(1)
+        .mount(&server)
+        .await;
+
+    let mut state = SyncState::new(TEST_REPO);
+    state.record_sync(
+        1,
+        "a",
+        "Test issue open simple",
+        "Simple body text",
+        "open",
+        &[],
+        &[],
+        chrono::Utc::now(),
+    );
+    let content = format!(
+        r#"#+GH_REPO: {}
+
+* TODO Test issue open simple
+:PROPERTIES:
+:GH_ISSUE: 1
+:END:
+Simple *body* text
+
+#+begin_src sh
+ls -la
+#+end_src
+"#,
+        TEST_REPO
+    );
+    let (_dir, mut org_file, mut engine) =
+        engine_for(&server, &content, state, org_gh::config::Config::default()).await;
+
+    let actions = engine.plan(&org_file, true).await.unwrap();
+    engine.execute(actions, &mut org_file).await.unwrap();
+
+    // The org text is left alone, and matches the new base once converted
+    assert_eq!(org_file.content, content);
+    let base = &engine.state().items[&1];
+    assert_eq!(base.body.as_deref(), Some(markdown));
+    assert!(!org_has_changes(
+        &org_file.items[0],
+        base,
+        &engine.config().sync
+    ));
+}
+
+#[tokio::test]
+async fn test_engine_merges_body_edits() {
+    use common::load_fixture_json;
+    use org_gh::sync::diff::Field;
+    use org_gh::sync::engine::{Direction, SyncAction};
+    use wiremock::matchers::{body_partial_json, method, path};
+    use wiremock::{Mock, ResponseTemplate};
+
+    let server = setup_mock_github().await;
+    common::mount_empty_comments(&server).await;
+
+    let merged = "Simple body text\nAdded in org";
+    let mut fixture = load_fixture_json("issue_1.json");
+    fixture["body"] = serde_json::json!(merged);
+    Mock::given(method("PATCH"))
+        .and(path(format!("/repos/{}/issues/1", TEST_REPO)))
+        .and(body_partial_json(serde_json::json!({"body": merged})))
+        .respond_with(ResponseTemplate::new(200).set_body_json(fixture))
+        .expect(1)
+        .mount(&server)
+        .await;
+
+    // GitHub dropped the first line, org appended one
+    let mut state = SyncState::new(TEST_REPO);
+    state.record_sync(
+        1,
+        "a",
+        "Test issue open simple",
+        "Intro\nSimple body text",
+        "open",
+        &[],
+        &[],
+        chrono::Utc::now(),
+    );
+
+    let content = format!(
+        r#"#+GH_REPO: {}
+
+* TODO Test issue open simple
+:PROPERTIES:
+:GH_ISSUE: 1
+:END:
+Intro
+Simple body text
+Added in org
+"#,
+        TEST_REPO
+    );
+    let (_dir, mut org_file, mut engine) =
+        engine_for(&server, &content, state, org_gh::config::Config::default()).await;
+
+    let actions = engine.plan(&org_file, true).await.unwrap();
+    assert!(actions.iter().any(|a| matches!(
+        a,
+        SyncAction::Update { fields, .. }
+            if fields == &[(Field::Body, Direction::Edit(merged.to_string()))]
+    )));
+
+    engine.execute(actions, &mut org_file).await.unwrap();
+    assert!(org_file
+        .content
+        .contains(":END:\nSimple body text\nAdded in org\n"));
+    assert_eq!(engine.state().items[&1].body.as_deref(), Some(merged));
+}
+
+#[tokio::test]
+async fn test_engine_pulls_markdown_body_as_org() {
+    use common::load_fixture_json;
+    use org_gh::sync::diff::org_has_changes;
+    use wiremock::matchers::{method, path, query_param};
+    use wiremock::{Mock, MockServer, ResponseTemplate};
+
+    let server = MockServer::start().await;
+    common::mount_empty_comments(&server).await;
+
+    // The heading comes back as a bold line, so org's Markdown differs
+    let markdown = "## Steps\n\n- [x] **Build** it\n- [ ] Run `make test`";
+    let mut fixture = load_fixture_json("issue_1.json");
+    fixture["body"] = serde_json::json!(markdown);
+    for (state, issues) in [
+        ("open", serde_json::json!([fixture])),
+        ("closed", serde_json::json!([])),
+    ] {
+        Mock::given(method("GET"))
+            .and(path(format!("/repos/{}/issues", TEST_REPO)))
+            .and(query_param("state", state))
+            .respond_with(ResponseTemplate::new(200).set_body_json(issues))
+            .mount(&server)
+            .await;
+    }
+
+    let mut state = SyncState::new(TEST_REPO);
+    state.record_sync(
+        1,
+        "a",
+        "Test issue open simple",
+        "Simple body text",
+        "open",
+        &[],
+        &[],
+        chrono::Utc::now(),
+    );
+    let content = format!(
+        r#"#+GH_REPO: {}
+
+* TODO Test issue open simple
+:PROPERTIES:
+:GH_ISSUE: 1
+:END:
+Simple body text
+"#,
+        TEST_REPO
+    );
+    let (_dir, mut org_file, mut engine) =
+        engine_for(&server, &content, state, org_gh::config::Config::default()).await;
+
+    let actions = engine.plan(&org_file, true).await.unwrap();
+    engine.execute(actions, &mut org_file).await.unwrap();
+
+    assert!(org_file
+        .content
+        .contains(":END:\n*Steps*\n\n- [X] *Build* it\n- [ ] Run =make test=\n"));
+
+    // The converted body does not count as an org edit
+    let org_file = org_gh::org::parser::parse_content(&org_file.path, &org_file.content).unwrap();
+    let base = &engine.state().items[&1];
+    assert_eq!(base.body.as_deref(), Some(markdown));
+    assert!(!org_has_changes(
+        &org_file.items[0],
+        base,
+        &engine.config().sync
+    ));
+}
+
+#[tokio::test]
+async fn test_engine_pulled_markup_title_stays_in_sync() {
+    use common::load_fixture_json;
+    use org_gh::sync::engine::SyncAction;
+    use wiremock::matchers::{method, path, query_param};
+    use wiremock::{Mock, MockServer, ResponseTemplate};
+
+    // Org would read the title as italics, and writes it back escaped
+    let mut issue = load_fixture_json("issue_1.json");
+    issue["title"] = "Support /usr/local/ paths in a*b*c".into();
+    let server = MockServer::start().await;
+    for (state, issues) in [
+        ("open", serde_json::json!([issue])),
+        ("closed", serde_json::json!([])),
+    ] {
+        Mock::given(method("GET"))
+            .and(path(format!("/repos/{}/issues", TEST_REPO)))
+            .and(query_param("state", state))
+            .respond_with(ResponseTemplate::new(200).set_body_json(issues))
+            .mount(&server)
+            .await;
+    }
+    common::mount_empty_comments(&server).await;
+
+    let mut state = SyncState::new(TEST_REPO);
+    state.record_sync(
+        1,
+        "test-issue-open-simple",
+        "Test issue open simple",
+        "Simple body text",
+        "open",
+        &[],
+        &[],
+        chrono::Utc::now(),
+    );
+    let content = format!(
+        "#+GH_REPO: {}\n\n* TODO Test issue open simple\n:PROPERTIES:\n:GH_ISSUE: 1\n:END:\nSimple body text\n",
+        TEST_REPO
+    );
+    let (_dir, mut org_file, mut engine) =
+        engine_for(&server, &content, state, Default::default()).await;
+
+    let actions = engine.plan(&org_file, true).await.unwrap();
+    engine.execute(actions, &mut org_file).await.unwrap();
+    assert!(
+        org_file
+            .content
+            .contains("* TODO Support \u{200b}/usr/local/ paths in a*b*c\n"),
+        "{}",
+        org_file.content
+    );
+
+    // The pulled title is not pushed back
+    let org_file = org_gh::org::parser::parse_content(&org_file.path, &org_file.content).unwrap();
+    let actions = engine.plan(&org_file, true).await.unwrap();
+    assert!(
+        actions.iter().all(|a| matches!(a, SyncAction::NoOp { .. })),
+        "{:?}",
+        actions
+    );
+}
+
+#[tokio::test]
+async fn test_engine_merges_checkboxes_and_updates_cookie() {
+    use common::load_fixture_json;
+    use wiremock::matchers::{body_partial_json, method, path, query_param};
+    use wiremock::{Mock, MockServer, ResponseTemplate};
+
+    let server = MockServer::start().await;
+    common::mount_empty_comments(&server).await;
+
+    // Org ticked the first item, GitHub the second
+    let mut fixture = load_fixture_json("issue_1.json");
+    fixture["body"] = serde_json::json!("- [ ] one\n- [x] two\n- [ ] three");
+    for (state, issues) in [
+        ("open", serde_json::json!([fixture])),
+        ("closed", serde_json::json!([])),
+    ] {
+        Mock::given(method("GET"))
+            .and(path(format!("/repos/{}/issues", TEST_REPO)))
+            .and(query_param("state", state))
+            .respond_with(ResponseTemplate::new(200).set_body_json(issues))
+            .mount(&server)
+            .await;
+    }
+    let merged = "- [x] one\n- [x] two\n- [ ] three";
+    fixture["body"] = serde_json::json!(merged);
+    Mock::given(method("PATCH"))
+        .and(path(format!("/repos/{}/issues/1", TEST_REPO)))
+        .and(body_partial_json(serde_json::json!({"body": merged})))
+        .respond_with(ResponseTemplate::new(200).set_body_json(fixture))
+        .expect(1)
+        .mount(&server)
+        .await;
+
+    let mut state = SyncState::new(TEST_REPO);
+    state.record_sync(
+        1,
+        "a",
+        "Test issue open simple",
+        "- [ ] one\n- [ ] two\n- [ ] three",
+        "open",
+        &[],
+        &[],
+        chrono::Utc::now(),
+    );
+    let content = format!(
+        r#"#+GH_REPO: {}
+
+* TODO Test issue open simple [1/3]
+:PROPERTIES:
+:GH_ISSUE: 1
+:END:
+- [X] one
+- [ ] two
+- [ ] three
+"#,
+        TEST_REPO
+    );
+    let (_dir, mut org_file, mut engine) =
+        engine_for(&server, &content, state, org_gh::config::Config::default()).await;
+
+    let actions = engine.plan(&org_file, true).await.unwrap();
+    engine.execute(actions, &mut org_file).await.unwrap();
+
+    assert!(org_file.content.contains(
+        "* TODO Test issue open simple [2/3]\n:PROPERTIES:\n:GH_ISSUE: 1\n:END:\n- [X] one\n- [X] two\n- [ ] three\n"
+    ));
+}
+
+#[tokio::test]
+async fn test_engine_maintains_sub_issues() {
+    use org_gh::sync::engine::SyncAction;
+    use wiremock::matchers::{body_json, method, path};
+    use wiremock::{Mock, ResponseTemplate};
+
+    let server = setup_mock_github().await;
+    common::mount_empty_comments(&server).await;
+
+    // A new child heading becomes a sub-issue of its linked parent
+    Mock::given(method("POST"))
+        .and(path(format!("/repos/{}/issues", TEST_REPO)))
+        .respond_with(ResponseTemplate::new(201).set_body_string(load_fixture("issue_3.json")))
+        .expect(1)
+        .mount(&server)
+        .await;
+    Mock::given(method("GET"))
+        .and(path(format!("/repos/{}/issues/1/sub_issues", TEST_REPO)))
+        .respond_with(ResponseTemplate::new(200).set_body_json(serde_json::json!([])))
+        .mount(&server)
+        .await;
+    let issue_1: serde_json::Value = serde_json::from_str(&load_fixture("issue_1.json")).unwrap();
+    Mock::given(method("POST"))
+        .and(path(format!("/repos/{}/issues/1/sub_issues", TEST_REPO)))
+        .and(body_json(
+            serde_json::json!({"sub_issue_id": 3797703008u64, "replace_parent": true}),
+        ))
+        .respond_with(ResponseTemplate::new(201).set_body_json(&issue_1))
+        .expect(1)
+        .mount(&server)
+        .await;
+
+    // Issue 2 was a sub-issue of 1 and its heading moved to the top level
+    Mock::given(method("DELETE"))
+        .and(path(format!("/repos/{}/issues/1/sub_issue", TEST_REPO)))
+        .and(body_json(
+            serde_json::json!({"sub_issue_id": 3797702961u64}),
+        ))
+        .respond_with(ResponseTemplate::new(200).set_body_json(&issue_1))
+        .expect(1)
+        .mount(&server)
+        .await;
+
+    let mut state = SyncState::new(TEST_REPO);
+    state.record_sync(
+        2,
+        "test-issue-with-labels",
+        "Test issue with labels",
+        "Has labels and assignee",
+        "open",
+        &[],
+        &[],
+        chrono::Utc::now(),
+    );
+    state.items.get_mut(&2).unwrap().parent = Some(1);
+
+    let content = format!(
+        r#"#+GH_REPO: {}
+
+* TODO Test issue open simple
+:PROPERTIES:
+:GH_ISSUE: 1
+:END:
+** TODO New child task
+* TODO Test issue with labels
+:PROPERTIES:
+:GH_ISSUE: 2
+:END:
+"#,
+        TEST_REPO
+    );
+    let (_dir, mut org_file, mut engine) =
+        engine_for(&server, &content, state, org_gh::config::Config::default()).await;
+
+    let actions: Vec<SyncAction> = engine
+        .plan(&org_file, true)
+        .await
+        .unwrap()
+        .into_iter()
+        .filter(|a| {
+            matches!(
+                a,
+                SyncAction::CreateIssue { .. } | SyncAction::SetParent { .. }
+            )
+        })
+        .collect();
+    assert!(matches!(
+        &actions[1],
+        SyncAction::SetParent {
+            issue_number: 2,
+            parent: None,
+            previous: Some(1),
+            ..
+        }
+    ));
+
+    engine.execute(actions, &mut org_file).await.unwrap();
+    assert_eq!(engine.state().items[&3].parent, Some(1));
+    assert_eq!(engine.state().items[&2].parent, None);
+}
+
+#[tokio::test]
+async fn test_engine_sub_issue_parent_with_duplicate_titles() {
+    use org_gh::sync::engine::SyncAction;
+    use wiremock::matchers::{method, path};
+    use wiremock::{Mock, ResponseTemplate};
+
+    let server = setup_mock_github().await;
+    common::mount_empty_comments(&server).await;
+
+    Mock::given(method("POST"))
+        .and(path(format!("/repos/{}/issues", TEST_REPO)))
+        .respond_with(ResponseTemplate::new(201).set_body_string(load_fixture("issue_3.json")))
+        .expect(2)
+        .mount(&server)
+        .await;
+    for (parent, calls) in [(3, 0), (4, 1)] {
+        Mock::given(method("GET"))
+            .and(path(format!(
+                "/repos/{}/issues/{}/sub_issues",
+                TEST_REPO, parent
+            )))
+            .respond_with(ResponseTemplate::new(200).set_body_json(serde_json::json!([])))
+            .mount(&server)
+            .await;
+        Mock::given(method("POST"))
+            .and(path(format!(
+                "/repos/{}/issues/{}/sub_issues",
+                TEST_REPO, parent
+            )))
+            .respond_with(ResponseTemplate::new(201).set_body_string(load_fixture("issue_1.json")))
+            .expect(calls)
+            .mount(&server)
+            .await;
+    }
+
+    // Both parents slugify to "epic"; the child belongs to the linked one
+    let content = format!(
+        r#"#+GH_REPO: {}
+
+* TODO Epic
+* TODO Epic
+:PROPERTIES:
+:GH_ISSUE: 4
+:END:
+** TODO New child task
+"#,
+        TEST_REPO
+    );
+    let (_dir, mut org_file, mut engine) = engine_for(
+        &server,
+        &content,
+        SyncState::new(TEST_REPO),
+        org_gh::config::Config::default(),
+    )
+    .await;
+    assert_eq!(org_file.items[2].parent, Some(1));
+
+    let actions: Vec<SyncAction> = engine
+        .plan(&org_file, true)
+        .await
+        .unwrap()
+        .into_iter()
+        .filter(|a| matches!(a, SyncAction::CreateIssue { .. }))
+        .collect();
+    assert_eq!(actions.len(), 2);
+    engine.execute(actions, &mut org_file).await.unwrap();
+    assert_eq!(engine.state().items[&3].parent, Some(4));
+}
+
+#[tokio::test]
+async fn test_engine_pulls_labels_as_tags() {
+    let server = setup_mock_github().await;
+    common::mount_empty_comments(&server).await;
+
+    // GitHub added `enhancement`; org reads labels from tags
+    let mut state = SyncState::new(TEST_REPO);
+    state.record_sync(
+        2,
+        "test-issue-with-labels",
+        "Test issue with labels",
+        "Has labels and assignee",
+        "open",
+        &["tftio".to_string()],
+        &["bug".to_string()],
+        chrono::Utc::now(),
+    );
+    let content = format!(
+        r#"#+GH_REPO: {}
+
+* TODO Test issue with labels   :bug:
+:PROPERTIES:
+:GH_ISSUE: 2
+:ASSIGNEE: tftio
+:END:
+Has labels and assignee
+"#,
+        TEST_REPO
+    );
+    let mut config = org_gh::config::Config::default();
+    config.org.tags_as_labels = true;
+    let (_dir, org_file, mut engine) = engine_for(&server, &content, state, config).await;
+    let mut org_file =
+        org_gh::org::parser::parse_content_with(&org_file.path, &content, &engine.config().org)
+            .unwrap();
+    assert_eq!(org_file.items[0].labels, vec!["bug"]);
+
+    let actions = engine.plan(&org_file, true).await.unwrap();
+    engine.execute(actions, &mut org_file).await.unwrap();
+
+    assert!(org_file
+        .content
+        .contains("* TODO Test issue with labels   :bug:enhancement:\n:PROPERTIES:\n:GH_ISSUE: 2\n:ASSIGNEE: tftio\n:END:\n"));
+}
+
+#[tokio::test]
+async fn test_engine_label_removed_on_github_stays_inherited() {
+    use common::load_fixture_json;
+    use org_gh::sync::engine::SyncAction;
+    use wiremock::matchers::{method, path, query_param};
+    use wiremock::{Mock, MockServer, ResponseTemplate};
+
+    // GitHub dropped `bug`, which the heading inherits from FILETAGS
+    let mut issue = load_fixture_json("issue_2.json");
+    issue["labels"].as_array_mut().unwrap().remove(0);
+    let server = MockServer::start().await;
+    for (state, issues) in [
+        ("open", serde_json::json!([issue])),
+        ("closed", serde_json::json!([])),
+    ] {
+        Mock::given(method("GET"))
+            .and(path(format!("/repos/{}/issues", TEST_REPO)))
+            .and(query_param("state", state))
+            .respond_with(ResponseTemplate::new(200).set_body_json(issues))
+            .mount(&server)
+            .await;
+    }
+    common::mount_empty_comments(&server).await;
+
+    let mut state = SyncState::new(TEST_REPO);
+    state.record_sync(
+        2,
+        "test-issue-with-labels",
+        "Test issue with labels",
+        "Has labels and assignee",
+        "open",
+        &["tftio".to_string()],
+        &["bug".to_string(), "enhancement".to_string()],
+        chrono::Utc::now(),
+    );
+    let content = format!(
+        r#"#+GH_REPO: {}
+#+FILETAGS: :bug:
+
+* TODO Test issue with labels   :enhancement:
+:PROPERTIES:
+:GH_ISSUE: 2
+:ASSIGNEE: tftio
+:END:
+Has labels and assignee
+"#,
+        TEST_REPO
+    );
+    let mut config = org_gh::config::Config::default();
+    config.org.tags_as_labels = true;
+    let org_config = config.org.clone();
+    let (_dir, org_file, mut engine) = engine_for(&server, &content, state, config).await;
+    let path = org_file.path.clone();
+    let parse = |content: &str| {
+        org_gh::org::parser::parse_content_with(&path, content, &org_config).unwrap()
+    };
+    let mut org_file = parse(&content);
+
+    let actions = engine.plan(&org_file, true).await.unwrap();
+    engine.execute(actions, &mut org_file).await.unwrap();
+    assert!(org_file
+        .content
+        .contains("* TODO Test issue with labels   :enhancement:\n"));
+
+    // The inherited tag is not pushed back as a label
+    let org_file = parse(&org_file.content);
+    let actions = engine.plan(&org_file, true).await.unwrap();
+    assert!(
+        actions.iter().all(|a| matches!(a, SyncAction::NoOp { .. })),
+        "{:?}",
+        actions
+    );
+}
+
+#[tokio::test]
+async fn test_engine_priority_label_rewrites_cookie() {
+    use common::load_fixture_json;
+    use wiremock::matchers::{method, path, query_param};
+    use wiremock::{Mock, ResponseTemplate};
+
+    let server = setup_mock_github().await;
+    common::mount_empty_comments(&server).await;
+
+    // Someone lowered the priority on GitHub
+    let mut issue = load_fixture_json("issue_2.json");
+    let mut label = issue["labels"][0].clone();
+    label["name"] = serde_json::json!("priority-low");
+    issue["labels"].as_array_mut().unwrap().push(label);
+    Mock::given(method("GET"))
+        .and(path(format!("/repos/{}/issues", TEST_REPO)))
+        .and(query_param("state", "open"))
+        .respond_with(ResponseTemplate::new(200).set_body_json(serde_json::json!([issue])))
+        .with_priority(1)
+        .mount(&server)
+        .await;
+
+    let mut state = SyncState::new(TEST_REPO);
+    state.record_sync(
+        2,
+        "test-issue-with-labels",
+        "Test issue with labels",
+        "Has labels and assignee",
+        "open",
+        &["tftio".to_string()],
+        &[
+            "bug".to_string(),
+            "enhancement".to_string(),
+            "priority-high".to_string(),
+        ],
+        chrono::Utc::now(),
+    );
+    let content = format!(
+        r#"#+GH_REPO: {}
+
+* TODO [#A] Test issue with labels
+:PROPERTIES:
+:GH_ISSUE: 2
+:ASSIGNEE: tftio
+:LABELS: bug, enhancement
+:END:
+Has labels and assignee
+"#,
+        TEST_REPO
+    );
+    let (_dir, mut org_file, mut engine) =
+        engine_for(&server, &content, state, Default::default()).await;
+
+    let actions = engine.plan(&org_file, true).await.unwrap();
+    engine.execute(actions, &mut org_file).await.unwrap();
+
+    assert!(org_file.content.contains(
+        "* TODO [#C] Test issue with labels\n:PROPERTIES:\n:GH_ISSUE: 2\n:ASSIGNEE: tftio\n:LABELS: bug, enhancement\n:END:\n"
+    ));
+}
+
+#[tokio::test]
+async fn test_engine_syncs_planning_dates_with_project() {
+    use wiremock::matchers::{body_partial_json, body_string_contains, method, path};
+    use wiremock::{Mock, ResponseTemplate};
+
+    let server = setup_mock_github().await;
+    common::mount_empty_comments(&server).await;
+
+    // The start date moved on the board
+    let board = serde_json::json!({"data": {"repositoryOwner": {"projectV2": {
+        "id": "PVT_1",
+        "fields": {"nodes": [
+            {"id": "F_TITLE", "name": "Title", "dataType": "TITLE"},
+            {"id": "F_START", "name": "Start date", "dataType": "DATE"},
+            {"id": "F_TARGET", "name": "Target date", "dataType": "DATE"}
+        ]},
+        "items": {
+            "pageInfo": {"hasNextPage": false, "endCursor": null},
+            "nodes": [{
+                "id": "PVTI_1",
+                "content": {"number": 1, "repository": {"nameWithOwner": TEST_REPO}},
+                "fieldValues": {"nodes": [
+                    {},
+                    {"date": "2026-01-12", "field": {"name": "Start date"}},
+                    {"date": "2026-01-30", "field": {"name": "Target date"}}
+                ]}
+            }]
+        }
+    }}}});
+    Mock::given(method("POST"))
+        .and(path("/graphql"))
+        .and(body_string_contains("projectV2(number"))
+        .respond_with(ResponseTemplate::new(200).set_body_json(&board))
+        .mount(&server)
+        .await;
+
+    // ...and the deadline moved in org
+    Mock::given(method("POST"))
+        .and(path("/graphql"))
+        .and(body_string_contains("updateProjectV2ItemFieldValue"))
+        .and(body_partial_json(serde_json::json!({"variables": {
+            "project": "PVT_1",
+            "item": "PVTI_1",
+            "field": "F_TARGET",
+            "date": "2026-02-01"
+        }})))
+        .respond_with(ResponseTemplate::new(200).set_body_json(
+            serde_json::json!({"data": {"updateProjectV2ItemFieldValue": {"projectV2Item": {"id": "PVTI_1"}}}}),
+        ))
+        .expect(1)
+        .mount(&server)
+        .await;
+
+    let mut state = SyncState::new(TEST_REPO);
+    state.record_sync(
+        1,
+        "test-issue-open-simple",
+        "Test issue open simple",
+        "Simple body text",
+        "open",
+        &[],
+        &[],
+        chrono::Utc::now(),
+    );
+    let synced = state.items.get_mut(&1).unwrap();
+    synced.scheduled = chrono::NaiveDate::from_ymd_opt(2026, 1, 5);
+    synced.deadline = chrono::NaiveDate::from_ymd_opt(2026, 1, 30);
+
+    let content = format!(
+        r#"#+GH_REPO: {}
+
+* TODO Test issue open simple
+SCHEDULED: <2026-01-05 Mon> DEADLINE: <2026-02-01 Sun>
+:PROPERTIES:
+:GH_ISSUE: 1
+:END:
+Simple body text
+"#,
+        TEST_REPO
+    );
+    let mut config = org_gh::config::Config::default();
+    config.sync.project = Some(org_gh::config::ProjectConfig {
+        owner: "tftio".to_string(),
+        number: 3,
+        scheduled_field: Some("Start date".to_string()),
+        deadline_field: Some("Target date".to_string()),
+    });
+    let (_dir, mut org_file, mut engine) = engine_for(&server, &content, state, config).await;
+
+    let actions = engine.plan(&org_file, true).await.unwrap();
+    engine.execute(actions, &mut org_file).await.unwrap();
+
+    assert!(org_file.content.contains(
+        "* TODO Test issue open simple\nSCHEDULED: <2026-01-12 Mon> DEADLINE: <2026-02-01 Sun>\n:PROPERTIES:\n"
+    ));
+    let base = &engine.state().items[&1];
+    assert_eq!(base.scheduled, chrono::NaiveDate::from_ymd_opt(2026, 1, 12));
+    assert_eq!(base.deadline, chrono::NaiveDate::from_ymd_opt(2026, 2, 1));
+}
+
+#[tokio::test]
+async fn test_engine_pushes_dates_of_issues_created_before_the_project() {
+    use common::load_fixture_json;
+    use wiremock::matchers::{body_partial_json, body_string_contains, method, path};
+    use wiremock::{Mock, ResponseTemplate};
+
+    let server = setup_mock_github().await;
+    common::mount_empty_comments(&server).await;
+
+    let mut created = load_fixture_json("issue_3.json");
+    created["number"] = serde_json::json!(9);
+    created["title"] = serde_json::json!("Brand new");
+    created["body"] = serde_json::Value::Null;
+    created["state"] = serde_json::json!("open");
+    created["state_reason"] = serde_json::Value::Null;
+    created["closed_at"] = serde_json::Value::Null;
+    created["labels"] = serde_json::json!([]);
+    Mock::given(method("POST"))
+        .and(path(format!("/repos/{}/issues", TEST_REPO)))
+        .respond_with(ResponseTemplate::new(201).set_body_json(&created))
+        .expect(1)
+        .mount(&server)
+        .await;
+    Mock::given(method("GET"))
+        .and(path(format!("/repos/{}/issues/9", TEST_REPO)))
+        .respond_with(ResponseTemplate::new(200).set_body_json(&created))
+        .mount(&server)
+        .await;
+
+    // Created without a project: the dates are not synced yet
+    let content = format!(
+        "#+GH_REPO: {}\n\n* TODO Brand new\nSCHEDULED: <2026-01-05 Mon> DEADLINE: <2026-02-01 Sun>\n",
+        TEST_REPO
+    );
+    let (dir, mut org_file, mut engine) = engine_for(
+        &server,
+        &content,
+        SyncState::new(TEST_REPO),
+        org_gh::config::Config::default(),
+    )
+    .await;
+    let actions = engine.plan(&org_file, true).await.unwrap();
+    engine.execute(actions, &mut org_file).await.unwrap();
+    engine.save(&org_file, true).unwrap();
+    let base = &engine.state().items[&9];
+    assert_eq!((base.scheduled, base.deadline), (None, None));
+
+    // The issue is on the board, without dates, once the project is set up
+    let board = serde_json::json!({"data": {"repositoryOwner": {"projectV2": {
+        "id": "PVT_1",
+        "fields": {"nodes": [
+            {"id": "F_START", "name": "Start date", "dataType": "DATE"},
+            {"id": "F_TARGET", "name": "Target date", "dataType": "DATE"}
+        ]},
+        "items": {
+            "pageInfo": {"hasNextPage": false, "endCursor": null},
+            "nodes": [{
+                "id": "PVTI_9",
+                "content": {"number": 9, "repository": {"nameWithOwner": TEST_REPO}},
+                "fieldValues": {"nodes": []}
+            }]
+        }
+    }}}});
+    Mock::given(method("POST"))
+        .and(path("/graphql"))
+        .and(body_string_contains("projectV2(number"))
+        .respond_with(ResponseTemplate::new(200).set_body_json(&board))
+        .mount(&server)
+        .await;
+    for (field, date) in [("F_START", "2026-01-05"), ("F_TARGET", "2026-02-01")] {
+        Mock::given(method("POST"))
+            .and(path("/graphql"))
+            .and(body_string_contains("updateProjectV2ItemFieldValue"))
+            .and(body_partial_json(serde_json::json!({"variables": {
+                "item": "PVTI_9",
+                "field": field,
+                "date": date
+            }})))
+            .respond_with(ResponseTemplate::new(200).set_body_json(
+                serde_json::json!({"data": {"updateProjectV2ItemFieldValue": {"projectV2Item": {"id": "PVTI_9"}}}}),
+            ))
+            .expect(1)
+            .mount(&server)
+            .await;
+    }
+
+    let mut config = org_gh::config::Config::default();
+    config.sync.project = Some(org_gh::config::ProjectConfig {
+        owner: "tftio".to_string(),
+        number: 3,
+        scheduled_field: Some("Start date".to_string()),
+        deadline_field: Some("Target date".to_string()),
+    });
+    let mut org_file = parse_file(&dir.path().join("test.org")).unwrap();
+    let client = GitHubClient::with_base_url("fake-token", TEST_REPO, Some(&server.uri()))
+        .await
+        .unwrap();
+    let mut engine =
+        org_gh::sync::SyncEngine::with_client(config, client, engine.state().clone(), &org_file);
+    let actions = engine.plan(&org_file, false).await.unwrap();
+    engine.execute(actions, &mut org_file).await.unwrap();
+
+    // The org dates survive and went to the board
+    assert!(
+        org_file
+            .content
+            .contains("* TODO Brand new\nSCHEDULED: <2026-01-05 Mon> DEADLINE: <2026-02-01 Sun>\n"),
+        "{}",
+        org_file.content
+    );
+    let base = &engine.state().items[&9];
+    assert_eq!(base.scheduled, chrono::NaiveDate::from_ymd_opt(2026, 1, 5));
+    assert_eq!(base.deadline, chrono::NaiveDate::from_ymd_opt(2026, 2, 1));
+}
+
+#[tokio::test]
+async fn test_engine_imports_unlinked_issues() {
+    use common::load_fixture_json;
+    use org_gh::sync::engine::SyncAction;
+    use wiremock::matchers::{method, path, query_param};
+    use wiremock::{Mock, ResponseTemplate};
+
+    let server = setup_mock_github().await;
+    common::mount_empty_comments(&server).await;
+
+    // The imported issue's comments come along
+    Mock::given(method("GET"))
+        .and(path(format!("/repos/{}/issues/2/comments", TEST_REPO)))
+        .respond_with(
+            ResponseTemplate::new(200)
+                .set_body_string(common::load_fixture("issue_5_comments.json")),
+        )
+        .with_priority(1)
+        .mount(&server)
+        .await;
+
+    Mock::given(method("GET"))
+        .and(path("/search/issues"))
+        .and(query_param(
+            "q",
+            format!("repo:{} is:issue is:open label:bug", TEST_REPO),
+        ))
+        .respond_with(ResponseTemplate::new(200).set_body_json(serde_json::json!({
+            "total_count": 2,
+            "incomplete_results": false,
+            "items": [load_fixture_json("issue_1.json"), load_fixture_json("issue_2.json")],
+        })))
+        .expect(2)
+        .mount(&server)
+        .await;
+
+    let mut state = SyncState::new(TEST_REPO);
+    state.record_sync(
+        1,
+        "test-issue-open-simple",
+        "Test issue open simple",
+        "Simple body text",
+        "open",
+        &[],
+        &[],
+        chrono::Utc::now(),
+    );
+    let content = format!(
+        r#"#+GH_REPO: {}
+#+GH_IMPORT: label:bug
+
+* TODO Test issue open simple
+:PROPERTIES:
+:GH_ISSUE: 1
+:END:
+Simple body text
+
+* Inbox
+"#,
+        TEST_REPO
+    );
+    let (_dir, mut org_file, mut engine) =
+        engine_for(&server, &content, state, Default::default()).await;
+
+    let actions = engine.plan(&org_file, true).await.unwrap();
+    let imports: Vec<u64> = actions
+        .iter()
+        .filter_map(|a| match a {
+            SyncAction::ImportIssue { gh_issue } => Some(gh_issue.number),
+            _ => None,
+        })
+        .collect();
+    assert_eq!(imports, vec![2]);
+    engine.execute(actions, &mut org_file).await.unwrap();
+
+    assert!(
+        org_file.content.ends_with(
+            "* Inbox\n** TODO Test issue with labels\n:PROPERTIES:\n:GH_ISSUE: 2\n:GH_URL: https://github.com/tftio/org-gh-test-fixture/issues/2\n:ASSIGNEE: tftio\n:LABELS: bug, enhancement\n:END:\n:LOGBOOK:\n- Comment by @tftio [2026-01-09T17:10:26Z]:\n  First comment on the issue\n- Comment by @tftio [2026-01-09T17:10:27Z]:\n  Second comment for testing\n:END:\nHas labels and assignee\n"
+        ),
+        "{}",
+        org_file.content
+    );
+
+    assert_eq!(engine.state().items[&2].last_comment_id, Some(3729844131));
+
+    // The imported heading is in sync straight away, its comments neither
+    // pulled again nor posted back
+    let org_file = org_gh::org::parser::parse_content(&org_file.path, &org_file.content).unwrap();
+    let actions = engine.plan(&org_file, true).await.unwrap();
+    assert!(
+        actions.iter().all(|a| matches!(a, SyncAction::NoOp { .. })),
+        "{:?}",
+        actions
+    );
+}
+
+#[tokio::test]
+async fn test_engine_cloned_file_is_in_sync() {
+    use org_gh::sync::engine::SyncAction;
+    use org_gh::sync::import::{clone_file, GroupBy};
+
+    let server = setup_mock_github().await;
+    common::mount_empty_comments(&server).await;
+
+    let client = GitHubClient::with_base_url("fake-token", TEST_REPO, Some(&server.uri()))
+        .await
+        .expect("Failed to create client");
+    let issues = client.fetch_issues().await.unwrap();
+    let numbers: Vec<u64> = issues.iter().map(|issue| issue.number).collect();
+    let comments = client.fetch_comments_for(&numbers, None).await.unwrap();
+    let config = org_gh::config::Config::default();
+    let (content, state) = clone_file(TEST_REPO, &issues, &comments, GroupBy::Label, &config);
+    assert!(content.contains("- Comment by @tftio [2026-01-09T17:10:26Z]:\n"));
+    assert!(content.contains(&format!("#+GH_REPO: {}\n", TEST_REPO)));
+
+    let (_dir, org_file, mut engine) = engine_for(&server, &content, state, config).await;
+    assert_eq!(org_file.items.len(), issues.len());
+
+    let actions = engine.plan(&org_file, true).await.unwrap();
+    assert!(
+        actions.iter().all(|a| matches!(a, SyncAction::NoOp { .. })),
+        "{:?}",
+        actions
+    );
+}
+
+#[tokio::test]
+async fn test_engine_deleted_heading_policy() {
+    use common::load_fixture_json;
+    use org_gh::config::DeletedHeading;
+    use org_gh::sync::engine::SyncAction;
+    use wiremock::matchers::{body_partial_json, method, path};
+    use wiremock::{Mock, ResponseTemplate};
+
+    let server = setup_mock_github().await;
+    common::mount_empty_comments(&server).await;
+
+    Mock::given(method("PATCH"))
+        .and(path(format!("/repos/{}/issues/4", TEST_REPO)))
+        .and(body_partial_json(serde_json::json!({
+            "state": "closed",
+            "state_reason": "not_planned"
+        })))
+        .respond_with(ResponseTemplate::new(200).set_body_json(load_fixture_json("issue_3.json")))
+        .expect(1)
+        .mount(&server)
+        .await;
+
+    let mut state = SyncState::new(TEST_REPO);
+    for (number, title) in [(2, "Moved"), (3, "Archived"), (4, "Deleted")] {
+        state.record_sync(
+            number,
+            &title.to_lowercase(),
+            title,
+            "",
+            "open",
+            &[],
+            &[],
+            chrono::Utc::now(),
+        );
+    }
+    let content = format!("#+GH_REPO: {}\n\n* Notes\n", TEST_REPO);
+    let mut config = org_gh::config::Config::default();
+    config.sync.deleted_heading = DeletedHeading::Close;
+    let (dir, mut org_file, mut engine) = engine_for(&server, &content, state, config).await;
+
+    // Moved to another file of the repo, archived into test.org_archive
+    // (the issue is closed already)
+    let heading = |n: u64| format!("* TODO Item\n:PROPERTIES:\n:GH_ISSUE: {}\n:END:\n", n);
+    fs::write(
+        dir.path().join("other.org"),
+        format!("#+GH_REPO: {}\n{}", TEST_REPO, heading(2)),
+    )
+    .unwrap();
+    fs::write(dir.path().join("test.org_archive"), heading(3)).unwrap();
+
+    let actions = engine.plan(&org_file, false).await.unwrap();
+    let described: Vec<String> = actions.iter().map(|a| a.describe()).collect();
+    assert_eq!(
+        described,
+        vec![
+            format!(
+                "Untrack #2: Moved (moved to {})",
+                dir.path().join("other.org").display()
+            ),
+            "Skip #3: no changes".to_string(),
+            "Close #4: Deleted (heading deleted, closing as not planned)".to_string(),
+        ]
+    );
+    assert!(matches!(
+        actions[2],
+        SyncAction::Untrack {
+            issue_number: 4,
+            close: true,
+            ..
+        }
+    ));
+
+    let report = engine.execute(actions, &mut org_file).await.unwrap();
+    assert_eq!((report.untracked, report.closed), (2, 1));
+    let mut tracked: Vec<u64> = engine.state().items.keys().copied().collect();
+    tracked.sort();
+    assert_eq!(tracked, vec![3]);
+}
+
+#[tokio::test]
+async fn test_engine_archived_headings() {
+    use common::load_fixture_json;
+    use org_gh::config::ArchiveClosed;
+    use org_gh::sync::engine::SyncAction;
+    use wiremock::matchers::{body_partial_json, method, path};
+    use wiremock::{Mock, ResponseTemplate};
+
+    let server = setup_mock_github().await;
+    common::mount_empty_comments(&server).await;
+
+    let mut closed = load_fixture_json("issue_1.json");
+    closed["state"] = serde_json::json!("closed");
+    closed["state_reason"] = serde_json::json!("completed");
+    Mock::given(method("PATCH"))
+        .and(path(format!("/repos/{}/issues/1", TEST_REPO)))
+        .and(body_partial_json(serde_json::json!({
+            "state": "closed",
+            "state_reason": "completed"
+        })))
+        .respond_with(ResponseTemplate::new(200).set_body_json(&closed))
+        .expect(1)
+        .mount(&server)
+        .await;
+
+    let mut state = SyncState::new(TEST_REPO);
+    state.record_sync(
+        1,
+        "test-issue-open-simple",
+        "Test issue open simple",
+        "Simple body text",
+        "open",
+        &[],
+        &[],
+        chrono::Utc::now(),
+    );
+    state.record_sync(
+        3,
+        "test-issue-closed",
+        "Test issue closed",
+        "This one is done",
+        "open",
+        &[],
+        &["documentation".to_string()],
+        chrono::Utc::now(),
+    );
+    let content = format!(
+        r#"#+GH_REPO: {}
+
+* DONE Test issue open simple  :ARCHIVE:
+:PROPERTIES:
+:GH_ISSUE: 1
+:END:
+Simple body text
+
+* Roadmap
+** TODO Test issue closed
+:PROPERTIES:
+:GH_ISSUE: 3
+:LABELS: documentation
+:END:
+This one is done
+"#,
+        TEST_REPO
+    );
+    let mut config = org_gh::config::Config::default();
+    config.sync.archive_closed = ArchiveClosed::Always;
+    let (dir, mut org_file, mut engine) = engine_for(&server, &content, state, config).await;
+
+    let actions = engine.plan(&org_file, true).await.unwrap();
+    let described: Vec<String> = actions.iter().map(|a| a.describe()).collect();
+    assert_eq!(
+        described,
+        vec![
+            "Close #1: Test issue open simple (archived)",
+            "Sync #3: Test issue closed\n  - state: pull from GitHub",
+            "Archive #3: Test issue closed (closed on GitHub)",
+        ]
+    );
+    assert!(matches!(
+        actions[2],
+        SyncAction::ArchiveHeading {
+            confirmed: true,
+            ..
+        }
+    ));
+
+    let report = engine.execute(actions, &mut org_file).await.unwrap();
+    engine.save(&org_file, true).unwrap();
+    assert_eq!(report.archived, 1);
+    // Both stay tracked, as closed
+    assert_eq!(engine.state().items[&1].state, "closed");
+    assert_eq!(engine.state().items[&3].state, "closed");
+
+    let remaining = fs::read_to_string(dir.path().join("test.org")).unwrap();
+    assert!(remaining.ends_with("* Roadmap\n"), "{}", remaining);
+    let archive = fs::read_to_string(dir.path().join("test.org_archive")).unwrap();
+    assert!(archive.starts_with("#    -*- mode: org -*-\n\n\nArchived entries from file "));
+    assert!(archive.contains("\n* DONE Test issue closed\n:PROPERTIES:\n:GH_ISSUE: 3\n"));
+    assert!(archive.contains(":ARCHIVE_CATEGORY: test\n:ARCHIVE_TODO: DONE\n:END:\n"));
+
+    // The archived issue is found there on the next sync, and left alone
+    let org_file = parse_file(&dir.path().join("test.org")).unwrap();
+    let actions = engine.plan(&org_file, true).await.unwrap();
+    assert!(
+        actions
+            .iter()
+            .any(|a| matches!(a, SyncAction::NoOp { issue_number: 3 })),
+        "{:?}",
+        actions
+    );
+}
+
+#[tokio::test]
+async fn test_engine_archives_into_a_new_directory() {
+    use org_gh::config::ArchiveClosed;
+
+    let server = setup_mock_github().await;
+    common::mount_empty_comments(&server).await;
+
+    let mut state = SyncState::new(TEST_REPO);
+    state.record_sync(
+        3,
+        "test-issue-closed",
+        "Test issue closed",
+        "This one is done",
+        "open",
+        &[],
+        &["documentation".to_string()],
+        chrono::Utc::now(),
+    );
+    let content = format!(
+        "#+GH_REPO: {}\n\n* TODO Test issue closed\n:PROPERTIES:\n:GH_ISSUE: 3\n:LABELS: documentation\n:END:\nThis one is done\n",
+        TEST_REPO
+    );
+    let mut config = org_gh::config::Config::default();
+    config.sync.archive_closed = ArchiveClosed::Always;
+    config.org.archive_location = "archive/%s::".to_string();
+    let (dir, mut org_file, mut engine) = engine_for(&server, &content, state, config).await;
+
+    let actions = engine.plan(&org_file, true).await.unwrap();
+    let report = engine.execute(actions, &mut org_file).await.unwrap();
+    assert_eq!(report.archived, 1);
+    engine.save(&org_file, true).unwrap();
+
+    // The archive directory is created rather than the subtree lost
+    let archive = fs::read_to_string(dir.path().join("archive/test.org")).unwrap();
+    assert!(archive.contains("\n* DONE Test issue closed\n:PROPERTIES:\n:GH_ISSUE: 3\n"));
+    let remaining = fs::read_to_string(dir.path().join("test.org")).unwrap();
+    assert!(!remaining.contains("Test issue closed"), "{}", remaining);
+}
+
+#[tokio::test]
+async fn test_engine_relinks_heading_by_id() {
+    use org_gh::sync::engine::SyncAction;
+
+    let server = setup_mock_github().await;
+    common::mount_empty_comments(&server).await;
+
+    let mut state = SyncState::new(TEST_REPO);
+    state.record_sync(
+        1,
+        "test-issue-open-simple",
+        "Test issue open simple",
+        "Simple body text",
+        "open",
+        &[],
+        &[],
+        chrono::Utc::now(),
+    );
+    // The GH_ISSUE property is gone; the title still gives the heading id
+    let content = format!(
+        "#+GH_REPO: {}\n\n* TODO Test issue open simple\nSimple body text\n",
+        TEST_REPO
+    );
+    let mut config = org_gh::config::Config::default();
+    config.org.assign_ids = true;
+    let (dir, mut org_file, mut engine) = engine_for(&server, &content, state, config).await;
+
+    let actions = engine.plan(&org_file, true).await.unwrap();
+    assert!(matches!(
+        actions[0],
+        SyncAction::Link {
+            issue_number: 1,
+            relink: true,
+            assign_id: true,
+            ..
+        }
+    ));
+    assert!(
+        actions[1..]
+            .iter()
+            .all(|a| matches!(a, SyncAction::NoOp { issue_number: 1 })),
+        "{:?}",
+        actions
+    );
+
+    let report = engine.execute(actions, &mut org_file).await.unwrap();
+    engine.save(&org_file, true).unwrap();
+    assert_eq!(report.relinked, 1);
+
+    // The heading keeps its identity under a new title from now on
+    let renamed = fs::read_to_string(dir.path().join("test.org"))
+        .unwrap()
+        .replace("Test issue open simple", "Renamed");
+    fs::write(dir.path().join("test.org"), renamed).unwrap();
+    let org_file = parse_file(&dir.path().join("test.org")).unwrap();
+    let item = &org_file.items[0];
+    assert_eq!(item.gh_issue, Some(1));
+    assert_eq!(item.org_id.as_deref(), Some(item.id.as_str()));
+    assert_eq!(engine.state().items[&1].org_heading_id, item.id);
+}
+
+#[tokio::test]
+async fn test_engine_label_prefix_and_default_labels() {
+    use common::load_fixture_json;
+    use org_gh::sync::diff::{org_has_changes, three_way_diff, FieldChange};
+    use wiremock::matchers::{body_partial_json, method, path};
+    use wiremock::{Mock, ResponseTemplate};
+
+    let server = setup_mock_github().await;
+    common::mount_empty_comments(&server).await;
+
+    let mut created = load_fixture_json("issue_1.json");
+    created["title"] = serde_json::json!("Brand new roadmap item");
+    created["body"] = serde_json::Value::Null;
+    let label = load_fixture_json("issue_2.json")["labels"][0].clone();
+    created["labels"] = serde_json::json!(["roadmap-tier1", "org-gh-managed"]
+        .iter()
+        .map(|name| {
+            let mut label = label.clone();
+            label["name"] = serde_json::json!(name);
+            label
+        })
+        .collect::<Vec<_>>());
+    Mock::given(method("POST"))
+        .and(path(format!("/repos/{}/issues", TEST_REPO)))
+        .and(body_partial_json(
+            serde_json::json!({"labels": ["roadmap-tier1", "org-gh-managed"]}),
+        ))
+        .respond_with(ResponseTemplate::new(201).set_body_json(&created))
+        .expect(1)
+        .mount(&server)
+        .await;
+
+    let content = format!(
+        r#"#+GH_REPO: {}
+#+GH_LABEL_PREFIX: roadmap-
+#+GH_DEFAULT_LABELS: org-gh-managed
+
+* TODO Brand new roadmap item
+:PROPERTIES:
+:LABELS: tier1
+:END:
+"#,
+        TEST_REPO
+    );
+    let (_dir, mut org_file, mut engine) = engine_for(
+        &server,
+        &content,
+        SyncState::new(TEST_REPO),
+        org_gh::config::Config::default(),
+    )
+    .await;
+
+    let actions = engine
+        .plan(&org_file, true)
+        .await
+        .unwrap()
+        .into_iter()
+        .filter(|a| matches!(a, org_gh::sync::engine::SyncAction::CreateIssue { .. }))
+        .collect();
+    engine.execute(actions, &mut org_file).await.unwrap();
+
+    // The prefix and the default label do not show up as label changes
+    let base = &engine.state().items[&1];
+    assert_eq!(base.labels, vec!["tier1"]);
+    let item = &org_file.items[0];
+    let config = &engine.config().sync;
+    assert!(!org_has_changes(item, base, config));
+    let issue = org_gh::github::model::GhIssue {
+        number: 1,
+        title: "Brand new roadmap item".to_string(),
+        body: None,
+        state: org_gh::github::model::GhIssueState::Open,
+        state_reason: None,
+        assignees: vec![],
+        labels: vec!["roadmap-tier1".to_string(), "org-gh-managed".to_string()],
+        milestone: None,
+        created_at: chrono::Utc::now(),
+        updated_at: chrono::Utc::now(),
+        closed_at: None,
+        html_url: String::new(),
+        comments: 0,
+        is_pull_request: false,
+    };
+    assert_eq!(
+        three_way_diff(item, &issue, base, config).labels,
+        FieldChange::None
+    );
+}
+
+// ============================================================================
+// CLI Init Command Tests
+// ============================================================================
+
+#[tokio::test]
+async fn test_init_validates_repo_format() {
+    use org_gh::cli::init::Args;
 
     let dir = TempDir::new().unwrap();
     let file_path = dir.path().join("test.org");
@@ -1054,6 +4005,52 @@ async fn test_init_file_not_found() {
     assert!(result.is_err());
 }
 
+// ============================================================================
+// CLI Clone Command Tests
+// ============================================================================
+
+#[tokio::test]
+async fn test_clone_validates_repo_format() {
+    use org_gh::cli::clone::Args;
+    use org_gh::sync::import::GroupBy;
+
+    let dir = TempDir::new().unwrap();
+    let args = Args {
+        repo: "invalid-no-slash".to_string(),
+        file: dir.path().join("roadmap.org"),
+        filter: None,
+        closed: false,
+        group_by: GroupBy::Milestone,
+        force: false,
+    };
+
+    let result = org_gh::cli::clone::run(args, Format::Human).await;
+    assert!(result.is_err());
+}
+
+#[tokio::test]
+async fn test_clone_keeps_existing_file() {
+    use org_gh::cli::clone::Args;
+    use org_gh::sync::import::GroupBy;
+
+    let dir = TempDir::new().unwrap();
+    let file_path = dir.path().join("roadmap.org");
+    fs::write(&file_path, "* TODO Mine\n").unwrap();
+
+    let args = Args {
+        repo: "owner/repo".to_string(),
+        file: file_path.clone(),
+        filter: None,
+        closed: false,
+        group_by: GroupBy::Milestone,
+        force: false,
+    };
+
+    let result = org_gh::cli::clone::run(args, Format::Human).await;
+    assert!(result.is_err());
+    assert_eq!(fs::read_to_string(&file_path).unwrap(), "* TODO Mine\n");
+}
+
 // ============================================================================
 // CLI Status Command Tests
 // ============================================================================
@@ -1112,6 +4109,7 @@ async fn test_pull_no_repo() {
         force: false,
         dry_run: false,
         verbose: false,
+        full: false,
     };
 
     let result = org_gh::cli::pull::run(args, Format::Human).await;
@@ -1135,6 +4133,7 @@ async fn test_sync_no_repo() {
         force: false,
         dry_run: false,
         verbose: false,
+        full: false,
     };
 
     let result = org_gh::cli::sync::run(args, Format::Human).await;